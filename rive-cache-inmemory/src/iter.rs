@@ -66,32 +66,32 @@ impl<'a> InMemoryCacheIter<'a> {
     }
 
     /// Create an iterator over the users in the cache.
-    pub fn users(&'a self) -> ResourceIter<Id<UserMarker>, User> {
+    pub fn users(&'a self) -> ResourceIter<'a, Id<UserMarker>, User> {
         ResourceIter::new(self.0.users.iter())
     }
 
     /// Create an iterator over the servers in the cache.
-    pub fn serevrs(&'a self) -> ResourceIter<Id<ServerMarker>, Server> {
+    pub fn serevrs(&'a self) -> ResourceIter<'a, Id<ServerMarker>, Server> {
         ResourceIter::new(self.0.servers.iter())
     }
 
     /// Create an iterator over the channels in the cache.
-    pub fn channels(&'a self) -> ResourceIter<Id<ChannelMarker>, Channel> {
+    pub fn channels(&'a self) -> ResourceIter<'a, Id<ChannelMarker>, Channel> {
         ResourceIter::new(self.0.channels.iter())
     }
 
     /// Create an iterator over the messages in the cache.
-    pub fn messages(&'a self) -> ResourceIter<Id<MessageMarker>, Message> {
+    pub fn messages(&'a self) -> ResourceIter<'a, Id<MessageMarker>, Message> {
         ResourceIter::new(self.0.messages.iter())
     }
 
     /// Create an iterator over the emojis in the cache.
-    pub fn emojis(&'a self) -> ResourceIter<Id<EmojiMarker>, Emoji> {
+    pub fn emojis(&'a self) -> ResourceIter<'a, Id<EmojiMarker>, Emoji> {
         ResourceIter::new(self.0.emojis.iter())
     }
 
     /// Create an iterator over the servers members in the cache.
-    pub fn members(&'a self) -> ResourceIter<MemberCompositeKey, Member> {
+    pub fn members(&'a self) -> ResourceIter<'a, MemberCompositeKey, Member> {
         ResourceIter::new(self.0.members.iter())
     }
 }
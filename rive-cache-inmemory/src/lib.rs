@@ -4,6 +4,7 @@ mod builder;
 mod config;
 mod iter;
 mod patch;
+mod permission;
 mod reference;
 mod remove;
 mod stats;
@@ -13,11 +14,13 @@ mod util;
 pub use builder::InMemoryCacheBuilder;
 pub use config::Config;
 pub use iter::{InMemoryCacheIter, ResourceIter};
+pub use permission::InMemoryCachePermissions;
 pub use reference::IterReference;
 pub use reference::Reference;
 pub use stats::InMemoryCacheStats;
 
 use dashmap::DashMap;
+use permission::PermissionMemo;
 use rive_models::{
     channel::Channel,
     emoji::Emoji,
@@ -45,6 +48,7 @@ pub struct InMemoryCache {
     messages: DashMap<Id<MessageMarker>, Message>,
     emojis: DashMap<Id<EmojiMarker>, Emoji>,
     members: DashMap<MemberCompositeKey, Member>,
+    permissions: PermissionMemo,
 }
 
 impl InMemoryCache {
@@ -80,6 +84,7 @@ impl InMemoryCache {
         self.messages.clear();
         self.emojis.clear();
         self.members.clear();
+        self.permissions.clear();
     }
 
     /// Create an interface for retrieving cache statistics.
@@ -95,7 +100,7 @@ impl InMemoryCache {
     /// let messages = cache.stats().messages();
     /// println!("messages count: {messages}");
     /// ```
-    pub const fn stats(&self) -> InMemoryCacheStats {
+    pub const fn stats(&self) -> InMemoryCacheStats<'_> {
         InMemoryCacheStats::new(self)
     }
 
@@ -114,42 +119,95 @@ impl InMemoryCache {
     ///     println!("{}: {}#{}", user.id, user.username, user.discriminator);
     /// }
     /// ```
-    pub const fn iter(&self) -> InMemoryCacheIter {
+    pub const fn iter(&self) -> InMemoryCacheIter<'_> {
         InMemoryCacheIter::new(self)
     }
 
+    /// Create an interface for calculating effective permissions from the
+    /// cached resources.
+    ///
+    /// Example:
+    ///
+    /// ```no_run
+    /// use rive_cache_inmemory::InMemoryCache;
+    /// use rive_models::id::Id;
+    ///
+    /// let cache = InMemoryCache::new();
+    ///
+    /// // later in the code...
+    /// let user_id = Id::new("01FD58YK5W7QRV5H3D64KTQYX3".to_string());
+    /// let server_id = Id::new("01FD58YK5W7QRV5H3D64KTQYX4".to_string());
+    /// if let Some(permissions) = cache.permissions().in_server(&user_id, &server_id) {
+    ///     println!("permissions: {permissions:?}");
+    /// }
+    /// ```
+    pub const fn permissions(&self) -> InMemoryCachePermissions<'_> {
+        InMemoryCachePermissions::new(self)
+    }
+
     /// Get a user by ID.
-    pub fn user(&self, id: &Id<UserMarker>) -> Option<Reference<Id<UserMarker>, User>> {
+    pub fn user(&self, id: &Id<UserMarker>) -> Option<Reference<'_, Id<UserMarker>, User>> {
         self.users.get(id).map(Reference::new)
     }
 
     /// Get a server by ID.
-    pub fn server(&self, id: &Id<ServerMarker>) -> Option<Reference<Id<ServerMarker>, Server>> {
+    pub fn server(&self, id: &Id<ServerMarker>) -> Option<Reference<'_, Id<ServerMarker>, Server>> {
         self.servers.get(id).map(Reference::new)
     }
 
     /// Get a channel by ID.
-    pub fn channel(&self, id: &Id<ChannelMarker>) -> Option<Reference<Id<ChannelMarker>, Channel>> {
+    pub fn channel(
+        &self,
+        id: &Id<ChannelMarker>,
+    ) -> Option<Reference<'_, Id<ChannelMarker>, Channel>> {
         self.channels.get(id).map(Reference::new)
     }
 
     /// Get a message by ID.
-    pub fn message(&self, id: &Id<MessageMarker>) -> Option<Reference<Id<MessageMarker>, Message>> {
+    pub fn message(
+        &self,
+        id: &Id<MessageMarker>,
+    ) -> Option<Reference<'_, Id<MessageMarker>, Message>> {
         self.messages.get(id).map(Reference::new)
     }
 
     /// Get an emoji by ID.
-    pub fn emoji(&self, id: &Id<EmojiMarker>) -> Option<Reference<Id<EmojiMarker>, Emoji>> {
+    pub fn emoji(&self, id: &Id<EmojiMarker>) -> Option<Reference<'_, Id<EmojiMarker>, Emoji>> {
         self.emojis.get(id).map(Reference::new)
     }
 
     /// Get a member by [`MemberCompositeKey`].
     ///
     /// [`MemberCompositeKey`]: rive_models::member::MemberCompositeKey
-    pub fn member(&self, id: &MemberCompositeKey) -> Option<Reference<MemberCompositeKey, Member>> {
+    pub fn member(
+        &self,
+        id: &MemberCompositeKey,
+    ) -> Option<Reference<'_, MemberCompositeKey, Member>> {
         self.members.get(id).map(Reference::new)
     }
 
+    /// Insert a user retrieved outside of the gateway, e.g. with the REST
+    /// API.
+    ///
+    /// Nothing is inserted if users are not cached.
+    pub fn insert_user(&self, user: User) {
+        if self.config.cache_users {
+            self.users.insert(user.id.clone(), user);
+        }
+    }
+
+    /// Insert a member retrieved outside of the gateway, e.g. with the REST
+    /// API.
+    ///
+    /// Nothing is inserted if members are not cached.
+    pub fn insert_member(&self, member: Member) {
+        if self.config.cache_members {
+            let id = member.id.clone();
+            self.members.insert(id.clone(), member);
+            self.permissions.invalidate_member(&id);
+        }
+    }
+
     /// Update the cache with an incoming event.
     pub fn update(&self, event: &impl CacheUpdate) {
         event.update(self);
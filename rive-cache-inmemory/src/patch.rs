@@ -143,6 +143,7 @@ impl Patch<PartialChannel> for Channel {
                 role_permissions: unwrap(&partial.role_permissions, role_permissions),
                 nsfw: unwrap(&partial.nsfw, nsfw),
             },
+            Self::Unknown(unknown) => Self::Unknown(unknown),
         }
     }
 }
@@ -0,0 +1,374 @@
+use std::{sync::RwLock, time::SystemTime};
+
+use dashmap::DashMap;
+use iso8601_timestamp::Timestamp;
+use rive_models::{
+    channel::Channel,
+    id::{
+        marker::{ChannelMarker, ServerMarker, UserMarker},
+        Id,
+    },
+    member::{Member, MemberCompositeKey},
+    permission::{Permission, PermissionCalculator},
+};
+
+use crate::InMemoryCache;
+
+/// Scope of memoised permissions.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct MemoKey {
+    user: Id<UserMarker>,
+    /// Server of the scope, if it is a server or a server channel.
+    server: Option<Id<ServerMarker>>,
+    channel: Option<Id<ChannelMarker>>,
+}
+
+#[derive(Debug, Clone)]
+struct Memoised {
+    permissions: Permission,
+    /// End of the timeout of the member, if any.
+    expires_at: Option<Timestamp>,
+}
+
+/// Memoised effective permissions.
+///
+/// Every invalidation must happen after the change of the cached data it
+/// accounts for. Permissions calculated while an invalidation happens may be
+/// from the previous data, so they are not memoised: each invalidation bumps
+/// a generation, which is checked before memoising.
+#[derive(Debug, Default)]
+pub(crate) struct PermissionMemo {
+    memoised: DashMap<MemoKey, Memoised>,
+    generation: RwLock<u64>,
+}
+
+impl PermissionMemo {
+    pub fn clear(&self) {
+        self.invalidate(|_| false);
+    }
+
+    /// Forget the permissions in a server and its channels.
+    pub fn invalidate_server(&self, server_id: &Id<ServerMarker>) {
+        self.invalidate(|key| key.server.as_ref() != Some(server_id));
+    }
+
+    /// Forget the permissions in a channel.
+    pub fn invalidate_channel(&self, channel_id: &Id<ChannelMarker>) {
+        self.invalidate(|key| key.channel.as_ref() != Some(channel_id));
+    }
+
+    /// Forget the permissions of a member in a server and its channels.
+    pub fn invalidate_member(&self, id: &MemberCompositeKey) {
+        self.invalidate(|key| key.user != id.user || key.server.as_ref() != Some(&id.server));
+    }
+
+    /// Forget the permissions of a user everywhere.
+    pub fn invalidate_user(&self, user_id: &Id<UserMarker>) {
+        self.invalidate(|key| key.user != *user_id);
+    }
+
+    /// Keep the permissions whose key matches, and prevent the ones being
+    /// calculated from being memoised.
+    fn invalidate(&self, keep: impl Fn(&MemoKey) -> bool) {
+        let mut generation = self.generation.write().expect("permission memo poisoned");
+        *generation += 1;
+        self.memoised.retain(|key, _| keep(key));
+    }
+
+    fn get_or_calculate(
+        &self,
+        key: MemoKey,
+        now: Timestamp,
+        calculate: impl FnOnce() -> Option<(Permission, Option<Timestamp>)>,
+    ) -> Option<Permission> {
+        if let Some(memoised) = self.memoised.get(&key) {
+            if memoised
+                .expires_at
+                .map_or(true, |expires_at| expires_at > now)
+            {
+                return Some(memoised.permissions);
+            }
+        }
+
+        let generation = *self.generation.read().expect("permission memo poisoned");
+        let (permissions, expires_at) = calculate()?;
+
+        // holding the lock keeps invalidations from happening until the
+        // permissions are memoised
+        let current = self.generation.read().expect("permission memo poisoned");
+        if *current == generation {
+            self.memoised.insert(
+                key,
+                Memoised {
+                    permissions,
+                    expires_at,
+                },
+            );
+        }
+
+        Some(permissions)
+    }
+}
+
+/// An interface for calculating effective permissions from the cached
+/// servers, channels and members.
+///
+/// Results are memoised until an event changes the server, channel or member
+/// they depend on, or the timeout of the member ends.
+///
+/// Example:
+///
+/// ```no_run
+/// use rive_cache_inmemory::InMemoryCache;
+/// use rive_models::{id::Id, permission::Permission};
+///
+/// let cache = InMemoryCache::new();
+///
+/// // later in the code...
+/// let user_id = Id::new("01FD58YK5W7QRV5H3D64KTQYX3".to_string());
+/// let channel_id = Id::new("01FD58YK5W7QRV5H3D64KTQYX4".to_string());
+/// let can_send = cache
+///     .permissions()
+///     .in_channel(&user_id, &channel_id)
+///     .map_or(false, |permissions| permissions.contains(Permission::SendMessage));
+/// ```
+#[derive(Clone, Debug)]
+pub struct InMemoryCachePermissions<'a>(&'a InMemoryCache);
+
+impl<'a> InMemoryCachePermissions<'a> {
+    /// Create a new permissions interface instance.
+    pub(super) const fn new(cache: &'a InMemoryCache) -> Self {
+        Self(cache)
+    }
+
+    /// Effective permissions of a user in a server.
+    ///
+    /// Returns [`None`] if the server, or the member if the user is not the
+    /// owner, is not cached.
+    pub fn in_server(
+        &self,
+        user_id: &Id<UserMarker>,
+        server_id: &Id<ServerMarker>,
+    ) -> Option<Permission> {
+        let key = MemoKey {
+            user: user_id.clone(),
+            server: Some(server_id.clone()),
+            channel: None,
+        };
+        let now = Timestamp::from(SystemTime::now());
+
+        self.0.permissions.get_or_calculate(key, now, || {
+            let server = self.0.server(server_id)?;
+            let calculator = PermissionCalculator::new(user_id).server(&server).at(now);
+            if server.owner == *user_id {
+                return Some((calculator.in_server(), None));
+            }
+
+            let member = self.member(server_id, user_id)?;
+            let permissions = calculator.member(&member).in_server();
+
+            Some((permissions, timeout(&member, now)))
+        })
+    }
+
+    /// Effective permissions of a user in a channel.
+    ///
+    /// Returns [`None`] if the channel is not cached, or if it belongs to a
+    /// server and the server, or the member if the user is not the owner, is
+    /// not cached.
+    pub fn in_channel(
+        &self,
+        user_id: &Id<UserMarker>,
+        channel_id: &Id<ChannelMarker>,
+    ) -> Option<Permission> {
+        let channel = self.0.channel(channel_id)?.clone();
+        let server_id = match &channel {
+            Channel::TextChannel { server, .. } => Some(Id::new(server.clone())),
+            Channel::VoiceChannel { server, .. } => Some(server.clone()),
+            _ => None,
+        };
+        let key = MemoKey {
+            user: user_id.clone(),
+            server: server_id.clone(),
+            channel: Some(channel_id.clone()),
+        };
+        let now = Timestamp::from(SystemTime::now());
+
+        self.0.permissions.get_or_calculate(key, now, || {
+            let calculator = PermissionCalculator::new(user_id).at(now);
+            let Some(server_id) = server_id else {
+                return Some((calculator.in_channel(&channel), None));
+            };
+
+            let server = self.0.server(&server_id)?;
+            let calculator = calculator.server(&server);
+            if server.owner == *user_id {
+                return Some((calculator.in_channel(&channel), None));
+            }
+
+            let member = self.member(&server_id, user_id)?;
+            let permissions = calculator.member(&member).in_channel(&channel);
+
+            Some((permissions, timeout(&member, now)))
+        })
+    }
+
+    fn member(&self, server_id: &Id<ServerMarker>, user_id: &Id<UserMarker>) -> Option<Member> {
+        self.0
+            .member(&MemberCompositeKey {
+                server: server_id.clone(),
+                user: user_id.clone(),
+            })
+            .map(|member| member.clone())
+    }
+}
+
+/// End of the timeout of a member, if they are in timeout.
+fn timeout(member: &Member, now: Timestamp) -> Option<Timestamp> {
+    member.timeout.filter(|timeout| *timeout > now)
+}
+
+#[cfg(test)]
+mod tests {
+    use rive_models::{
+        event::{ServerCreateEvent, ServerMemberUpdateEvent, ServerRoleUpdateEvent},
+        id::Id,
+        member::{Member, MemberCompositeKey},
+        permission::Permission,
+    };
+    use serde_json::json;
+
+    use super::*;
+    use crate::InMemoryCache;
+
+    /// A cache with a server and a member in it.
+    fn cache() -> InMemoryCache {
+        let cache = InMemoryCache::new();
+        let event: ServerCreateEvent = serde_json::from_value(json!({
+            "id": "server",
+            "server": {
+                "_id": "server",
+                "owner": "owner",
+                "name": "Server",
+                "channels": ["channel"],
+                "roles": {
+                    "muted": { "name": "Muted", "permissions": { "a": 0, "d": 0 } },
+                },
+                "default_permissions": Permission::DEFAULT_SERVER.bits(),
+            },
+            "channels": [{
+                "channel_type": "TextChannel",
+                "_id": "channel",
+                "server": "server",
+                "name": "general",
+            }],
+        }))
+        .unwrap();
+        let member: Member = serde_json::from_value(json!({
+            "_id": { "server": "server", "user": "member" },
+            "joined_at": "2023-01-01T00:00:00Z",
+        }))
+        .unwrap();
+        cache.update(&event);
+        cache.insert_member(member);
+
+        cache
+    }
+
+    #[test]
+    fn memoised_until_invalidated() {
+        let cache = cache();
+
+        let user_id = Id::new("member".to_string());
+        let server_id = Id::new("server".to_string());
+        let channel_id = Id::new("channel".to_string());
+        let permissions = cache.permissions();
+        assert_eq!(
+            permissions.in_server(&user_id, &server_id),
+            Some(Permission::DEFAULT_SERVER)
+        );
+        assert_eq!(
+            permissions.in_channel(&user_id, &channel_id),
+            Some(Permission::DEFAULT_SERVER)
+        );
+        assert_eq!(
+            permissions.in_server(&Id::new("owner".to_string()), &server_id),
+            Some(Permission::all())
+        );
+        assert_eq!(
+            permissions.in_server(&Id::new("stranger".to_string()), &server_id),
+            None
+        );
+
+        let event: ServerMemberUpdateEvent = serde_json::from_value(json!({
+            "id": { "server": "server", "user": "member" },
+            "data": { "roles": ["muted"] },
+            "clear": [],
+        }))
+        .unwrap();
+        cache.update(&event);
+
+        let event: ServerRoleUpdateEvent = serde_json::from_value(json!({
+            "id": "server",
+            "role_id": "muted",
+            "data": { "permissions": { "a": 0, "d": Permission::SendMessage.bits() } },
+            "clear": [],
+        }))
+        .unwrap();
+        cache.update(&event);
+
+        let expected = Permission::DEFAULT_SERVER - Permission::SendMessage;
+        assert_eq!(permissions.in_server(&user_id, &server_id), Some(expected));
+        assert_eq!(
+            permissions.in_channel(&user_id, &channel_id),
+            Some(expected)
+        );
+
+        let id = MemberCompositeKey {
+            server: server_id,
+            user: user_id,
+        };
+        assert!(cache.member(&id).is_some());
+    }
+
+    #[test]
+    fn stale_calculations_are_not_memoised() {
+        let cache = cache();
+        let user_id = Id::new("member".to_string());
+        let server_id = Id::new("server".to_string());
+        let key = MemoKey {
+            user: user_id.clone(),
+            server: Some(server_id.clone()),
+            channel: None,
+        };
+        let now = Timestamp::from(SystemTime::now());
+
+        // the member is updated after the permissions are calculated from
+        // their old roles, but before they are memoised
+        let permissions = cache.permissions.get_or_calculate(key, now, || {
+            let event: ServerMemberUpdateEvent = serde_json::from_value(json!({
+                "id": { "server": "server", "user": "member" },
+                "data": { "roles": ["muted"] },
+                "clear": [],
+            }))
+            .unwrap();
+            let role_event: ServerRoleUpdateEvent = serde_json::from_value(json!({
+                "id": "server",
+                "role_id": "muted",
+                "data": { "permissions": { "a": 0, "d": Permission::SendMessage.bits() } },
+                "clear": [],
+            }))
+            .unwrap();
+            cache.update(&event);
+            cache.update(&role_event);
+
+            Some((Permission::DEFAULT_SERVER, None))
+        });
+        assert_eq!(permissions, Some(Permission::DEFAULT_SERVER));
+
+        assert_eq!(
+            cache.permissions().in_server(&user_id, &server_id),
+            Some(Permission::DEFAULT_SERVER - Permission::SendMessage)
+        );
+    }
+}
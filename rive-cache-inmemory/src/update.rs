@@ -92,9 +92,9 @@ impl CacheUpdate for ReadyEvent {
 
         if cache.config.cache_channels {
             for channel in &self.channels {
-                cache
-                    .channels
-                    .insert(channel_id(channel).clone(), channel.clone());
+                if let Some(id) = channel_id(channel) {
+                    cache.channels.insert(id.clone(), channel.clone());
+                }
             }
         }
 
@@ -111,6 +111,8 @@ impl CacheUpdate for ReadyEvent {
                 cache.members.insert(member.id.clone(), member.clone());
             }
         }
+
+        cache.permissions.clear();
     }
 }
 
@@ -138,26 +140,26 @@ impl CacheUpdate for ServerCreateEvent {
 
         cache.servers.insert(self.id.clone(), self.server.clone());
         for channel in &self.channels {
-            cache
-                .channels
-                .insert(channel_id(channel).clone(), channel.clone());
+            if let Some(id) = channel_id(channel) {
+                cache.channels.insert(id.clone(), channel.clone());
+            }
         }
     }
 }
 
 impl CacheUpdate for ServerUpdateEvent {
     fn update(&self, cache: &InMemoryCache) {
-        if !cache.config.cache_servers {
-            return;
+        // permissions are invalidated after the change, so that they are not
+        // calculated again from the previous state
+        if cache.config.cache_servers {
+            let server = cache.server(&self.id).map(|server| server.clone());
+            if let Some(server) = server {
+                let new_server = update_fields(server, &self.data, &self.clear);
+                cache.servers.insert(new_server.id.clone(), new_server);
+            }
         }
 
-        let server = match cache.server(&self.id) {
-            Some(server) => server.clone(),
-            None => return,
-        };
-        let new_server = update_fields(server, &self.data, &self.clear);
-
-        cache.servers.insert(new_server.id.clone(), new_server);
+        cache.permissions.invalidate_server(&self.id);
     }
 }
 
@@ -166,34 +168,35 @@ impl CacheUpdate for ServerDeleteEvent {
         if cache.config.cache_servers {
             cache.servers.remove(&self.id);
         }
+        cache.permissions.invalidate_server(&self.id);
     }
 }
 
 impl CacheUpdate for Channel {
     fn update(&self, cache: &InMemoryCache) {
+        // unknown channels are not cached
+        let Some(id) = channel_id(self) else {
+            return;
+        };
+
         if cache.config.cache_channels {
-            cache
-                .channels
-                .insert(channel_id(self).clone(), self.clone());
+            cache.channels.insert(id.clone(), self.clone());
         }
+        cache.permissions.invalidate_channel(id);
     }
 }
 
 impl CacheUpdate for ChannelUpdateEvent {
     fn update(&self, cache: &InMemoryCache) {
-        if !cache.config.cache_channels {
-            return;
+        if cache.config.cache_channels {
+            let channel = cache.channel(&self.id).map(|channel| channel.clone());
+            if let Some(channel) = channel {
+                let new_channel = update_fields(channel, &self.data, &self.clear);
+                cache.channels.insert(self.id.clone(), new_channel);
+            }
         }
 
-        let channel = match cache.channel(&self.id) {
-            Some(channel) => channel.clone(),
-            None => return,
-        };
-        let new_channel = update_fields(channel, &self.data, &self.clear);
-
-        cache
-            .channels
-            .insert(channel_id(&new_channel).clone(), new_channel);
+        cache.permissions.invalidate_channel(&self.id);
     }
 }
 
@@ -202,6 +205,7 @@ impl CacheUpdate for ChannelDeleteEvent {
         if cache.config.cache_channels {
             cache.channels.remove(&self.id);
         }
+        cache.permissions.invalidate_channel(&self.id);
     }
 }
 
@@ -360,91 +364,85 @@ impl CacheUpdate for EmojiDeleteEvent {
 
 impl CacheUpdate for ServerMemberJoinEvent {
     fn update(&self, cache: &InMemoryCache) {
-        if !cache.config.cache_members {
-            return;
-        }
-
         let id = MemberCompositeKey {
             server: self.id.clone(),
             user: self.user.clone(),
         };
-        let member = Member {
-            id: id.clone(),
-            // TODO: should it be like that?
-            joined_at: SystemTime::now().into(),
-            nickname: Default::default(),
-            avatar: Default::default(),
-            roles: Default::default(),
-            timeout: Default::default(),
-        };
 
-        cache.members.insert(id, member);
+        if cache.config.cache_members {
+            let member = Member {
+                id: id.clone(),
+                // TODO: should it be like that?
+                joined_at: SystemTime::now().into(),
+                nickname: Default::default(),
+                avatar: Default::default(),
+                roles: Default::default(),
+                timeout: Default::default(),
+            };
+
+            cache.members.insert(id.clone(), member);
+        }
+
+        cache.permissions.invalidate_member(&id);
     }
 }
 
 impl CacheUpdate for ServerMemberUpdateEvent {
     fn update(&self, cache: &InMemoryCache) {
-        if !cache.config.cache_members {
-            return;
+        if cache.config.cache_members {
+            let member = cache.member(&self.id).map(|member| member.clone());
+            if let Some(member) = member {
+                let new_member = update_fields(member, &self.data, &self.clear);
+                cache.members.insert(new_member.id.clone(), new_member);
+            }
         }
 
-        let member = match cache.member(&self.id) {
-            Some(channel) => channel.clone(),
-            None => return,
-        };
-        let new_member = update_fields(member, &self.data, &self.clear);
-
-        cache.members.insert(new_member.id.clone(), new_member);
+        cache.permissions.invalidate_member(&self.id);
     }
 }
 
 impl CacheUpdate for ServerMemberLeaveEvent {
     fn update(&self, cache: &InMemoryCache) {
-        if !cache.config.cache_members {
-            cache.members.remove(&MemberCompositeKey {
-                server: self.id.clone(),
-                user: self.user.clone(),
-            });
+        let id = MemberCompositeKey {
+            server: self.id.clone(),
+            user: self.user.clone(),
+        };
+        if cache.config.cache_members {
+            cache.members.remove(&id);
         }
+        cache.permissions.invalidate_member(&id);
     }
 }
 
 impl CacheUpdate for ServerRoleUpdateEvent {
     fn update(&self, cache: &InMemoryCache) {
-        if !cache.config.cache_servers {
-            return;
-        }
-
-        let mut server = match cache.server(&self.id) {
-            Some(server) => server.clone(),
-            None => return,
-        };
-        let role = match server.roles.get(&self.role_id) {
-            Some(role) => role.clone(),
-            None => return,
-        };
+        if cache.config.cache_servers {
+            let server = cache.server(&self.id).map(|server| server.clone());
+            if let Some(mut server) = server {
+                if let Some(role) = server.roles.get(&self.role_id).cloned() {
+                    let new_role = update_fields(role, &self.data, &self.clear);
+                    server.roles.insert(self.role_id.clone(), new_role);
 
-        let new_role = update_fields(role, &self.data, &self.clear);
-        server.roles.insert(self.role_id.clone(), new_role);
+                    cache.servers.insert(self.id.clone(), server);
+                }
+            }
+        }
 
-        cache.servers.insert(self.id.clone(), server);
+        cache.permissions.invalidate_server(&self.id);
     }
 }
 
 impl CacheUpdate for ServerRoleDeleteEvent {
     fn update(&self, cache: &InMemoryCache) {
-        if !cache.config.cache_servers {
-            return;
+        if cache.config.cache_servers {
+            let server = cache.server(&self.id).map(|server| server.clone());
+            if let Some(mut server) = server {
+                server.roles.remove(&self.role_id);
+                cache.servers.insert(self.id.clone(), server);
+            }
         }
 
-        let mut server = match cache.server(&self.id) {
-            Some(server) => server.clone(),
-            None => return,
-        };
-
-        server.roles.remove(&self.role_id);
-
-        cache.servers.insert(self.id.clone(), server);
+        cache.permissions.invalidate_server(&self.id);
     }
 }
 
@@ -467,6 +465,7 @@ impl CacheUpdate for UserPlatformWipeEvent {
                 _ => true,
             });
         }
+        cache.permissions.invalidate_user(&self.user_id);
 
         if !cache.config.cache_users {
             return;
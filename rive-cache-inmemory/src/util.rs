@@ -5,14 +5,17 @@ use rive_models::{
 
 /// Get an ID of a given [`Channel`].
 ///
+/// Returns [`None`] if the channel is unknown to this crate.
+///
 /// [`Channel`]: rive_models::channel::Channel
 // TODO: move it to rive_models
-pub const fn channel_id(channel: &Channel) -> &Id<ChannelMarker> {
+pub const fn channel_id(channel: &Channel) -> Option<&Id<ChannelMarker>> {
     match channel {
-        Channel::SavedMessages { id, .. } => id,
-        Channel::DirectMessage { id, .. } => id,
-        Channel::Group { id, .. } => id,
-        Channel::TextChannel { id, .. } => id,
-        Channel::VoiceChannel { id, .. } => id,
+        Channel::SavedMessages { id, .. } => Some(id),
+        Channel::DirectMessage { id, .. } => Some(id),
+        Channel::Group { id, .. } => Some(id),
+        Channel::TextChannel { id, .. } => Some(id),
+        Channel::VoiceChannel { id, .. } => Some(id),
+        Channel::Unknown(_) => None,
     }
 }
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use hyper::body::Bytes;
 use serde::Serialize;
 
@@ -18,6 +20,8 @@ impl RequestBuilder {
             inner: Ok(Request {
                 method: "GET",
                 path: String::new(),
+                bucket: None,
+                timeout: None,
                 body: Bytes::new(),
             }),
         }
@@ -31,6 +35,7 @@ impl RequestBuilder {
         self.and_then(move |mut request| {
             request.method = route.method();
             request.path = route.to_string();
+            request.bucket = Some(route.bucket());
             Ok(request)
         })
     }
@@ -48,6 +53,14 @@ impl RequestBuilder {
         })
     }
 
+    /// Override the client-wide timeout for this request.
+    pub fn timeout(self, timeout: Duration) -> Self {
+        self.and_then(move |mut request| {
+            request.timeout = Some(timeout);
+            Ok(request)
+        })
+    }
+
     pub fn build(self) -> Result<Request> {
         self.inner
     }
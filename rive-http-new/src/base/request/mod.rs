@@ -2,7 +2,9 @@ mod builder;
 mod route;
 
 pub use builder::RequestBuilder;
-pub use route::Route;
+pub use route::{Bucket, Route, RouteInfo};
+
+use std::time::Duration;
 
 use crate::Result;
 use hyper::body::Bytes;
@@ -11,6 +13,8 @@ use hyper::body::Bytes;
 pub struct Request {
     pub(crate) method: &'static str,
     pub(crate) path: String,
+    pub(crate) bucket: Option<String>,
+    pub(crate) timeout: Option<Duration>,
     pub(crate) body: Bytes,
 }
 
@@ -23,3 +27,9 @@ impl Request {
 pub trait TryIntoRequest: Send {
     fn try_into_request(self) -> Result<Request>;
 }
+
+impl TryIntoRequest for Request {
+    fn try_into_request(self) -> Result<Request> {
+        Ok(self)
+    }
+}
@@ -1,16 +1,128 @@
 use std::fmt::{self, Display};
 
+use rive_models::permission::Permission;
+
 #[derive(Debug, Clone, Copy)]
 pub enum Route<'a> {
     SendMessage { channel_id: &'a str },
 }
 
+/// Ratelimit bucket a route is counted against by the API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Bucket {
+    /// Fetching users and relationships.
+    Users,
+    /// Editing the current user.
+    UserEdit,
+    /// Bot management.
+    Bots,
+    /// Channel management.
+    Channels,
+    /// Sending messages, counted per channel.
+    Messaging,
+    /// Server management.
+    Servers,
+    /// Authentication.
+    Auth,
+    /// Deleting sessions and accounts.
+    AuthDelete,
+    /// Platform safety.
+    Safety,
+    /// Reporting content.
+    SafetyReport,
+    /// Any other route.
+    Any,
+}
+
+impl Bucket {
+    /// Name of the bucket as returned in the ratelimit headers.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Users => "users",
+            Self::UserEdit => "user_edit",
+            Self::Bots => "bots",
+            Self::Channels => "channels",
+            Self::Messaging => "messaging",
+            Self::Servers => "servers",
+            Self::Auth => "auth",
+            Self::AuthDelete => "auth_delete",
+            Self::Safety => "safety",
+            Self::SafetyReport => "safety_report",
+            Self::Any => "any",
+        }
+    }
+}
+
+/// Metadata of a [`Route`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RouteInfo {
+    /// Ratelimit bucket of the route.
+    pub bucket: Bucket,
+    /// Whether sending the same request several times has the same effect as
+    /// sending it once.
+    pub idempotent: bool,
+    /// Whether the route requires an authenticated session or bot.
+    pub authenticated: bool,
+    /// Permission usually required to use the route, if any.
+    pub permission: Option<Permission>,
+}
+
 impl<'a> Route<'a> {
     pub const fn method(&self) -> &'static str {
         match self {
             Route::SendMessage { .. } => "POST",
         }
     }
+
+    /// Metadata of this route.
+    pub const fn info(&self) -> RouteInfo {
+        match self {
+            Route::SendMessage { .. } => RouteInfo {
+                bucket: Bucket::Messaging,
+                idempotent: false,
+                authenticated: true,
+                permission: Some(Permission::SendMessage),
+            },
+        }
+    }
+
+    /// Key of the ratelimit bucket this route is counted against.
+    ///
+    /// Buckets counted per resource, such as [`Bucket::Messaging`], are
+    /// suffixed with the resource ID.
+    pub fn bucket(&self) -> String {
+        let bucket = self.info().bucket;
+
+        match (bucket, self.major_parameter()) {
+            (Bucket::Messaging, Some(id)) => format!("{}:{id}", bucket.name()),
+            _ => bucket.name().to_string(),
+        }
+    }
+
+    /// Whether this route is idempotent.
+    pub const fn is_idempotent(&self) -> bool {
+        self.info().idempotent
+    }
+
+    /// Whether this route requires authentication.
+    pub const fn requires_authentication(&self) -> bool {
+        self.info().authenticated
+    }
+
+    /// Permission usually required to use this route, if any.
+    pub const fn permission(&self) -> Option<Permission> {
+        self.info().permission
+    }
+
+    /// ID of the resource the route operates on, used to separate buckets
+    /// counted per resource.
+    const fn major_parameter(&self) -> Option<&'a str> {
+        match self {
+            Route::SendMessage { channel_id } => Some(channel_id),
+        }
+    }
 }
 
 impl<'a> fmt::Display for Route<'a> {
@@ -24,3 +136,79 @@ impl<'a> fmt::Display for Route<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rive_models::permission::Permission;
+
+    use super::{Bucket, Route, RouteInfo};
+    use crate::base::request::Request;
+
+    const CHANNEL_ID: &str = "01FFD06NDVZ14W5T1WKKB4KKZX";
+
+    /// Test the metadata of sending a message.
+    #[test]
+    fn send_message() {
+        let route = Route::SendMessage {
+            channel_id: CHANNEL_ID,
+        };
+
+        assert_eq!(route.method(), "POST");
+        assert_eq!(
+            route.to_string(),
+            format!("/channels/{CHANNEL_ID}/messages")
+        );
+        assert_eq!(
+            route.info(),
+            RouteInfo {
+                bucket: Bucket::Messaging,
+                idempotent: false,
+                authenticated: true,
+                permission: Some(Permission::SendMessage),
+            }
+        );
+        assert!(!route.is_idempotent());
+        assert!(route.requires_authentication());
+        assert_eq!(route.permission(), Some(Permission::SendMessage));
+    }
+
+    /// Test that buckets counted per resource are keyed by the major
+    /// parameter of the route.
+    #[test]
+    fn bucket_key() {
+        let route = Route::SendMessage {
+            channel_id: CHANNEL_ID,
+        };
+        let other = Route::SendMessage {
+            channel_id: "01FFD06NDVZ14W5T1WKKB4KKZY",
+        };
+
+        assert_eq!(route.major_parameter(), Some(CHANNEL_ID));
+        assert_eq!(route.bucket(), format!("messaging:{CHANNEL_ID}"));
+        assert_ne!(route.bucket(), other.bucket());
+
+        let request = Request::builder().route(route).build().unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.bucket, Some(route.bucket()));
+    }
+
+    /// Test that bucket names match those of the ratelimit headers.
+    #[test]
+    fn bucket_names() {
+        for (bucket, name) in [
+            (Bucket::Users, "users"),
+            (Bucket::UserEdit, "user_edit"),
+            (Bucket::Bots, "bots"),
+            (Bucket::Channels, "channels"),
+            (Bucket::Messaging, "messaging"),
+            (Bucket::Servers, "servers"),
+            (Bucket::Auth, "auth"),
+            (Bucket::AuthDelete, "auth_delete"),
+            (Bucket::Safety, "safety"),
+            (Bucket::SafetyReport, "safety_report"),
+            (Bucket::Any, "any"),
+        ] {
+            assert_eq!(bucket.name(), name);
+        }
+    }
+}
@@ -1,28 +1,89 @@
+use std::marker::PhantomData;
+
+use http::HeaderMap;
 use http_body_util::BodyExt as _;
-use hyper::body::Incoming;
+use hyper::body::{Bytes, Incoming};
 use serde::de::DeserializeOwned;
+use tokio::time::Instant;
+
+use crate::{
+    error::{Error, ErrorKind},
+    ratelimiter::RatelimitHeaders,
+    Result,
+};
 
+/// Response of a request, deserializable into a model of type `T`.
+///
+/// The body is not received until one of [`bytes`], [`text`] or [`model`] is
+/// called, so the response of a request may be dropped without paying for
+/// its body or deserialization.
+///
+/// The body has to be received before the timeout of the request elapses,
+/// like the headers.
+///
+/// [`bytes`]: Response::bytes
+/// [`text`]: Response::text
+/// [`model`]: Response::model
 #[derive(Debug)]
-pub struct Response {
+pub struct Response<T> {
     inner: http::Response<Incoming>,
+    deadline: Option<Instant>,
+    phantom: PhantomData<fn() -> T>,
 }
 
-impl Response {
+impl<T> Response<T> {
+    pub(crate) const fn new(inner: http::Response<Incoming>, deadline: Option<Instant>) -> Self {
+        Self {
+            inner,
+            deadline,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Status code of the response.
     pub fn status(&self) -> u16 {
         self.inner.status().as_u16()
     }
 
-    pub async fn json<T>(self) -> T
-    where
-        T: DeserializeOwned,
-    {
-        let bytes = self.inner.into_body().collect().await.unwrap().to_bytes();
-        serde_json::from_slice(&bytes).unwrap()
+    /// Headers of the response.
+    pub fn headers(&self) -> &HeaderMap {
+        self.inner.headers()
+    }
+
+    /// Ratelimit information of the response, if present.
+    pub fn ratelimit(&self) -> Option<RatelimitHeaders> {
+        RatelimitHeaders::from_headers(self.headers())
+    }
+
+    /// Receive the raw body of the response.
+    pub async fn bytes(self) -> Result<Bytes> {
+        let body = self.inner.into_body().collect();
+        let body = match self.deadline {
+            Some(deadline) => tokio::time::timeout_at(deadline, body)
+                .await
+                .map_err(|source| Error::new(ErrorKind::Timeout, Some(Box::new(source))))?,
+            None => body.await,
+        };
+
+        body.map(|body| body.to_bytes())
+            .map_err(|source| Error::new(ErrorKind::ReceivingBody, Some(Box::new(source))))
+    }
+
+    /// Receive the body of the response as a string.
+    pub async fn text(self) -> Result<String> {
+        let bytes = self.bytes().await?;
+
+        String::from_utf8(bytes.to_vec())
+            .map_err(|source| Error::new(ErrorKind::Deserializing, Some(Box::new(source))))
     }
 }
 
-impl From<http::Response<Incoming>> for Response {
-    fn from(value: http::Response<Incoming>) -> Self {
-        Self { inner: value }
+impl<T: DeserializeOwned> Response<T> {
+    /// Receive the body of the response and deserialize it into the model.
+    pub async fn model(self) -> Result<T> {
+        let bytes = self.bytes().await?;
+
+        serde_json::from_slice(&bytes)
+            .map_err(|source| Error::new(ErrorKind::Deserializing, Some(Box::new(source))))
     }
 }
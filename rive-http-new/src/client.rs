@@ -1,20 +1,44 @@
+use std::future::Future;
+
 use http_body_util::Full;
-use hyper::body::Bytes;
-use hyper_util::{client::legacy::Client as HyperClient, rt::TokioExecutor};
+use hyper::body::{Bytes, Incoming};
+use hyper_util::{
+    client::legacy::{connect::Connect, Client as HyperClient},
+    rt::{TokioExecutor, TokioTimer},
+};
 use rive_models::{
     authentication::Authentication,
     id::{marker::ChannelMarker, Id},
+    validate::Validate,
 };
-use serde::de::DeserializeOwned;
+use tokio::time::Instant;
+use tracing::warn;
 
 use crate::{
     base::{request::TryIntoRequest, response::Response},
     connector::{create_connector, Connector},
     error::{Error, ErrorKind},
+    ratelimiter::RatelimitHeaders,
     request::channels::messaging::SendMessageRequest,
-    Config, Result, BASE_URL,
+    Config, Result,
 };
 
+/// Client for the Revolt REST API.
+///
+/// The client keeps a pool of connections, so it should be created once and
+/// shared, e.g. in an [`Arc`], instead of being created for every request.
+///
+/// Once a response body has been fully received, or the response has been
+/// dropped, its connection returns to the pool and is reused by the next
+/// request to the same host. Idle connections are closed after
+/// [`Config::pool_idle_timeout`] and at most [`Config::pool_max_idle_per_host`]
+/// of them are kept.
+///
+/// With the `http2` feature and a `rustls` TLS backend, HTTP/2 is negotiated
+/// with the server, in which case concurrent requests are multiplexed over a
+/// single connection.
+///
+/// [`Arc`]: std::sync::Arc
 #[derive(Debug)]
 pub struct Client {
     config: Config,
@@ -22,16 +46,33 @@ pub struct Client {
 }
 
 impl Client {
-    pub(crate) async fn execute(&self, request: impl TryIntoRequest) -> Result<Response> {
+    pub(crate) async fn execute(
+        &self,
+        request: impl TryIntoRequest,
+    ) -> Result<http::Response<Incoming>> {
         let request = request.try_into_request()?;
 
-        let request = http::Request::builder()
+        if let (Some(ratelimiter), Some(bucket)) = (&self.config.ratelimiter, &request.bucket) {
+            ratelimiter
+                .acquire(bucket)
+                .await
+                .map_err(|source| Error::new(ErrorKind::Ratelimiting, Some(source)))?;
+        }
+
+        let bucket = request.bucket;
+        let mut builder = http::Request::builder()
             .method(request.method)
-            .uri(self.config.base_url.clone() + &request.path)
-            .header(
+            .uri(self.config.base_url.clone() + &request.path);
+        if !matches!(self.config.authentication, Authentication::None) {
+            builder = builder.header(
                 self.config.authentication.header_key(),
                 self.config.authentication.value(),
-            )
+            );
+        }
+        if let Some(ticket) = &self.config.mfa_ticket {
+            builder = builder.header("x-mfa-ticket", ticket);
+        }
+        let request = builder
             .body::<Full<Bytes>>(request.body.into())
             .map_err(|source| Error {
                 kind: ErrorKind::SendingRequest,
@@ -43,48 +84,74 @@ impl Client {
             source: Some(Box::new(source)),
         })?;
 
-        Ok(response.into())
+        if let (Some(ratelimiter), Some(bucket)) = (&self.config.ratelimiter, &bucket) {
+            if let Some(headers) = RatelimitHeaders::from_headers(response.headers()) {
+                // the request went through, so failing it now would make
+                // callers retry requests that already had an effect
+                if let Err(source) = ratelimiter.update(bucket, headers).await {
+                    warn!(%bucket, error = %source, "failed to update ratelimiter");
+                }
+            }
+        }
+
+        Ok(response)
     }
 
-    pub(crate) async fn fire<T>(&self, request: impl TryIntoRequest) -> Result<T>
-    where
-        T: DeserializeOwned,
-    {
-        let response = self.execute(request).await?;
+    pub(crate) async fn fire<T>(&self, request: impl TryIntoRequest) -> Result<Response<T>> {
+        let request = request.try_into_request()?;
+        let deadline = request
+            .timeout
+            .or(self.config.timeout)
+            .map(|timeout| Instant::now() + timeout);
+
+        let response = with_deadline(deadline, self.execute(request)).await?;
+        let response = Response::<T>::new(response, deadline);
 
         if response.status() >= 200 && response.status() < 300 {
-            let json = response.json::<T>().await;
-            Ok(json)
+            Ok(response)
         } else {
-            Err(Error::new(ErrorKind::Api, None))
+            let status = response.status();
+            let body = response.bytes().await?;
+
+            Err(Error::new(ErrorKind::Api { status, body }, None))
         }
     }
+}
 
-    // TODO: remove this attribute when it will be used
-    #[allow(dead_code)]
-    pub(crate) async fn wind(&self, request: impl TryIntoRequest) -> Result<()> {
-        let response = self.execute(request).await?;
+/// Build the underlying HTTP client with the pool settings of the config.
+fn build_client<C>(config: &Config, connector: C) -> HyperClient<C, Full<Bytes>>
+where
+    C: Connect + Clone + Send + Sync + 'static,
+{
+    HyperClient::builder(TokioExecutor::new())
+        .pool_timer(TokioTimer::new())
+        .pool_idle_timeout(config.pool_idle_timeout)
+        .pool_max_idle_per_host(config.pool_max_idle_per_host)
+        .build(connector)
+}
 
-        if response.status() >= 200 && response.status() < 300 {
-            Ok(())
-        } else {
-            Err(Error::new(ErrorKind::Api, None))
-        }
+/// Run the future, failing with [`ErrorKind::Timeout`] if it does not complete
+/// before the deadline.
+async fn with_deadline<T>(
+    deadline: Option<Instant>,
+    future: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    match deadline {
+        Some(deadline) => tokio::time::timeout_at(deadline, future)
+            .await
+            .map_err(|source| Error::new(ErrorKind::Timeout, Some(Box::new(source))))?,
+        None => future.await,
     }
 }
 
 impl Client {
     pub fn new(authentication: Authentication) -> Self {
-        Self::with_config(Config {
-            authentication,
-            base_url: BASE_URL.to_string(),
-        })
+        Self::with_config(Config::new(authentication))
     }
 
     pub fn with_config(config: Config) -> Self {
-        let connector = create_connector();
-        let executor = TokioExecutor::new();
-        let client = HyperClient::builder(executor).build(connector);
+        let connector = create_connector(&config);
+        let client = build_client(&config, connector);
 
         Self { config, client }
     }
@@ -95,4 +162,359 @@ impl Client {
     ) -> SendMessageRequest<'a> {
         SendMessageRequest::new(self, channel_id)
     }
+
+    /// Fail if the payload exceeds a limit of the API.
+    pub(crate) fn validate(&self, data: &impl Validate) -> Result<()> {
+        if self.config.validate_payloads {
+            data.validate()
+                .map_err(|source| Error::new(ErrorKind::Validation, Some(Box::new(source))))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(all(
+    test,
+    any(feature = "rustls-native-roots", feature = "rustls-webpki-roots")
+))]
+mod tests {
+    use std::{
+        convert::Infallible,
+        future::IntoFuture as _,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use http_body_util::{BodyExt as _, Full};
+    use hyper::{
+        body::{Bytes, Incoming},
+        service::service_fn,
+    };
+    use hyper_rustls::HttpsConnector;
+    use hyper_util::{
+        client::legacy::{connect::HttpConnector, Client as HyperClient},
+        rt::{TokioExecutor, TokioIo},
+        server::conn::auto,
+    };
+    use rive_models::{authentication::Authentication, id::Id};
+    use tokio::{
+        io::{AsyncReadExt as _, AsyncWriteExt as _},
+        net::TcpListener,
+    };
+    use tokio_rustls::{
+        rustls::{
+            crypto::ring,
+            pki_types::{PrivateKeyDer, PrivatePkcs8KeyDer},
+            ClientConfig, RootCertStore, ServerConfig,
+        },
+        TlsAcceptor,
+    };
+
+    use super::build_client;
+    use crate::{error::ErrorKind, Client, Config};
+
+    type TestClient = HyperClient<HttpsConnector<HttpConnector>, Full<Bytes>>;
+
+    /// A local HTTPS server standing in for the API.
+    struct StandIn {
+        url: String,
+        connections: Arc<AtomicUsize>,
+        tls: ClientConfig,
+    }
+
+    impl StandIn {
+        /// Start a server with a self-signed certificate, offering the given
+        /// protocols via ALPN.
+        async fn start(alpn_protocols: &[&[u8]]) -> Self {
+            let certified = rcgen::generate_simple_self_signed(vec!["localhost".to_string()])
+                .expect("failed to generate certificate");
+            let cert = certified.cert.der().clone();
+            let key =
+                PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(certified.key_pair.serialize_der()));
+
+            let mut server_config =
+                ServerConfig::builder_with_provider(Arc::new(ring::default_provider()))
+                    .with_safe_default_protocol_versions()
+                    .unwrap()
+                    .with_no_client_auth()
+                    .with_single_cert(vec![cert.clone()], key)
+                    .unwrap();
+            server_config.alpn_protocols = alpn_protocols.iter().map(|p| p.to_vec()).collect();
+            let acceptor = TlsAcceptor::from(Arc::new(server_config));
+
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let port = listener.local_addr().unwrap().port();
+            let connections = Arc::new(AtomicUsize::new(0));
+
+            let counter = Arc::clone(&connections);
+            tokio::spawn(async move {
+                loop {
+                    let (stream, _) = listener.accept().await.unwrap();
+                    counter.fetch_add(1, Ordering::SeqCst);
+
+                    let acceptor = acceptor.clone();
+                    tokio::spawn(async move {
+                        let stream = acceptor.accept(stream).await.unwrap();
+                        let service = service_fn(|_| async {
+                            Ok::<_, Infallible>(http::Response::new(Full::new(Bytes::from_static(
+                                b"{}",
+                            ))))
+                        });
+
+                        let _ = auto::Builder::new(TokioExecutor::new())
+                            .serve_connection(TokioIo::new(stream), service)
+                            .await;
+                    });
+                }
+            });
+
+            let mut roots = RootCertStore::empty();
+            roots.add(cert).unwrap();
+            let tls = ClientConfig::builder_with_provider(Arc::new(ring::default_provider()))
+                .with_safe_default_protocol_versions()
+                .unwrap()
+                .with_root_certificates(roots)
+                .with_no_client_auth();
+
+            Self {
+                url: format!("https://localhost:{port}/"),
+                connections,
+                tls,
+            }
+        }
+
+        /// Build a client trusting the stand-in with the pool settings of the
+        /// config.
+        fn client(&self, config: &Config) -> TestClient {
+            let mut http = HttpConnector::new();
+            http.enforce_http(false);
+
+            let builder = hyper_rustls::HttpsConnectorBuilder::new()
+                .with_tls_config(self.tls.clone())
+                .https_only();
+
+            #[cfg(not(feature = "http2"))]
+            let connector = builder.enable_http1().wrap_connector(http);
+            #[cfg(feature = "http2")]
+            let connector = builder.enable_all_versions().wrap_connector(http);
+
+            build_client(config, connector)
+        }
+
+        fn connections(&self) -> usize {
+            self.connections.load(Ordering::SeqCst)
+        }
+    }
+
+    async fn get(client: &TestClient, url: &str) -> http::Response<Incoming> {
+        let request = http::Request::get(url).body(Full::default()).unwrap();
+        client.request(request).await.unwrap()
+    }
+
+    /// Test that sequential requests reuse a single pooled connection.
+    #[tokio::test]
+    async fn reuses_connections() {
+        let stand_in = StandIn::start(&[b"http/1.1"]).await;
+        let client = stand_in.client(&Config::new(Authentication::None));
+
+        for _ in 0..3 {
+            let response = get(&client, &stand_in.url).await;
+            response.into_body().collect().await.unwrap();
+        }
+
+        assert_eq!(stand_in.connections(), 1);
+    }
+
+    /// Test that connections are not reused when no idle connections may be
+    /// kept.
+    #[tokio::test]
+    async fn disabled_pool() {
+        let stand_in = StandIn::start(&[b"http/1.1"]).await;
+        let client = stand_in.client(&Config {
+            pool_max_idle_per_host: 0,
+            ..Config::new(Authentication::None)
+        });
+
+        for _ in 0..2 {
+            let response = get(&client, &stand_in.url).await;
+            response.into_body().collect().await.unwrap();
+        }
+
+        assert_eq!(stand_in.connections(), 2);
+    }
+
+    /// Test that HTTP/2 is negotiated and concurrent requests are multiplexed
+    /// over a single connection.
+    #[cfg(feature = "http2")]
+    #[tokio::test]
+    async fn multiplexes_http2() {
+        let stand_in = StandIn::start(&[b"h2", b"http/1.1"]).await;
+        let client = stand_in.client(&Config::new(Authentication::None));
+
+        let response = get(&client, &stand_in.url).await;
+        assert_eq!(response.version(), http::Version::HTTP_2);
+
+        let (first, second, third) = tokio::join!(
+            get(&client, &stand_in.url),
+            get(&client, &stand_in.url),
+            get(&client, &stand_in.url),
+        );
+        for response in [first, second, third] {
+            assert_eq!(response.version(), http::Version::HTTP_2);
+        }
+
+        assert_eq!(stand_in.connections(), 1);
+    }
+
+    /// Start a server writing the given bytes in response to every request,
+    /// then leaving the connection open without writing anything else.
+    async fn raw_server(response: &'static [u8]) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut request = [0; 1024];
+                    let _ = stream.read(&mut request).await;
+                    let _ = stream.write_all(response).await;
+                    tokio::time::sleep(Duration::from_secs(60)).await;
+                });
+            }
+        });
+
+        url
+    }
+
+    fn raw_client(base_url: String, timeout: Option<Duration>) -> Client {
+        Client::with_config(Config {
+            base_url,
+            ratelimiter: None,
+            timeout,
+            ..Config::new(Authentication::None)
+        })
+    }
+
+    /// Test that a request to a stalled server fails once the client-wide
+    /// or per-request timeout elapses.
+    #[tokio::test]
+    async fn times_out() {
+        let channel_id = Id::new("01FFD06NDVZ14W5T1WKKB4KKZX".to_string());
+        let client = raw_client(raw_server(b"").await, Some(Duration::from_millis(100)));
+
+        let error = client
+            .send_message(&channel_id)
+            .content("hi")
+            .await
+            .unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::Timeout));
+
+        let client = raw_client(raw_server(b"").await, None);
+        let request = client
+            .send_message(&channel_id)
+            .content("hi")
+            .timeout(Duration::from_millis(100))
+            .into_future();
+
+        let error = tokio::time::timeout(Duration::from_secs(5), request)
+            .await
+            .expect("per-request timeout was not applied")
+            .unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::Timeout));
+    }
+
+    /// Test that the timeout also bounds receiving the body of a response
+    /// whose headers arrived in time.
+    #[tokio::test]
+    async fn body_times_out() {
+        let channel_id = Id::new("01FFD06NDVZ14W5T1WKKB4KKZX".to_string());
+        let server = raw_server(b"HTTP/1.1 200 OK\r\ncontent-length: 64\r\n\r\n{").await;
+        let client = raw_client(server, Some(Duration::from_millis(100)));
+
+        let response = client
+            .send_message(&channel_id)
+            .content("hi")
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+
+        let error = tokio::time::timeout(Duration::from_secs(5), response.bytes())
+            .await
+            .expect("timeout was not applied to the body")
+            .unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::Timeout));
+    }
+
+    /// Test that error responses keep their status code and body.
+    #[tokio::test]
+    async fn api_error() {
+        let channel_id = Id::new("01FFD06NDVZ14W5T1WKKB4KKZX".to_string());
+        let server = raw_server(
+            b"HTTP/1.1 404 Not Found\r\ncontent-length: 19\r\n\r\n{\"type\":\"NotFound\"}",
+        )
+        .await;
+        let client = raw_client(server, Some(Duration::from_secs(5)));
+
+        let error = client
+            .send_message(&channel_id)
+            .content("hi")
+            .await
+            .unwrap_err();
+        match error.kind() {
+            ErrorKind::Api { status, body } => {
+                assert_eq!(*status, 404);
+                assert_eq!(&body[..], br#"{"type":"NotFound"}"#);
+            }
+            kind => panic!("unexpected error kind {kind:?}"),
+        }
+    }
+
+    /// Test that the authentication header is only sent with a credential,
+    /// and the MFA ticket is sent along with it.
+    #[tokio::test]
+    async fn authentication_headers() {
+        let channel_id = Id::new("01FFD06NDVZ14W5T1WKKB4KKZX".to_string());
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, mut requests) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = [0; 1024];
+                let read = stream.read(&mut request).await.unwrap();
+                let _ = sender.send(String::from_utf8_lossy(&request[..read]).to_lowercase());
+                let _ = stream
+                    .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+                    .await;
+            }
+        });
+
+        let client = Client::with_config(Config {
+            base_url: base_url.clone(),
+            mfa_ticket: Some("ticket".to_string()),
+            ratelimiter: None,
+            ..Config::new(Authentication::SessionToken("token".to_string()))
+        });
+        client
+            .send_message(&channel_id)
+            .content("hi")
+            .await
+            .unwrap();
+        let request = requests.recv().await.unwrap();
+        assert!(request.contains("\r\nx-session-token: token\r\n"));
+        assert!(request.contains("\r\nx-mfa-ticket: ticket\r\n"));
+
+        let client = raw_client(base_url, Some(Duration::from_secs(5)));
+        client
+            .send_message(&channel_id)
+            .content("hi")
+            .await
+            .unwrap();
+        let request = requests.recv().await.unwrap();
+        assert!(!request.contains("x-session-token") && !request.contains("x-mfa-ticket"));
+        assert!(!request.contains("\r\n: "));
+    }
 }
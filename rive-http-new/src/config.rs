@@ -1,7 +1,86 @@
+use std::{sync::Arc, time::Duration};
+
 use rive_models::authentication::Authentication;
 
+use crate::{
+    ratelimiter::{InMemoryRatelimiter, Ratelimiter},
+    BASE_URL,
+};
+
+/// Default timeout for establishing a connection.
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default timeout for a whole request.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default time an idle connection is kept in the pool.
+pub const DEFAULT_POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+
 #[derive(Debug, Clone)]
 pub struct Config {
     pub authentication: Authentication,
+    /// MFA ticket sent along with the authentication.
+    ///
+    /// Endpoints changing the MFA settings of an account require both a
+    /// session token and a validated ticket.
+    pub mfa_ticket: Option<String>,
     pub base_url: String,
+    /// Ratelimiter consulted before each request.
+    ///
+    /// If [`None`], requests are not ratelimited on the client side.
+    pub ratelimiter: Option<Arc<dyn Ratelimiter>>,
+    /// Timeout for establishing a connection.
+    ///
+    /// If [`None`], connecting is not bounded in time.
+    pub connect_timeout: Option<Duration>,
+    /// Timeout for a whole request, from acquiring a ratelimit ticket to
+    /// receiving the response body.
+    ///
+    /// It can be overridden for a single request. If [`None`], requests are
+    /// not bounded in time.
+    pub timeout: Option<Duration>,
+    /// Time an idle connection is kept in the pool before being closed.
+    ///
+    /// If [`None`], idle connections are kept until the server closes them.
+    pub pool_idle_timeout: Option<Duration>,
+    /// Maximum amount of idle connections kept in the pool per host.
+    ///
+    /// Setting it to `0` disables connection reuse.
+    pub pool_max_idle_per_host: usize,
+    /// Interval of TCP keepalive probes sent on open connections.
+    ///
+    /// If [`None`], TCP keepalive is disabled.
+    pub tcp_keepalive: Option<Duration>,
+    /// Whether to check request payloads against the limits of the API
+    /// before sending them.
+    ///
+    /// Payloads exceeding a limit fail with [`ErrorKind::Validation`]. Disable
+    /// it for instances configured with other limits.
+    ///
+    /// [`ErrorKind::Validation`]: crate::error::ErrorKind::Validation
+    pub validate_payloads: bool,
+}
+
+impl Config {
+    /// Create a new [`Config`] without MFA ticket, with the official instance
+    /// base URL, an
+    /// [`InMemoryRatelimiter`], [`DEFAULT_CONNECT_TIMEOUT`],
+    /// [`DEFAULT_TIMEOUT`] and [`DEFAULT_POOL_IDLE_TIMEOUT`].
+    ///
+    /// The amount of idle connections per host is not limited, TCP keepalive
+    /// is disabled and payloads are validated.
+    pub fn new(authentication: Authentication) -> Self {
+        Self {
+            authentication,
+            mfa_ticket: None,
+            base_url: BASE_URL.to_string(),
+            ratelimiter: Some(Arc::new(InMemoryRatelimiter::new())),
+            connect_timeout: Some(DEFAULT_CONNECT_TIMEOUT),
+            timeout: Some(DEFAULT_TIMEOUT),
+            pool_idle_timeout: Some(DEFAULT_POOL_IDLE_TIMEOUT),
+            pool_max_idle_per_host: usize::MAX,
+            tcp_keepalive: None,
+            validate_payloads: true,
+        }
+    }
 }
@@ -1,5 +1,7 @@
 use hyper_util::client::legacy::connect::HttpConnector;
 
+use crate::Config;
+
 #[cfg(any(
     all(
         feature = "native",
@@ -31,27 +33,38 @@ pub type Connector = hyper_rustls::HttpsConnector<HttpConnector>;
 )))]
 pub(crate) type Connector = HttpConnector;
 
-pub fn create_connector() -> Connector {
+pub fn create_connector(config: &Config) -> Connector {
     let mut connector = HttpConnector::new();
     connector.enforce_http(false);
+    connector.set_connect_timeout(config.connect_timeout);
+    connector.set_keepalive(config.tcp_keepalive);
 
     #[cfg(feature = "native")]
-    let connector = hyper_tls::HttpsConnector::new();
+    let connector = hyper_tls::HttpsConnector::new_with_connector(connector);
 
     #[cfg(feature = "rustls-native-roots")]
-    let connector = hyper_rustls::HttpsConnectorBuilder::new()
+    let builder = hyper_rustls::HttpsConnectorBuilder::new()
         .with_native_roots()
         .expect("no native root CA certificates found")
-        .https_or_http()
-        .enable_http1()
-        .build();
+        .https_or_http();
 
     #[cfg(feature = "rustls-webpki-roots")]
-    let connector = hyper_rustls::HttpsConnectorBuilder::new()
+    let builder = hyper_rustls::HttpsConnectorBuilder::new()
         .with_webpki_roots()
-        .https_or_http()
-        .enable_http1()
-        .build();
+        .https_or_http();
+
+    #[cfg(all(
+        any(feature = "rustls-native-roots", feature = "rustls-webpki-roots"),
+        not(feature = "http2")
+    ))]
+    let connector = builder.enable_http1().wrap_connector(connector);
+
+    // HTTP/2 is negotiated via ALPN, falling back to HTTP/1.1
+    #[cfg(all(
+        any(feature = "rustls-native-roots", feature = "rustls-webpki-roots"),
+        feature = "http2"
+    ))]
+    let connector = builder.enable_all_versions().wrap_connector(connector);
 
     connector
 }
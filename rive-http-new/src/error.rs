@@ -1,12 +1,30 @@
 pub use std::error::Error as StdError;
 use std::fmt::{self, Display, Formatter};
 
+use hyper::body::Bytes;
+
 #[derive(Debug)]
 #[non_exhaustive]
 pub enum ErrorKind {
     BuildingRequest,
+    /// The request payload exceeds a limit of the API.
+    ///
+    /// The source is the [`ValidationError`].
+    ///
+    /// [`ValidationError`]: rive_models::validate::ValidationError
+    Validation,
     SendingRequest,
-    Api,
+    Ratelimiting,
+    Timeout,
+    ReceivingBody,
+    Deserializing,
+    /// The API responded with a status code other than 2xx.
+    Api {
+        /// Status code of the response.
+        status: u16,
+        /// Raw body of the response, usually a JSON-encoded API error.
+        body: Bytes,
+    },
 }
 
 #[derive(Debug)]
@@ -33,10 +51,17 @@ impl Error {
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        match self.kind {
+        match &self.kind {
             ErrorKind::BuildingRequest => f.write_str("request building failed"),
+            ErrorKind::Validation => f.write_str("request payload is invalid"),
             ErrorKind::SendingRequest => f.write_str("error while sending request"),
-            ErrorKind::Api => f.write_str("API returned an error"),
+            ErrorKind::Ratelimiting => f.write_str("ratelimiter failed to process request"),
+            ErrorKind::Timeout => f.write_str("request timed out"),
+            ErrorKind::ReceivingBody => f.write_str("failed to receive response body"),
+            ErrorKind::Deserializing => f.write_str("response body could not be deserialized"),
+            ErrorKind::Api { status, .. } => {
+                write!(f, "API returned an error with status code {status}")
+            }
         }
     }
 }
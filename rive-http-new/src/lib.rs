@@ -1,5 +1,6 @@
 pub mod base;
 pub mod error;
+pub mod ratelimiter;
 pub mod request;
 
 mod client;
@@ -7,7 +8,7 @@ mod connector;
 pub use client::Client;
 
 mod config;
-pub use config::Config;
+pub use config::{Config, DEFAULT_CONNECT_TIMEOUT, DEFAULT_POOL_IDLE_TIMEOUT, DEFAULT_TIMEOUT};
 
 use error::Error;
 use std::{future::Future, pin::Pin, result::Result as StdResult};
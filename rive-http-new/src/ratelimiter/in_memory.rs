@@ -0,0 +1,252 @@
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use tokio::time::Instant;
+
+use crate::ratelimiter::{RatelimitHeaders, Ratelimiter, RatelimiterFuture};
+
+/// State of a single bucket.
+#[derive(Debug)]
+struct Bucket {
+    /// Maximum amount of requests in the bucket.
+    limit: u64,
+    /// Amount of requests left in the bucket.
+    remaining: u64,
+    /// When the bucket resets.
+    reset_at: Instant,
+    /// Time between two resets, assumed to be the largest reset delay
+    /// received, since responses may arrive late in a window.
+    ///
+    /// [`None`] if every response reported an immediate reset.
+    period: Option<Duration>,
+}
+
+/// A [`Ratelimiter`] keeping the state of the buckets in the process memory.
+///
+/// Buckets that are unknown to the ratelimiter, e.g. ones that have not
+/// received a response yet, are not limited.
+#[derive(Debug, Default)]
+pub struct InMemoryRatelimiter {
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl InMemoryRatelimiter {
+    /// Create a new [`InMemoryRatelimiter`] without any known buckets.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Time until the given bucket resets, if it is known and exhausted.
+    pub fn time_until_reset(&self, bucket: &str) -> Option<Duration> {
+        let buckets = self.buckets.lock().expect("ratelimiter state is poisoned");
+        let state = buckets.get(bucket)?;
+
+        if state.remaining > 0 {
+            return None;
+        }
+
+        Some(state.reset_at.saturating_duration_since(Instant::now()))
+    }
+
+    /// Try to take a request from the bucket.
+    ///
+    /// Returns the time the caller has to wait until if the bucket is
+    /// exhausted.
+    fn try_acquire(&self, bucket: &str) -> Option<Instant> {
+        let mut buckets = self.buckets.lock().expect("ratelimiter state is poisoned");
+        let state = buckets.get_mut(bucket)?;
+
+        let now = Instant::now();
+        if now >= state.reset_at {
+            // the bucket keeps resetting periodically until new headers
+            // arrive, so requests taken meanwhile are still limited
+            let Some(period) = state.period else {
+                // without a known period the bucket is not limited, like an
+                // unknown one
+                return None;
+            };
+            state.remaining = state.limit;
+            state.reset_at = now + period;
+        }
+
+        if state.remaining > 0 {
+            state.remaining -= 1;
+            return None;
+        }
+
+        Some(state.reset_at)
+    }
+}
+
+impl Ratelimiter for InMemoryRatelimiter {
+    fn acquire<'a>(&'a self, bucket: &'a str) -> RatelimiterFuture<'a, ()> {
+        Box::pin(async move {
+            while let Some(reset_at) = self.try_acquire(bucket) {
+                tokio::time::sleep_until(reset_at).await;
+            }
+
+            Ok(())
+        })
+    }
+
+    fn update<'a>(
+        &'a self,
+        bucket: &'a str,
+        headers: RatelimitHeaders,
+    ) -> RatelimiterFuture<'a, ()> {
+        Box::pin(async move {
+            let mut buckets = self.buckets.lock().expect("ratelimiter state is poisoned");
+            let reset_after = Some(headers.reset_after).filter(|delay| !delay.is_zero());
+            let period = buckets
+                .get(bucket)
+                .and_then(|state| state.period)
+                .max(reset_after);
+
+            buckets.insert(
+                bucket.to_string(),
+                Bucket {
+                    limit: headers.limit,
+                    remaining: headers.remaining,
+                    reset_at: Instant::now() + headers.reset_after,
+                    period,
+                },
+            );
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::time::Instant;
+
+    use super::InMemoryRatelimiter;
+    use crate::ratelimiter::{RatelimitHeaders, Ratelimiter};
+
+    const BUCKET: &str = "messaging:01FFD06NDVZ14W5T1WKKB4KKZX";
+
+    fn headers(remaining: u64) -> RatelimitHeaders {
+        RatelimitHeaders {
+            bucket: None,
+            limit: 2,
+            remaining,
+            reset_after: Duration::from_secs(10),
+        }
+    }
+
+    fn late_headers(remaining: u64) -> RatelimitHeaders {
+        RatelimitHeaders {
+            reset_after: Duration::from_millis(100),
+            ..headers(remaining)
+        }
+    }
+
+    /// Test that unknown buckets are not limited.
+    #[tokio::test(start_paused = true)]
+    async fn unknown_bucket() {
+        let ratelimiter = InMemoryRatelimiter::new();
+        let start = Instant::now();
+
+        for _ in 0..5 {
+            ratelimiter.acquire(BUCKET).await.unwrap();
+        }
+
+        assert_eq!(start.elapsed(), Duration::ZERO);
+        assert_eq!(ratelimiter.time_until_reset(BUCKET), None);
+    }
+
+    /// Test that an exhausted bucket waits for its reset.
+    #[tokio::test(start_paused = true)]
+    async fn waits_for_reset() {
+        let ratelimiter = InMemoryRatelimiter::new();
+        ratelimiter.update(BUCKET, headers(1)).await.unwrap();
+        let start = Instant::now();
+
+        ratelimiter.acquire(BUCKET).await.unwrap();
+        assert_eq!(start.elapsed(), Duration::ZERO);
+        assert_eq!(
+            ratelimiter.time_until_reset(BUCKET),
+            Some(Duration::from_secs(10))
+        );
+
+        ratelimiter.acquire(BUCKET).await.unwrap();
+        assert_eq!(start.elapsed(), Duration::from_secs(10));
+    }
+
+    /// Test that a bucket keeps being limited after resetting on its own,
+    /// before new headers arrive.
+    #[tokio::test(start_paused = true)]
+    async fn limits_after_reset() {
+        let ratelimiter = InMemoryRatelimiter::new();
+        ratelimiter.update(BUCKET, headers(0)).await.unwrap();
+        let start = Instant::now();
+
+        // the whole limit is available once the bucket resets, after which
+        // the next period has to be waited for
+        for _ in 0..2 {
+            ratelimiter.acquire(BUCKET).await.unwrap();
+        }
+        assert_eq!(start.elapsed(), Duration::from_secs(10));
+
+        ratelimiter.acquire(BUCKET).await.unwrap();
+        assert_eq!(start.elapsed(), Duration::from_secs(20));
+    }
+
+    /// Test that new headers replace the state of the bucket.
+    #[tokio::test(start_paused = true)]
+    async fn update_replaces_state() {
+        let ratelimiter = InMemoryRatelimiter::new();
+        ratelimiter.update(BUCKET, headers(0)).await.unwrap();
+        assert!(ratelimiter.time_until_reset(BUCKET).is_some());
+
+        ratelimiter.update(BUCKET, headers(2)).await.unwrap();
+        assert_eq!(ratelimiter.time_until_reset(BUCKET), None);
+
+        let start = Instant::now();
+        ratelimiter.acquire(BUCKET).await.unwrap();
+        assert_eq!(start.elapsed(), Duration::ZERO);
+    }
+
+    /// Test that a response arriving late in a window does not shorten the
+    /// period of the bucket.
+    #[tokio::test(start_paused = true)]
+    async fn late_response_keeps_period() {
+        let ratelimiter = InMemoryRatelimiter::new();
+        ratelimiter.update(BUCKET, headers(2)).await.unwrap();
+        ratelimiter.update(BUCKET, late_headers(0)).await.unwrap();
+        let start = Instant::now();
+
+        for _ in 0..2 {
+            ratelimiter.acquire(BUCKET).await.unwrap();
+        }
+        assert_eq!(start.elapsed(), Duration::from_millis(100));
+
+        ratelimiter.acquire(BUCKET).await.unwrap();
+        assert_eq!(start.elapsed(), Duration::from_millis(10_100));
+    }
+
+    /// Test that a bucket whose period is unknown is not limited once it
+    /// resets, rather than resetting continuously.
+    #[tokio::test(start_paused = true)]
+    async fn unknown_period() {
+        let ratelimiter = InMemoryRatelimiter::new();
+        ratelimiter
+            .update(
+                BUCKET,
+                RatelimitHeaders {
+                    reset_after: Duration::ZERO,
+                    ..headers(0)
+                },
+            )
+            .await
+            .unwrap();
+        let start = Instant::now();
+
+        for _ in 0..5 {
+            ratelimiter.acquire(BUCKET).await.unwrap();
+        }
+        assert_eq!(start.elapsed(), Duration::ZERO);
+    }
+}
@@ -0,0 +1,132 @@
+//! Ratelimiting of outgoing requests.
+//!
+//! Before a request is sent, the [`Client`] asks its [`Ratelimiter`] for
+//! permission to use the request's bucket, and once a response arrives, the
+//! ratelimiter is updated with the ratelimit headers returned by the API.
+//!
+//! Buckets are keyed by strings derived from a [`Route`], see [`Route::bucket`].
+//!
+//! [`InMemoryRatelimiter`] is used by default. Applications running in
+//! several processes may implement [`Ratelimiter`] on top of shared state, so
+//! that all of the processes respect the same buckets.
+//!
+//! [`Client`]: crate::Client
+//! [`Route`]: crate::base::request::Route
+//! [`Route::bucket`]: crate::base::request::Route::bucket
+
+mod in_memory;
+
+pub use in_memory::InMemoryRatelimiter;
+
+use std::{error::Error, fmt::Debug, future::Future, pin::Pin, time::Duration};
+
+use http::HeaderMap;
+
+/// Header containing the name of the bucket the request was counted against.
+pub const BUCKET_HEADER: &str = "x-ratelimit-bucket";
+
+/// Header containing the maximum amount of requests in the bucket.
+pub const LIMIT_HEADER: &str = "x-ratelimit-limit";
+
+/// Header containing the amount of requests left in the bucket.
+pub const REMAINING_HEADER: &str = "x-ratelimit-remaining";
+
+/// Header containing the amount of milliseconds until the bucket resets.
+pub const RESET_AFTER_HEADER: &str = "x-ratelimit-reset-after";
+
+/// Error returned by a [`Ratelimiter`] implementation.
+pub type RatelimiterError = Box<dyn Error + Send + Sync>;
+
+/// Future returned by [`Ratelimiter`] methods.
+pub type RatelimiterFuture<'a, T> =
+    Pin<Box<dyn Future<Output = Result<T, RatelimiterError>> + Send + Sync + 'a>>;
+
+/// Ratelimit information parsed from response headers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RatelimitHeaders {
+    /// Name of the bucket assigned by the API, if present.
+    pub bucket: Option<String>,
+    /// Maximum amount of requests in the bucket.
+    pub limit: u64,
+    /// Amount of requests left in the bucket.
+    pub remaining: u64,
+    /// Time until the bucket resets.
+    pub reset_after: Duration,
+}
+
+impl RatelimitHeaders {
+    /// Parse ratelimit information from response headers.
+    ///
+    /// Returns [`None`] if any of the limit, remaining or reset headers are
+    /// missing or malformed.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        fn parse(headers: &HeaderMap, name: &str) -> Option<u64> {
+            headers.get(name)?.to_str().ok()?.trim().parse().ok()
+        }
+
+        let bucket = headers
+            .get(BUCKET_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(ToString::to_string);
+
+        Some(Self {
+            bucket,
+            limit: parse(headers, LIMIT_HEADER)?,
+            remaining: parse(headers, REMAINING_HEADER)?,
+            reset_after: Duration::from_millis(parse(headers, RESET_AFTER_HEADER)?),
+        })
+    }
+}
+
+/// A ratelimiter consulted by the [`Client`] before each request.
+///
+/// [`Client`]: crate::Client
+pub trait Ratelimiter: Debug + Send + Sync {
+    /// Wait until a request may be sent in the given bucket.
+    ///
+    /// The returned future should resolve once the bucket has capacity for
+    /// one more request, and account for that request.
+    fn acquire<'a>(&'a self, bucket: &'a str) -> RatelimiterFuture<'a, ()>;
+
+    /// Update the state of the bucket with the ratelimit headers of a
+    /// response.
+    fn update<'a>(
+        &'a self,
+        bucket: &'a str,
+        headers: RatelimitHeaders,
+    ) -> RatelimiterFuture<'a, ()>;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use http::{HeaderMap, HeaderValue};
+
+    use super::{
+        RatelimitHeaders, BUCKET_HEADER, LIMIT_HEADER, REMAINING_HEADER, RESET_AFTER_HEADER,
+    };
+
+    /// Test that ratelimit headers are parsed from a response.
+    #[test]
+    fn parse_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(BUCKET_HEADER, HeaderValue::from_static("messaging"));
+        headers.insert(LIMIT_HEADER, HeaderValue::from_static("10"));
+        headers.insert(REMAINING_HEADER, HeaderValue::from_static("9"));
+        headers.insert(RESET_AFTER_HEADER, HeaderValue::from_static("9500"));
+
+        assert_eq!(
+            RatelimitHeaders::from_headers(&headers),
+            Some(RatelimitHeaders {
+                bucket: Some("messaging".to_string()),
+                limit: 10,
+                remaining: 9,
+                reset_after: Duration::from_millis(9500),
+            })
+        );
+
+        headers.remove(REMAINING_HEADER);
+        assert_eq!(RatelimitHeaders::from_headers(&headers), None);
+    }
+}
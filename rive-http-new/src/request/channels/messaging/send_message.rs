@@ -1,4 +1,4 @@
-use std::future::IntoFuture;
+use std::{future::IntoFuture, time::Duration};
 
 use rive_models::{
     data::SendMessageData,
@@ -11,7 +11,10 @@ use rive_models::{
 };
 
 use crate::{
-    base::request::{Request, Route, TryIntoRequest},
+    base::{
+        request::{Request, Route, TryIntoRequest},
+        response::Response,
+    },
     Client, ResponseFuture, Result,
 };
 
@@ -20,6 +23,7 @@ pub struct SendMessageRequest<'a> {
     client: &'a Client,
     channel_id: &'a Id<ChannelMarker>,
     data: SendMessageData<'a>,
+    timeout: Option<Duration>,
 }
 
 impl<'a> SendMessageRequest<'a> {
@@ -35,6 +39,7 @@ impl<'a> SendMessageRequest<'a> {
                 masquerade: None,
                 interactions: None,
             },
+            timeout: None,
         }
     }
 
@@ -67,24 +72,73 @@ impl<'a> SendMessageRequest<'a> {
         self.data.interactions = Some(interactions);
         self
     }
+
+    /// Override the client-wide timeout for this request.
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
 }
 
 impl TryIntoRequest for SendMessageRequest<'_> {
     fn try_into_request(self) -> Result<Request> {
-        Request::builder()
+        self.client.validate(&self.data)?;
+
+        let builder = Request::builder()
             .route(Route::SendMessage {
                 channel_id: self.channel_id.value_ref(),
             })
-            .json(&self.data)
-            .build()
+            .json(&self.data);
+
+        match self.timeout {
+            Some(timeout) => builder.timeout(timeout),
+            None => builder,
+        }
+        .build()
     }
 }
 
 impl<'a> IntoFuture for SendMessageRequest<'a> {
-    type Output = Result<Message>;
+    type Output = Result<Response<Message>>;
     type IntoFuture = ResponseFuture<'a, Self::Output>;
 
     fn into_future(self) -> Self::IntoFuture {
         Box::pin(self.client.fire(self))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rive_models::{authentication::Authentication, id::Id};
+
+    use crate::{base::request::TryIntoRequest as _, error::ErrorKind, Client, Config};
+
+    #[test]
+    fn validates_payload() {
+        let channel_id = Id::new("01FFD06NDVZ14W5T1WKKB4KKZX".to_string());
+        let content = "a".repeat(2001);
+
+        let client = Client::new(Authentication::None);
+        let error = client
+            .send_message(&channel_id)
+            .try_into_request()
+            .unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::Validation));
+        let error = client
+            .send_message(&channel_id)
+            .content(&content)
+            .try_into_request()
+            .unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::Validation));
+
+        let client = Client::with_config(Config {
+            validate_payloads: false,
+            ..Config::new(Authentication::None)
+        });
+        client
+            .send_message(&channel_id)
+            .content(&content)
+            .try_into_request()
+            .unwrap();
+    }
+}
@@ -48,7 +48,7 @@ impl Client {
     pub async fn delete_account(&self) -> Result<()> {
         self.client
             .post(ep!(self, "/auth/account/delete"))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -58,23 +58,15 @@ impl Client {
 
     /// Fetch account information from the current session.
     pub async fn fetch_account(&self) -> Result<AccountInfo> {
-        Ok(self
-            .client
-            .get(ep!(self, "/auth/account/"))
-            .auth(&self.authentication)
-            .send()
-            .await?
-            .process_error()
-            .await?
-            .json()
-            .await?)
+        self.get_coalesced(self.client.get(ep!(self, "/auth/account/")))
+            .await
     }
 
     /// Disable an account.
     pub async fn disable_account(&self) -> Result<()> {
         self.client
             .post(ep!(self, "/auth/account/disable"))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -87,7 +79,7 @@ impl Client {
         self.client
             .patch(ep!(self, "/auth/account/change/password"))
             .json(&data)
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -100,7 +92,7 @@ impl Client {
         self.client
             .patch(ep!(self, "/auth/account/change/email"))
             .json(&data)
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -0,0 +1,189 @@
+use std::{error::Error as StdError, future::Future};
+
+use rive_models::{
+    authentication::Authentication,
+    data::LoginData,
+    id::{marker::UserMarker, Id},
+    mfa::{MFAData, MFAMethod},
+    session::LoginResponse,
+};
+
+use crate::{prelude::*, Error};
+
+/// Login flow error
+#[derive(Debug, thiserror::Error)]
+pub enum LoginError {
+    /// Submitting the credentials failed
+    #[error("Error while submitting credentials: {0}")]
+    Credentials(Error),
+
+    /// The account is disabled
+    #[error("Account {user_id} is disabled")]
+    Disabled { user_id: Id<UserMarker> },
+
+    /// The MFA response uses a method the account does not allow
+    #[error("MFA method {method:?} is not allowed, expected one of {allowed:?}")]
+    MethodNotAllowed {
+        method: MFAMethod,
+        allowed: Vec<MFAMethod>,
+    },
+
+    /// Getting the MFA response failed, e.g. the user cancelled the prompt
+    #[error("MFA prompt failed: {0}")]
+    MfaPrompt(Box<dyn StdError + Send + Sync>),
+
+    /// Submitting the MFA response failed, e.g. the code is invalid
+    #[error("Error while submitting MFA response: {0}")]
+    Mfa(Error),
+
+    /// The API asked for another MFA response after one was submitted
+    #[error("MFA response was not accepted")]
+    MfaNotAccepted,
+}
+
+impl Client {
+    /// Login to an account, completing multi-factor authentication if the
+    /// account requires it.
+    ///
+    /// If the API asks for MFA, `mfa` is called with the methods allowed for
+    /// the account and should return the response, such as a TOTP code or a
+    /// recovery code. If it returns an error, for example because the user
+    /// dismissed the prompt, the login is aborted with
+    /// [`LoginError::MfaPrompt`]. On success, the session token is returned.
+    pub async fn login_with_mfa<F, Fut, E>(
+        &self,
+        data: &LoginData,
+        mfa: F,
+    ) -> std::result::Result<Authentication, LoginError>
+    where
+        F: FnOnce(Vec<MFAMethod>) -> Fut,
+        Fut: Future<Output = std::result::Result<MFAData, E>>,
+        E: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        let (ticket, allowed) = match self.login(data).await.map_err(LoginError::Credentials)? {
+            LoginResponse::Success(session) => {
+                return Ok(Authentication::SessionToken(session.token))
+            }
+            LoginResponse::Disabled { user_id } => return Err(LoginError::Disabled { user_id }),
+            LoginResponse::MFA {
+                ticket,
+                allowed_methods,
+            } => (ticket, allowed_methods),
+        };
+
+        let response = mfa(allowed.clone())
+            .await
+            .map_err(|source| LoginError::MfaPrompt(source.into()))?;
+        let method = match response {
+            MFAData::Password { .. } => MFAMethod::Password,
+            MFAData::Recovery { .. } => MFAMethod::Recovery,
+            MFAData::Totp { .. } => MFAMethod::Totp,
+        };
+        if !allowed.contains(&method) {
+            return Err(LoginError::MethodNotAllowed { method, allowed });
+        }
+
+        let friendly_name = match data {
+            LoginData::Email { friendly_name, .. } | LoginData::MFA { friendly_name, .. } => {
+                friendly_name.clone()
+            }
+        };
+        let data = LoginData::MFA {
+            mfa_ticket: ticket,
+            mfa_response: Some(response),
+            friendly_name,
+        };
+
+        match self.login(&data).await.map_err(LoginError::Mfa)? {
+            LoginResponse::Success(session) => Ok(Authentication::SessionToken(session.token)),
+            LoginResponse::Disabled { user_id } => Err(LoginError::Disabled { user_id }),
+            LoginResponse::MFA { .. } => Err(LoginError::MfaNotAccepted),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{convert::Infallible, io};
+
+    use rive_models::{
+        authentication::Authentication,
+        data::LoginData,
+        mfa::{MFAData, MFAMethod},
+    };
+
+    use super::LoginError;
+    use crate::test_util::FakeServer;
+
+    fn credentials(password: &str) -> LoginData {
+        LoginData::Email {
+            email: "ferris@example.com".to_string(),
+            password: password.to_string(),
+            friendly_name: None,
+        }
+    }
+
+    async fn no_mfa(_: Vec<MFAMethod>) -> Result<MFAData, Infallible> {
+        unreachable!("MFA is disabled")
+    }
+
+    #[tokio::test]
+    async fn login() {
+        let server = FakeServer::start().await.unwrap();
+        let account = server.add_login("ferris", "ferris@example.com", "crab");
+        let client = server.client(Authentication::None);
+
+        let authentication = client
+            .login_with_mfa(&credentials("crab"), no_mfa)
+            .await
+            .unwrap();
+        let user = server.client(authentication).fetch_self().await.unwrap();
+        assert_eq!(user.id, account.id);
+
+        let error = client
+            .login_with_mfa(&credentials("lobster"), no_mfa)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, LoginError::Credentials(_)));
+    }
+
+    #[tokio::test]
+    async fn login_with_mfa() {
+        let server = FakeServer::start().await.unwrap();
+        let account = server.add_login("ferris", "ferris@example.com", "crab");
+        server.enable_mfa("ferris@example.com", "recovery");
+        let client = server.client(Authentication::None);
+
+        let authentication = client
+            .login_with_mfa(&credentials("crab"), |allowed| async move {
+                assert_eq!(allowed.len(), 1);
+                Ok::<_, Infallible>(MFAData::Recovery {
+                    recovery_code: "recovery".to_string(),
+                })
+            })
+            .await
+            .unwrap();
+        let user = server.client(authentication).fetch_self().await.unwrap();
+        assert_eq!(user.id, account.id);
+
+        let error = client
+            .login_with_mfa(&credentials("crab"), |_| async {
+                Ok::<_, Infallible>(MFAData::Recovery {
+                    recovery_code: "invalid".to_string(),
+                })
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(error, LoginError::Mfa(_)));
+
+        let error = client
+            .login_with_mfa(&credentials("crab"), |_| async {
+                Err::<MFAData, _>(io::Error::new(io::ErrorKind::Interrupted, "dismissed"))
+            })
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(error, LoginError::MfaPrompt(source) if source.to_string() == "dismissed")
+        );
+    }
+}
@@ -11,7 +11,7 @@ impl Client {
         Ok(self
             .client
             .put(ep!(self, "/auth/mfa/ticket"))
-            .auth(&self.authentication)
+            .auth(self)
             .json(&data)
             .send()
             .await?
@@ -23,16 +23,8 @@ impl Client {
 
     /// Fetch MFA status of an account.
     pub async fn fetch_mfa_status(&self) -> Result<MFAStatus> {
-        Ok(self
-            .client
-            .get(ep!(self, "/auth/mfa/"))
-            .auth(&self.authentication)
-            .send()
-            .await?
-            .process_error()
-            .await?
-            .json()
-            .await?)
+        self.get_coalesced(self.client.get(ep!(self, "/auth/mfa/")))
+            .await
     }
 
     /// Fetch recovery codes for an account.
@@ -40,7 +32,7 @@ impl Client {
         Ok(self
             .client
             .post(ep!(self, "/auth/mfa/recovery"))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -54,7 +46,7 @@ impl Client {
         Ok(self
             .client
             .patch(ep!(self, "/auth/mfa/recovery"))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -65,16 +57,8 @@ impl Client {
 
     /// Fetch available MFA methods.
     pub async fn get_mfa_methods(&self) -> Result<Vec<MFAMethod>> {
-        Ok(self
-            .client
-            .get(ep!(self, "/auth/mfa/methods"))
-            .auth(&self.authentication)
-            .send()
-            .await?
-            .process_error()
-            .await?
-            .json()
-            .await?)
+        self.get_coalesced(self.client.get(ep!(self, "/auth/mfa/methods")))
+            .await
     }
 
     /// Enable TOTP 2FA for an account.
@@ -82,7 +66,7 @@ impl Client {
         self.client
             .put(ep!(self, "/auth/mfa/totp"))
             .json(&data)
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -95,7 +79,7 @@ impl Client {
         Ok(self
             .client
             .post(ep!(self, "/auth/mfa/totp"))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -108,7 +92,7 @@ impl Client {
     pub async fn disable_totp_2fa(&self) -> Result<()> {
         self.client
             .delete(ep!(self, "/auth/mfa/totp"))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -1,4 +1,7 @@
 mod account;
+mod login;
 mod mfa;
 mod onboarding;
 mod session;
+
+pub use login::LoginError;
@@ -7,16 +7,8 @@ impl Client {
     /// can continue to send requests as usual. You may skip calling this if you're restoring
     /// an existing session.
     pub async fn check_onboarding_status(&self) -> Result<OnboardingStatus> {
-        Ok(self
-            .client
-            .get(ep!(self, "/onboarding/hello"))
-            .auth(&self.authentication)
-            .send()
-            .await?
-            .process_error()
-            .await?
-            .json()
-            .await?)
+        self.get_coalesced(self.client.get(ep!(self, "/onboarding/hello")))
+            .await
     }
 
     /// This sets a new username, completes onboarding and allows a user to start using Revolt.
@@ -24,7 +16,7 @@ impl Client {
         self.client
             .post(ep!(self, "/onboarding/complete"))
             .json(&data)
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -1,14 +1,21 @@
-use crate::prelude::*;
+use crate::{prelude::*, Error, StoredSession};
 use rive_models::{
+    authentication::Authentication,
     data::{DeleteAllSessionsData, EditSessionData, LoginData},
+    event::AuthifierEvent,
     id::{marker::SessionMarker, Id},
     session::{LoginResponse, SessionInfo},
 };
+use tracing::warn;
 
 impl Client {
     /// Login to an account.
+    ///
+    /// A new session is used for subsequent requests. If a session store is
+    /// configured, the session is also saved to it; failing to do so is logged
+    /// and does not fail the login, as the session is valid regardless.
     pub async fn login(&self, data: &LoginData) -> Result<LoginResponse> {
-        Ok(self
+        let response = self
             .client
             .post(ep!(self, "/auth/session/login"))
             .json(&data)
@@ -17,33 +24,71 @@ impl Client {
             .process_error()
             .await?
             .json()
-            .await?)
+            .await?;
+
+        if let LoginResponse::Success(session) = &response {
+            self.set_authentication(Authentication::SessionToken(session.token.clone()));
+            if let Some(store) = &self.session_store {
+                if let Err(error) = store.save(&StoredSession::from(session.clone())) {
+                    warn!(%error, "failed to save the session");
+                }
+            }
+        }
+
+        Ok(response)
     }
 
     /// Delete current session.
+    ///
+    /// The client is left unauthenticated. If a session store is configured,
+    /// the session is removed from it; failing to do so is returned as an
+    /// error, after the session was deleted.
     pub async fn logout(&self) -> Result<()> {
         self.client
             .post(ep!(self, "/auth/session/logout"))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
             .await?;
+
+        self.set_authentication(Authentication::None);
+        if let Some(store) = &self.session_store {
+            store.clear().map_err(Error::SessionStore)?;
+        }
+
         Ok(())
     }
 
+    /// Handle a session event received from the gateway.
+    ///
+    /// This is not called automatically: clients driving the gateway
+    /// themselves should pass it every [`AuthifierEvent`]. `rive::Rive::update`
+    /// does so.
+    ///
+    /// If the stored session was revoked, it is removed from the session
+    /// store, the client is left unauthenticated and `true` is returned: the
+    /// account has to log in again.
+    pub fn handle_authifier_event(&self, event: &AuthifierEvent) -> Result<bool> {
+        let Some(store) = &self.session_store else {
+            return Ok(false);
+        };
+
+        match store.load().map_err(Error::SessionStore)? {
+            Some(session) if session.is_revoked_by(event) => {
+                store.clear().map_err(Error::SessionStore)?;
+                self.set_authentication(Authentication::None);
+
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
     /// Fetch all sessions associated with this account.
     pub async fn fetch_sessions(&self) -> Result<Vec<SessionInfo>> {
-        Ok(self
-            .client
-            .get(ep!(self, "/auth/session/all"))
-            .auth(&self.authentication)
-            .send()
-            .await?
-            .process_error()
-            .await?
-            .json()
-            .await?)
+        self.get_coalesced(self.client.get(ep!(self, "/auth/session/all")))
+            .await
     }
 
     /// Delete all active sessions, optionally including current one.
@@ -51,7 +96,7 @@ impl Client {
         self.client
             .delete(ep!(self, "/auth/session/all"))
             .json(&data)
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -63,7 +108,7 @@ impl Client {
     pub async fn revoke_session(&self, id: &Id<SessionMarker>) -> Result<()> {
         self.client
             .delete(ep!(self, "/auth/session/{}", id.value_ref()))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -81,7 +126,7 @@ impl Client {
             .client
             .patch(ep!(self, "/auth/session/{}", id.value_ref()))
             .json(&data)
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -90,3 +135,94 @@ impl Client {
             .await?)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{io, sync::Arc};
+
+    use rive_models::{authentication::Authentication, data::LoginData, session::LoginResponse};
+
+    use crate::{test_util::FakeServer, Client, Config, Error, SessionStore, StoredSession};
+
+    #[derive(Debug)]
+    struct ReadOnlyStore;
+
+    impl SessionStore for ReadOnlyStore {
+        fn load(&self) -> io::Result<Option<StoredSession>> {
+            Ok(None)
+        }
+
+        fn save(&self, _: &StoredSession) -> io::Result<()> {
+            Err(io::Error::new(io::ErrorKind::PermissionDenied, "read-only"))
+        }
+
+        fn clear(&self) -> io::Result<()> {
+            Err(io::Error::new(io::ErrorKind::PermissionDenied, "read-only"))
+        }
+    }
+
+    #[tokio::test]
+    async fn login_without_saving() {
+        let server = FakeServer::start().await.unwrap();
+        let account = server.add_login("ferris", "ferris@example.com", "crab");
+        let client = Client::with_config(Config {
+            base_url: server.url(),
+            session_store: Some(Arc::new(ReadOnlyStore)),
+            ..Config::new(Authentication::None)
+        });
+
+        let response = client
+            .login(&LoginData::Email {
+                email: "ferris@example.com".to_string(),
+                password: "crab".to_string(),
+                friendly_name: None,
+            })
+            .await
+            .unwrap();
+        assert!(matches!(response, LoginResponse::Success(_)));
+        assert_eq!(client.fetch_self().await.unwrap().id, account.id);
+    }
+
+    #[tokio::test]
+    async fn login_without_store() {
+        let server = FakeServer::start().await.unwrap();
+        let account = server.add_login("ferris", "ferris@example.com", "crab");
+        let client = server.client(Authentication::None);
+
+        client
+            .login(&LoginData::Email {
+                email: "ferris@example.com".to_string(),
+                password: "crab".to_string(),
+                friendly_name: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(client.fetch_self().await.unwrap().id, account.id);
+
+        client.logout().await.unwrap();
+        assert!(matches!(client.authentication(), Authentication::None));
+    }
+
+    #[tokio::test]
+    async fn logout_without_clearing() {
+        let server = FakeServer::start().await.unwrap();
+        server.add_login("ferris", "ferris@example.com", "crab");
+        let client = Client::with_config(Config {
+            base_url: server.url(),
+            session_store: Some(Arc::new(ReadOnlyStore)),
+            ..Config::new(Authentication::None)
+        });
+        client
+            .login(&LoginData::Email {
+                email: "ferris@example.com".to_string(),
+                password: "crab".to_string(),
+                friendly_name: None,
+            })
+            .await
+            .unwrap();
+
+        let error = client.logout().await.unwrap_err();
+        assert!(matches!(error, Error::SessionStore(_)));
+        assert!(matches!(client.authentication(), Authentication::None));
+    }
+}
@@ -12,7 +12,7 @@ impl Client {
             .client
             .post(ep!(self, "/bots/create"))
             .json(&data)
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -23,16 +23,11 @@ impl Client {
 
     /// Fetch details of a public (or owned) bot by its id.
     pub async fn fetch_public_bot(&self, id: Id<UserMarker>) -> Result<PublicBot> {
-        Ok(self
-            .client
-            .get(ep!(self, "/bots/{}/invite", id.value_ref()))
-            .auth(&self.authentication)
-            .send()
-            .await?
-            .process_error()
-            .await?
-            .json()
-            .await?)
+        self.get_coalesced(
+            self.client
+                .get(ep!(self, "/bots/{}/invite", id.value_ref())),
+        )
+        .await
     }
 
     /// Invite a bot to a server or group by its id.
@@ -40,7 +35,7 @@ impl Client {
         self.client
             .post(ep!(self, "/bots/{}/invite", bot_id.value_ref()))
             .json(&data)
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -50,23 +45,18 @@ impl Client {
 
     /// Fetch details of a bot you own by its id.
     pub async fn fetch_bot(&self, id: &Id<UserMarker>) -> Result<OwnedBot> {
-        Ok(self
-            .client
-            .get(ep!(self, "/bots/{}/invite", id.value_ref()))
-            .auth(&self.authentication)
-            .send()
-            .await?
-            .process_error()
-            .await?
-            .json()
-            .await?)
+        self.get_coalesced(
+            self.client
+                .get(ep!(self, "/bots/{}/invite", id.value_ref())),
+        )
+        .await
     }
 
     /// Delete a bot by its id.
     pub async fn delete_bot(&self, id: &Id<UserMarker>) -> Result<()> {
         self.client
             .delete(ep!(self, "/bots/{}", id.value_ref()))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -76,11 +66,13 @@ impl Client {
 
     /// Edit bot details by its id.
     pub async fn edit_bot(&self, id: &Id<UserMarker>, data: &EditBotData) -> Result<Bot> {
+        self.validate(data)?;
+
         Ok(self
             .client
             .patch(ep!(self, "/bots/{}", id.value_ref()))
             .json(&data)
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -91,15 +83,7 @@ impl Client {
 
     /// Fetch all of the bots that you have control over.
     pub async fn fetch_owned_bots(&self) -> Result<OwnedBots> {
-        Ok(self
-            .client
-            .get(ep!(self, "/bots/@me"))
-            .auth(&self.authentication)
-            .send()
-            .await?
-            .process_error()
-            .await?
-            .json()
-            .await?)
+        self.get_coalesced(self.client.get(ep!(self, "/bots/@me")))
+            .await
     }
 }
@@ -8,23 +8,15 @@ use rive_models::{
 impl Client {
     /// Fetch channel by its ID.
     pub async fn fetch_channel(&self, id: &Id<ChannelMarker>) -> Result<Channel> {
-        Ok(self
-            .client
-            .get(ep!(self, "/channels/{}", id.value_ref()))
-            .auth(&self.authentication)
-            .send()
-            .await?
-            .process_error()
-            .await?
-            .json()
-            .await?)
+        self.get_coalesced(self.client.get(ep!(self, "/channels/{}", id.value_ref())))
+            .await
     }
 
     /// Deletes a server channel, leaves a group or closes a group.
     pub async fn close_channel(&self, id: &Id<ChannelMarker>) -> Result<()> {
         self.client
             .delete(ep!(self, "/channels/{}", id.value_ref()))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -38,10 +30,12 @@ impl Client {
         id: &Id<ChannelMarker>,
         data: &EditChannelData,
     ) -> Result<Channel> {
+        self.validate(data)?;
+
         Ok(self
             .client
             .patch(ep!(self, "/channels/{}", id.value_ref()))
-            .auth(&self.authentication)
+            .auth(self)
             .json(&data)
             .send()
             .await?
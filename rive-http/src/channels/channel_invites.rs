@@ -14,7 +14,7 @@ impl Client {
         Ok(self
             .client
             .post(ep!(self, "/channels/{}/invites", id.value_ref()))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -26,7 +26,7 @@ impl Client {
                 channel_id.value_ref(),
                 role_id.value_ref()
             ))
-            .auth(&self.authentication)
+            .auth(self)
             .json(&data)
             .send()
             .await?
@@ -51,7 +51,7 @@ impl Client {
                 "/channels/{}/permissions/default",
                 channel_id.value_ref(),
             ))
-            .auth(&self.authentication)
+            .auth(self)
             .json(&data)
             .send()
             .await?
@@ -12,16 +12,11 @@ use rive_models::{
 impl Client {
     /// Retrieves all users who are part of this group.
     pub async fn fetch_group_members(&self, id: &Id<ChannelMarker>) -> Result<Vec<User>> {
-        Ok(self
-            .client
-            .get(ep!(self, "/channels/{}/members", id.value_ref()))
-            .auth(&self.authentication)
-            .send()
-            .await?
-            .process_error()
-            .await?
-            .json()
-            .await?)
+        self.get_coalesced(
+            self.client
+                .get(ep!(self, "/channels/{}/members", id.value_ref())),
+        )
+        .await
     }
 
     /// Create a new group channel.
@@ -30,7 +25,7 @@ impl Client {
             .client
             .post(ep!(self, "/channels/create"))
             .json(&data)
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -52,12 +47,12 @@ impl Client {
                 group_id.value_ref(),
                 member_id.value_ref()
             ))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
             .await?
-            .json()
+            .json::<()>()
             .await?;
         Ok(())
     }
@@ -75,12 +70,12 @@ impl Client {
                 group_id.value_ref(),
                 member_id.value_ref()
             ))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
             .await?
-            .json()
+            .json::<()>()
             .await?;
         Ok(())
     }
@@ -24,7 +24,7 @@ impl Client {
                 message_id.value_ref(),
                 emoji.value_ref()
             ))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -53,7 +53,7 @@ impl Client {
                 emoji.value_ref()
             ))
             .query(&data)
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -78,7 +78,7 @@ impl Client {
                 channel_id.value_ref(),
                 message_id.value_ref(),
             ))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -1,16 +1,30 @@
-use crate::prelude::*;
+use crate::{generate_nonce, prelude::*, Error};
 use rive_models::{
     data::{
         BulkDeleteMessagesData, EditMessageData, FetchMessagesData, SearchForMessagesData,
         SendMessageData,
     },
+    error::ApiError,
     id::{
         marker::{ChannelMarker, MessageMarker},
         Id,
     },
-    message::{BulkMessageResponse, Message},
+    message::{BulkMessageResponse, Message, MessageSort},
+    permission::Permission,
 };
 
+/// Header carrying the idempotency key of a message send.
+const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Whether a failed request may not have reached the API.
+fn is_transient(error: &Error) -> bool {
+    match error {
+        Error::Timeout(_) => true,
+        Error::HttpRequest(error) => error.is_connect() || error.is_request(),
+        _ => false,
+    }
+}
+
 impl Client {
     /// Lets the server and all other clients know that we've seen this message id in this channel.
     pub async fn acknowledge_message(
@@ -25,7 +39,7 @@ impl Client {
                 channel_id.value_ref(),
                 message_id.value_ref()
             ))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -39,36 +53,118 @@ impl Client {
         channel_id: &Id<ChannelMarker>,
         data: &FetchMessagesData,
     ) -> Result<BulkMessageResponse> {
-        Ok(self
-            .client
-            .get(ep!(self, "/channels/{}/messages", channel_id.value_ref()))
-            .auth(&self.authentication)
-            .query(&data)
-            .send()
-            .await?
-            .process_error()
-            .await?
-            .json()
-            .await?)
+        self.get_coalesced(
+            self.client
+                .get(ep!(self, "/channels/{}/messages", channel_id.value_ref()))
+                .query(&data),
+        )
+        .await
     }
 
     /// Send a message to a given channel.
+    ///
+    /// A new idempotency key is generated with [`generate_nonce`], see
+    /// [`send_message_with_nonce`](Self::send_message_with_nonce).
     pub async fn send_message(
         &self,
         channel_id: &Id<ChannelMarker>,
         data: &SendMessageData<'_>,
     ) -> Result<Message> {
-        Ok(self
-            .client
-            .post(ep!(self, "/channels/{}/messages", channel_id.value_ref()))
-            .auth(&self.authentication)
-            .json(&data)
-            .send()
-            .await?
-            .process_error()
-            .await?
-            .json()
-            .await?)
+        self.send_message_with_nonce(channel_id, data, &generate_nonce())
+            .await
+    }
+
+    /// Send a message to a given channel with the given idempotency key.
+    ///
+    /// The key becomes the nonce of the message, which allows matching the
+    /// message echoed by the gateway. After a timeout or a connection error
+    /// the send is retried with the same key, with an exponential backoff, as
+    /// many times as configured.
+    ///
+    /// If a retry fails with [`ApiError::DuplicateNonce`], the message was
+    /// created by an earlier attempt whose response was lost: it is fetched
+    /// from the latest messages of the channel and returned, or
+    /// [`Error::AlreadySent`] is returned if it cannot be found.
+    pub async fn send_message_with_nonce(
+        &self,
+        channel_id: &Id<ChannelMarker>,
+        data: &SendMessageData<'_>,
+        nonce: &str,
+    ) -> Result<Message> {
+        self.validate(data)?;
+
+        let mut required = Permission::SendMessage;
+        if data
+            .attachments
+            .map_or(false, |attachments| !attachments.is_empty())
+        {
+            required |= Permission::UploadFiles;
+        }
+        if data.embeds.map_or(false, |embeds| !embeds.is_empty()) {
+            required |= Permission::SendEmbeds;
+        }
+        if data.masquerade.is_some() {
+            required |= Permission::Masquerade;
+        }
+        self.preflight_channel(channel_id, required)?;
+
+        let mut attempt = 0;
+        let mut backoff = self.send_retry_backoff;
+
+        loop {
+            let result: Result<Message> = async {
+                Ok(self
+                    .client
+                    .post(ep!(self, "/channels/{}/messages", channel_id.value_ref()))
+                    .auth(self)
+                    .header(IDEMPOTENCY_KEY_HEADER, nonce)
+                    .json(&data)
+                    .send()
+                    .await?
+                    .process_error()
+                    .await?
+                    .json()
+                    .await?)
+            }
+            .await;
+
+            match result {
+                Err(error) if attempt < self.send_retries && is_transient(&error) => {
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                    backoff *= 2;
+                }
+                Err(Error::Api(ApiError::DuplicateNonce)) if attempt > 0 => {
+                    return self.fetch_sent_message(channel_id, nonce).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Find a message sent with the given nonce among the latest of the
+    /// channel.
+    async fn fetch_sent_message(
+        &self,
+        channel_id: &Id<ChannelMarker>,
+        nonce: &str,
+    ) -> Result<Message> {
+        let data = FetchMessagesData {
+            limit: Some(100),
+            sort: Some(MessageSort::Latest),
+            ..Default::default()
+        };
+        let messages = match self.fetch_messages(channel_id, &data).await? {
+            BulkMessageResponse::JustMessages(messages)
+            | BulkMessageResponse::MessagesAndUsers { messages, .. } => messages,
+        };
+
+        messages
+            .into_iter()
+            .find(|message| message.nonce.as_deref() == Some(nonce))
+            .ok_or_else(|| Error::AlreadySent {
+                nonce: nonce.to_string(),
+            })
     }
 
     /// Search for messages within the given parameters.
@@ -84,7 +180,7 @@ impl Client {
                 "/channels/{}/messages/search",
                 channel_id.value_ref()
             ))
-            .auth(&self.authentication)
+            .auth(self)
             .json(&data)
             .send()
             .await?
@@ -100,21 +196,13 @@ impl Client {
         channel_id: &Id<ChannelMarker>,
         message_id: &Id<ChannelMarker>,
     ) -> Result<Message> {
-        Ok(self
-            .client
-            .get(ep!(
-                self,
-                "/channels/{}/messages/{}",
-                channel_id.value_ref(),
-                message_id.value_ref()
-            ))
-            .auth(&self.authentication)
-            .send()
-            .await?
-            .process_error()
-            .await?
-            .json()
-            .await?)
+        self.get_coalesced(self.client.get(ep!(
+            self,
+            "/channels/{}/messages/{}",
+            channel_id.value_ref(),
+            message_id.value_ref()
+        )))
+        .await
     }
 
     /// Delete a message you've sent or one you have permission to delete.
@@ -123,6 +211,10 @@ impl Client {
         channel_id: &Id<ChannelMarker>,
         message_id: &Id<ChannelMarker>,
     ) -> Result<()> {
+        if self.preflight_foreign_message(&message_id.clone().cast()) {
+            self.preflight_channel(channel_id, Permission::ManageMessages)?;
+        }
+
         self.client
             .delete(ep!(
                 self,
@@ -130,7 +222,7 @@ impl Client {
                 channel_id.value_ref(),
                 message_id.value_ref()
             ))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -144,6 +236,8 @@ impl Client {
         message_id: &Id<MessageMarker>,
         data: &EditMessageData,
     ) -> Result<Message> {
+        self.validate(data)?;
+
         Ok(self
             .client
             .patch(ep!(
@@ -152,7 +246,7 @@ impl Client {
                 channel_id.value_ref(),
                 message_id.value_ref()
             ))
-            .auth(&self.authentication)
+            .auth(self)
             .json(&data)
             .send()
             .await?
@@ -178,7 +272,7 @@ impl Client {
                 "/channels/{}/messages/bulk",
                 channel_id.value_ref(),
             ))
-            .auth(&self.authentication)
+            .auth(self)
             .json(&data)
             .send()
             .await?
@@ -187,3 +281,105 @@ impl Client {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use rive_models::{
+        data::{CreateServerData, FetchMessagesData, SendMessageData},
+        message::BulkMessageResponse,
+        validate::ValidationError,
+    };
+    use tokio::time::Instant;
+
+    use crate::{generate_nonce, test_util::FakeServer, Client, Config, Error};
+
+    #[tokio::test]
+    async fn retries_lost_sends() {
+        let server = FakeServer::start().await.unwrap();
+        let account = server.add_bot("retries");
+        let client = server.client(account.authentication.clone());
+        let created = client
+            .create_server(&CreateServerData {
+                name: "Retries".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let channel_id = &created.channels[0];
+
+        let client = Client::with_config(Config {
+            base_url: server.url(),
+            timeout: Some(Duration::from_millis(200)),
+            send_retries: 2,
+            send_retry_backoff: Duration::from_millis(100),
+            ..Config::new(account.authentication)
+        });
+        let data = SendMessageData {
+            content: Some("hello"),
+            ..Default::default()
+        };
+        let nonce = generate_nonce();
+
+        // the first attempt creates the message but its response is lost
+        server.stall_responses(1, Duration::from_secs(1));
+        let started = Instant::now();
+        let message = client
+            .send_message_with_nonce(channel_id, &data, &nonce)
+            .await
+            .unwrap();
+        assert_eq!(message.nonce.as_deref(), Some(nonce.as_str()));
+        assert!(started.elapsed() >= Duration::from_millis(300));
+
+        let messages = client
+            .fetch_messages(channel_id, &FetchMessagesData::default())
+            .await
+            .unwrap();
+        let BulkMessageResponse::JustMessages(messages) = messages else {
+            panic!("users were not requested");
+        };
+        assert_eq!(messages.len(), 1);
+
+        // every attempt times out
+        server.stall_responses(3, Duration::from_secs(1));
+        let error = client.send_message(channel_id, &data).await.unwrap_err();
+        assert!(matches!(error, Error::Timeout(_)));
+    }
+
+    #[tokio::test]
+    async fn validates_payloads() {
+        let server = FakeServer::start().await.unwrap();
+        let account = server.add_bot("validation");
+        let client = server.client(account.authentication.clone());
+        let created = client
+            .create_server(&CreateServerData {
+                name: "Validation".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let requests = server.request_count();
+        let error = client
+            .send_message(&created.channels[0], &SendMessageData::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            Error::Validation(ValidationError::EmptyMessage)
+        ));
+        assert_eq!(server.request_count(), requests);
+
+        let client = Client::with_config(Config {
+            base_url: server.url(),
+            validate_payloads: false,
+            ..Config::new(account.authentication)
+        });
+        let error = client
+            .send_message(&created.channels[0], &SendMessageData::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(error, Error::Api(_)));
+    }
+}
@@ -10,7 +10,7 @@ impl Client {
         Ok(self
             .client
             .post(ep!(self, "/channels/{}/join_call", id.value_ref()))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -17,7 +17,7 @@ impl Client {
             .client
             .post(ep!(self, "/channels/{}/webhooks", channel_id.value_ref()))
             .json(&data)
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -28,15 +28,11 @@ impl Client {
 
     /// Get all webhooks inside the channel.
     pub async fn get_all_webhooks(&self, channel_id: &Id<ChannelMarker>) -> Result<Vec<Webhook>> {
-        Ok(self
-            .client
-            .get(ep!(self, "/channels/{}/webhooks", channel_id.value_ref()))
-            .auth(&self.authentication)
-            .send()
-            .await?
-            .process_error()
-            .await?
-            .json()
-            .await?)
+        self.get_coalesced(self.client.get(ep!(
+            self,
+            "/channels/{}/webhooks",
+            channel_id.value_ref()
+        )))
+        .await
     }
 }
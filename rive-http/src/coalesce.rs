@@ -0,0 +1,278 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use http::StatusCode;
+use hyper::body::Bytes;
+use rive_models::{authentication::Authentication, error::ApiError};
+use serde::de::DeserializeOwned;
+use tokio::sync::watch;
+
+use crate::{api_error, prelude::*, Error};
+
+/// Requests are identical if they have the same URL, authentication and MFA
+/// ticket.
+type Key = (String, Authentication, Option<String>);
+
+/// Status and body of a response.
+type Payload = (StatusCode, Bytes);
+
+/// GET requests being sent, with the receivers of their response.
+#[derive(Debug, Default)]
+pub(crate) struct InFlight(Mutex<HashMap<Key, watch::Receiver<Option<Payload>>>>);
+
+impl InFlight {
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<Key, watch::Receiver<Option<Payload>>>> {
+        self.0.lock().expect("in-flight requests poisoned")
+    }
+}
+
+/// Part a caller takes in a request.
+enum Role {
+    /// The caller sends the request.
+    Leader(watch::Sender<Option<Payload>>),
+    /// The caller waits for the response of an identical request.
+    Follower(watch::Receiver<Option<Payload>>),
+}
+
+/// Request sent on behalf of identical concurrent ones.
+///
+/// Dropping it without a response, when the request fails or the caller is
+/// cancelled, lets one of the waiting callers send the request instead.
+struct Flight<'a> {
+    in_flight: &'a InFlight,
+    key: Key,
+    sender: watch::Sender<Option<Payload>>,
+}
+
+impl Flight<'_> {
+    /// Stop sharing the request with new callers, then share its response
+    /// with the waiting ones.
+    fn land(self, payload: Payload) {
+        self.leave();
+        self.sender.send_replace(Some(payload));
+    }
+
+    /// Remove the request from the in-flight ones, unless an identical
+    /// request already replaced it.
+    fn leave(&self) {
+        let mut in_flight = self.in_flight.lock();
+        let current = in_flight.get(&self.key).map_or(false, |receiver| {
+            receiver.same_channel(&self.sender.subscribe())
+        });
+
+        if current {
+            in_flight.remove(&self.key);
+        }
+    }
+}
+
+impl Drop for Flight<'_> {
+    fn drop(&mut self) {
+        self.leave();
+    }
+}
+
+impl Client {
+    /// Send a GET request and deserialize the response body.
+    ///
+    /// Identical concurrent requests share a single request and all receive
+    /// its response. If that request fails before a response is received,
+    /// one of the waiting callers sends it again on behalf of the others.
+    pub(crate) async fn get_coalesced<T: DeserializeOwned>(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<T> {
+        let request = request.auth(self).build()?;
+        let key = (
+            request.url().to_string(),
+            self.authentication(),
+            self.mfa_ticket.clone(),
+        );
+
+        loop {
+            let role = {
+                let mut in_flight = self.in_flight.lock();
+
+                match in_flight.get(&key) {
+                    Some(receiver) => Role::Follower(receiver.clone()),
+                    None => {
+                        let (sender, receiver) = watch::channel(None);
+                        in_flight.insert(key.clone(), receiver);
+                        Role::Leader(sender)
+                    }
+                }
+            };
+
+            let mut receiver = match role {
+                Role::Follower(receiver) => receiver,
+                Role::Leader(sender) => {
+                    let flight = Flight {
+                        in_flight: &self.in_flight,
+                        key,
+                        sender,
+                    };
+                    let payload = self.send_payload(request).await?;
+                    flight.land(payload.clone());
+
+                    return parse(payload);
+                }
+            };
+
+            // the timeout of this caller applies, not the one of the leader
+            let wait = receiver.wait_for(Option::is_some);
+            let shared = match self.request_timeout.or(self.timeout) {
+                Some(timeout) => tokio::time::timeout(timeout, wait)
+                    .await
+                    .map_err(|elapsed| Error::Timeout(Box::new(elapsed)))?,
+                None => wait.await,
+            }
+            .ok()
+            .and_then(|payload| payload.clone());
+
+            // the leader failed, so a follower takes over
+            if let Some(payload) = shared {
+                return parse(payload);
+            }
+        }
+    }
+
+    async fn send_payload(&self, request: reqwest::Request) -> Result<Payload> {
+        let response = self.client.execute(request).await?;
+        let status = response.status();
+
+        Ok((status, response.bytes().await?))
+    }
+}
+
+/// Deserialize a response body, or the API error it contains.
+fn parse<T: DeserializeOwned>((status, body): Payload) -> Result<T> {
+    match status.as_u16() {
+        200..=299 => Ok(serde_json::from_slice(&body)?),
+        // see `ResponseExt::process_error`
+        401 => Err(Error::Api(ApiError::Unauthenticated)),
+        _ => Err(api_error(status, &body)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use http::StatusCode;
+    use hyper::body::Bytes;
+    use rive_models::{
+        authentication::Authentication, data::CreateServerData, error::ApiError, id::Id,
+    };
+    use tokio::{sync::watch, time::Instant};
+
+    use super::{Flight, InFlight};
+    use crate::{test_util::FakeServer, Error};
+
+    #[test]
+    fn landed_flights_are_not_joined() {
+        let in_flight = InFlight::default();
+        let key = ("url".to_string(), Authentication::None, None);
+
+        let (sender, mut receiver) = watch::channel(None);
+        in_flight.lock().insert(key.clone(), receiver.clone());
+        let flight = Flight {
+            in_flight: &in_flight,
+            key: key.clone(),
+            sender,
+        };
+        flight.land((StatusCode::OK, Bytes::from_static(b"{}")));
+        assert!(in_flight.lock().is_empty());
+        assert!(receiver.borrow_and_update().is_some());
+
+        // a flight does not remove the identical one that replaced it
+        let (old, _) = watch::channel(None);
+        let (_new, receiver) = watch::channel(None);
+        in_flight.lock().insert(key.clone(), receiver);
+        drop(Flight {
+            in_flight: &in_flight,
+            key: key.clone(),
+            sender: old,
+        });
+        assert!(in_flight.lock().contains_key(&key));
+    }
+
+    #[tokio::test]
+    async fn followers_take_over_failed_requests() {
+        let server = FakeServer::start().await.unwrap();
+        let account = server.add_bot("coalesce");
+        let client = server.client(account.authentication);
+        let impatient = client.with_timeout(Duration::from_millis(100));
+
+        server.stall_responses(1, Duration::from_millis(500));
+        let requests = server.request_count();
+        let (leader, first, second, third) = tokio::join!(
+            impatient.fetch_user(&account.id),
+            client.fetch_user(&account.id),
+            client.fetch_user(&account.id),
+            client.fetch_user(&account.id),
+        );
+        assert!(matches!(leader, Err(Error::Timeout(_))));
+        for follower in [first, second, third] {
+            assert_eq!(follower.unwrap().id, account.id);
+        }
+        assert_eq!(server.request_count(), requests + 2);
+    }
+
+    #[tokio::test]
+    async fn impatient_followers_time_out() {
+        let server = FakeServer::start().await.unwrap();
+        let account = server.add_bot("coalesce");
+        let client = server.client(account.authentication);
+        let patient = client.with_timeout(Duration::from_secs(5));
+        let impatient = client.with_timeout(Duration::from_millis(100));
+
+        server.stall_responses(1, Duration::from_millis(500));
+        let requests = server.request_count();
+        let (leader, follower) = tokio::join!(patient.fetch_user(&account.id), async {
+            let started = Instant::now();
+            let result = impatient.fetch_user(&account.id).await;
+            (result, started.elapsed())
+        });
+        assert_eq!(leader.unwrap().id, account.id);
+        let (result, elapsed) = follower;
+        assert!(matches!(result, Err(Error::Timeout(_))));
+        assert!(elapsed < Duration::from_millis(400));
+        assert_eq!(server.request_count(), requests + 1);
+    }
+
+    #[tokio::test]
+    async fn identical_requests_are_shared() {
+        let server = FakeServer::start().await.unwrap();
+        let account = server.add_bot("coalesce");
+        let client = server.client(account.authentication);
+        let created = client
+            .create_server(&CreateServerData {
+                name: "Coalesce".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let requests = server.request_count();
+        let (first, second, third, server_result) = tokio::join!(
+            client.fetch_user(&account.id),
+            client.fetch_user(&account.id),
+            client.fetch_user(&account.id),
+            client.fetch_server(&created.id),
+        );
+        assert_eq!(first.unwrap().id, account.id);
+        assert_eq!(second.unwrap().id, account.id);
+        assert_eq!(third.unwrap().id, account.id);
+        assert_eq!(server_result.unwrap().id, created.id);
+        assert_eq!(server.request_count(), requests + 2);
+
+        let unknown = Id::new("01H9F3XJ2C2K6G8B4Y7Q9M0S1T".to_string());
+        let (first, second) =
+            tokio::join!(client.fetch_user(&unknown), client.fetch_user(&unknown));
+        assert!(matches!(first, Err(Error::Api(ApiError::NotFound))));
+        assert!(matches!(second, Err(Error::Api(ApiError::NotFound))));
+        assert_eq!(server.request_count(), requests + 3);
+
+        client.fetch_user(&account.id).await.unwrap();
+        assert_eq!(server.request_count(), requests + 4);
+    }
+}
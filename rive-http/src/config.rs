@@ -0,0 +1,83 @@
+use std::{sync::Arc, time::Duration};
+
+use rive_models::authentication::Authentication;
+
+use crate::{Preflight, SessionStore, BASE_URL};
+
+/// Default timeout for establishing a connection.
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default timeout for a whole request.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default amount of times a message send is retried.
+pub const DEFAULT_SEND_RETRIES: u32 = 2;
+
+/// Default delay before the first retry of a message send.
+pub const DEFAULT_SEND_RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Client configuration
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Authentication attached to requests.
+    pub authentication: Authentication,
+    /// Base URL of the API.
+    pub base_url: String,
+    /// Timeout for establishing a connection.
+    ///
+    /// If [`None`], connecting is not bounded in time.
+    pub connect_timeout: Option<Duration>,
+    /// Timeout for a whole request, from connecting to receiving the response
+    /// body.
+    ///
+    /// If [`None`], requests are not bounded in time.
+    pub timeout: Option<Duration>,
+    /// Store of the session of a user account.
+    ///
+    /// If the configured authentication is [`Authentication::None`], the
+    /// stored session is used instead. A session that cannot be loaded is
+    /// ignored.
+    pub session_store: Option<Arc<dyn SessionStore>>,
+    /// Amount of times a message send is retried after a timeout or a
+    /// connection error.
+    ///
+    /// Retries reuse the idempotency key of the send, so a message is never
+    /// created twice.
+    pub send_retries: u32,
+    /// Delay before the first retry of a message send, doubled for every
+    /// following retry.
+    pub send_retry_backoff: Duration,
+    /// Permission checks made before sending requests.
+    ///
+    /// If [`None`], every request is sent and permissions are only checked
+    /// by the API.
+    pub preflight: Option<Preflight>,
+    /// Whether to check request payloads against the limits of the API
+    /// before sending them.
+    ///
+    /// Payloads exceeding a limit fail with [`Error::Validation`]. Disable it
+    /// for instances configured with other limits.
+    ///
+    /// [`Error::Validation`]: crate::Error::Validation
+    pub validate_payloads: bool,
+}
+
+impl Config {
+    /// Create a new [`Config`] with the official instance base URL,
+    /// [`DEFAULT_CONNECT_TIMEOUT`], [`DEFAULT_TIMEOUT`],
+    /// [`DEFAULT_SEND_RETRIES`] and [`DEFAULT_SEND_RETRY_BACKOFF`], without a session store or preflight
+    /// checks and with payload validation.
+    pub fn new(authentication: Authentication) -> Self {
+        Self {
+            authentication,
+            base_url: BASE_URL.to_string(),
+            connect_timeout: Some(DEFAULT_CONNECT_TIMEOUT),
+            timeout: Some(DEFAULT_TIMEOUT),
+            session_store: None,
+            send_retries: DEFAULT_SEND_RETRIES,
+            send_retry_backoff: DEFAULT_SEND_RETRY_BACKOFF,
+            preflight: None,
+            validate_payloads: true,
+        }
+    }
+}
@@ -12,16 +12,11 @@ use crate::prelude::*;
 impl Client {
     /// Fetch an emoji by its ID.
     pub async fn fetch_emoji(&self, id: &Id<EmojiMarker>) -> Result<Emoji> {
-        Ok(self
-            .client
-            .get(ep!(self, "/custom/emoji/{}", id.value_ref()))
-            .auth(&self.authentication)
-            .send()
-            .await?
-            .process_error()
-            .await?
-            .json()
-            .await?)
+        self.get_coalesced(
+            self.client
+                .get(ep!(self, "/custom/emoji/{}", id.value_ref())),
+        )
+        .await
     }
 
     /// Create an emoji by its Autumn upload id.
@@ -34,7 +29,7 @@ impl Client {
             .client
             .put(ep!(self, "/custom/emoji/{}", id.value_ref()))
             .json(&data)
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -47,7 +42,7 @@ impl Client {
     pub async fn delete_emoji(&self, id: &Id<EmojiMarker>) -> Result<()> {
         self.client
             .delete(ep!(self, "/custom/emoji/{}", id.value_ref()))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -7,16 +7,8 @@ use rive_models::{
 impl Client {
     /// Fetch an invite by its ID.
     pub async fn fetch_invite(&self, id: &Id<InviteMarker>) -> Result<Invite> {
-        Ok(self
-            .client
-            .get(ep!(self, "/invites/{}", id.value_ref()))
-            .auth(&self.authentication)
-            .send()
-            .await?
-            .process_error()
-            .await?
-            .json()
-            .await?)
+        self.get_coalesced(self.client.get(ep!(self, "/invites/{}", id.value_ref())))
+            .await
     }
 
     /// Join an invite by its ID.
@@ -24,7 +16,7 @@ impl Client {
         Ok(self
             .client
             .post(ep!(self, "/invites/{}", id.value_ref()))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -37,7 +29,7 @@ impl Client {
     pub async fn delete_invite(&self, id: &Id<InviteMarker>) -> Result<()> {
         self.client
             .delete(ep!(self, "/invites/{}", id.value_ref()))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
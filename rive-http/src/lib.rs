@@ -3,15 +3,48 @@
 mod authentication;
 mod bots;
 mod channels;
+mod coalesce;
+mod config;
 mod customisation;
 mod invites;
 mod miscellaneous;
+mod nonce;
+mod outbox;
 mod platform_administration;
+mod preflight;
 mod revolt;
 mod servers;
+mod session_store;
 mod users;
 
-use rive_models::{authentication::Authentication, error::ApiError};
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
+
+pub use authentication::LoginError;
+pub use config::{
+    Config, DEFAULT_CONNECT_TIMEOUT, DEFAULT_SEND_RETRIES, DEFAULT_SEND_RETRY_BACKOFF,
+    DEFAULT_TIMEOUT,
+};
+pub use nonce::generate_nonce;
+pub use outbox::{
+    DeliveryStatus, Outbox, OutboxConfig, OutgoingMessage, DEFAULT_INITIAL_BACKOFF,
+    DEFAULT_MAX_BACKOFF,
+};
+pub use preflight::{PermissionSource, Preflight};
+pub use session_store::{FileSessionStore, InMemorySessionStore, SessionStore, StoredSession};
+
+use std::{
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use coalesce::InFlight;
+
+use rive_models::{
+    authentication::Authentication,
+    error::ApiError,
+    validate::{Validate, ValidationError},
+};
 
 type Result<T> = std::result::Result<T, Error>;
 
@@ -24,6 +57,7 @@ pub const BASE_URL: &str = "https://api.revolt.chat";
 
 /// Client error
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum Error {
     /// Data serialization/deserialization error
     #[error("Serde JSON serialization/deserialization error: {0}")]
@@ -31,11 +65,43 @@ pub enum Error {
 
     /// HTTP error
     #[error("Error while processing an HTTP request: {0}")]
-    HttpRequest(#[from] reqwest::Error),
+    HttpRequest(reqwest::Error),
+
+    /// The request did not complete within the configured timeout
+    #[error("Request timed out: {0}")]
+    Timeout(Box<dyn std::error::Error + Send + Sync>),
 
     /// An error returned from Revolt API
     #[error("Error returned from API: {0:#?}")]
     Api(ApiError),
+
+    /// The API responded with an error status and a body that is not an API
+    /// error, e.g. when ratelimited or when a proxy in front of it failed
+    #[error("Unexpected response status: {0}")]
+    UnexpectedStatus(http::StatusCode),
+
+    /// Session store error
+    #[error("Error while accessing the session store: {0}")]
+    SessionStore(std::io::Error),
+
+    /// The request payload exceeds a limit of the API
+    #[error("Invalid request payload: {0}")]
+    Validation(#[from] ValidationError),
+
+    /// A retried message send was already created by an earlier attempt, but
+    /// the message could not be found among the latest of the channel
+    #[error("Message with nonce {nonce} was already sent")]
+    AlreadySent { nonce: String },
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            Self::Timeout(Box::new(error))
+        } else {
+            Self::HttpRequest(error)
+        }
+    }
 }
 
 macro_rules! ep {
@@ -54,12 +120,31 @@ macro_rules! ep {
 pub(crate) use ep;
 
 trait RequestBuilderExt {
-    fn auth(self, authentication: &Authentication) -> Self;
+    /// Attach the authentication of the client, and apply its request
+    /// timeout if it overrides the client-wide one.
+    fn auth(self, client: &Client) -> Self;
 }
 
 impl RequestBuilderExt for reqwest::RequestBuilder {
-    fn auth(self, authentication: &Authentication) -> Self {
-        self.header(authentication.header_key(), authentication.value())
+    fn auth(mut self, client: &Client) -> Self {
+        if let Some(timeout) = client.request_timeout {
+            self = self.timeout(timeout);
+        }
+
+        let authentication = client
+            .authentication
+            .read()
+            .expect("authentication poisoned");
+
+        if !matches!(*authentication, Authentication::None) {
+            self = self.header(authentication.header_key(), authentication.value());
+        }
+
+        if let Some(ticket) = &client.mfa_ticket {
+            self = self.header("x-mfa-ticket", ticket);
+        }
+
+        self
     }
 }
 
@@ -79,33 +164,333 @@ impl ResponseExt for reqwest::Response {
             // NOTE: it's a workaround thing but there are no alternative ways
             // because API returns some rocket's HTML instead of parseable JSON
             401 => Err(Error::Api(ApiError::Unauthenticated)),
-            _ => Err(Error::Api(self.json().await?)),
+            _ => {
+                let status = self.status();
+                let body = self.bytes().await?;
+
+                Err(api_error(status, &body))
+            }
         }
     }
 }
 
+/// Error of a response with the given error status and body.
+fn api_error(status: http::StatusCode, body: &[u8]) -> Error {
+    match serde_json::from_slice(body) {
+        Ok(error) => Error::Api(error),
+        Err(_) => Error::UnexpectedStatus(status),
+    }
+}
+
+/// Revolt REST API client
+///
+/// Clones share the authentication, which changes on login and logout when a
+/// [`SessionStore`] is configured.
+///
+/// Identical concurrent GET requests, such as many handlers fetching the same
+/// user at once, are sent only once and share the response.
 #[derive(Debug, Clone)]
 pub struct Client {
     base_url: String,
     client: reqwest::Client,
-    authentication: Authentication,
+    authentication: Arc<RwLock<Authentication>>,
+    session_store: Option<Arc<dyn SessionStore>>,
+    send_retries: u32,
+    send_retry_backoff: Duration,
+    in_flight: Arc<InFlight>,
+    preflight: Option<Preflight>,
+    validate_payloads: bool,
+    /// Client-wide timeout of requests.
+    timeout: Option<Duration>,
+    request_timeout: Option<Duration>,
+    mfa_ticket: Option<String>,
 }
 
 impl Client {
     /// Create a client instance with the API base URL of Revolt official instance.
     pub fn new(authentication: Authentication) -> Self {
-        Self::new_base_url(authentication, BASE_URL)
+        Self::with_config(Config::new(authentication))
     }
 
     /// Create a client instance with given base URL.
     pub fn new_base_url(authentication: Authentication, base_url: impl ToString) -> Self {
-        Self {
+        Self::with_config(Config {
             base_url: base_url.to_string(),
-            client: reqwest::Client::builder()
-                .user_agent("rive-http")
-                .build()
-                .unwrap(),
-            authentication,
+            ..Config::new(authentication)
+        })
+    }
+
+    /// Create a client instance with given configuration.
+    pub fn with_config(config: Config) -> Self {
+        let mut builder = reqwest::Client::builder().user_agent("rive-http");
+
+        if let Some(connect_timeout) = config.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+
+        if let Some(timeout) = config.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        let mut authentication = config.authentication;
+        if matches!(authentication, Authentication::None) {
+            if let Some(Ok(Some(session))) = config.session_store.as_ref().map(|store| store.load())
+            {
+                authentication = Authentication::SessionToken(session.token);
+            }
         }
+
+        Self {
+            base_url: config.base_url,
+            client: builder.build().unwrap(),
+            authentication: Arc::new(RwLock::new(authentication)),
+            session_store: config.session_store,
+            send_retries: config.send_retries,
+            send_retry_backoff: config.send_retry_backoff,
+            in_flight: Arc::default(),
+            preflight: config.preflight,
+            validate_payloads: config.validate_payloads,
+            timeout: config.timeout,
+            request_timeout: None,
+            mfa_ticket: None,
+        }
+    }
+
+    /// Authentication currently attached to requests.
+    pub fn authentication(&self) -> Authentication {
+        self.authentication
+            .read()
+            .expect("authentication poisoned")
+            .clone()
+    }
+
+    /// Create a client sending requests with another authentication.
+    ///
+    /// The returned client shares the connection pool of this one, but its
+    /// authentication is independent and it has no session store, preflight
+    /// checks or MFA ticket. It is meant for calls needing another credential
+    /// or no credential at all:
+    ///
+    /// ```no_run
+    /// # async fn example(client: rive_http::Client) -> Result<(), rive_http::Error> {
+    /// use rive_models::id::Id;
+    ///
+    /// let invite = client
+    ///     .without_authentication()
+    ///     .fetch_invite(&Id::new("Testers".to_string()))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_authentication(&self, authentication: Authentication) -> Self {
+        Self {
+            base_url: self.base_url.clone(),
+            client: self.client.clone(),
+            authentication: Arc::new(RwLock::new(authentication)),
+            session_store: None,
+            send_retries: self.send_retries,
+            send_retry_backoff: self.send_retry_backoff,
+            in_flight: Arc::clone(&self.in_flight),
+            preflight: None,
+            validate_payloads: self.validate_payloads,
+            timeout: self.timeout,
+            request_timeout: self.request_timeout,
+            mfa_ticket: None,
+        }
+    }
+
+    /// Create a client sending requests without authentication.
+    ///
+    /// See [`with_authentication`](Self::with_authentication).
+    pub fn without_authentication(&self) -> Self {
+        self.with_authentication(Authentication::None)
+    }
+
+    /// Create a client sending an MFA ticket along with its authentication.
+    ///
+    /// The returned client shares the connection pool and the authentication
+    /// of this one. Endpoints changing the MFA settings of an account require
+    /// both the session token and a ticket validated by
+    /// [`create_mfa_ticket`](Self::create_mfa_ticket):
+    ///
+    /// ```no_run
+    /// # async fn example(client: rive_http::Client) -> Result<(), rive_http::Error> {
+    /// use rive_models::mfa::MFAData;
+    ///
+    /// let ticket = client
+    ///     .create_mfa_ticket(&MFAData::Password {
+    ///         password: "password".to_string(),
+    ///     })
+    ///     .await?;
+    /// let codes = client
+    ///     .with_mfa_ticket(ticket.token)
+    ///     .generate_recovery_codes()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_mfa_ticket(&self, ticket: impl Into<String>) -> Self {
+        Self {
+            mfa_ticket: Some(ticket.into()),
+            ..self.clone()
+        }
+    }
+
+    /// Create a client checking permissions before sending requests.
+    ///
+    /// The returned client shares the connection pool and the authentication
+    /// of this one. See [`Preflight`].
+    pub fn with_preflight(&self, preflight: Preflight) -> Self {
+        Self {
+            preflight: Some(preflight),
+            ..self.clone()
+        }
+    }
+
+    /// Create a client whose requests time out after the given duration
+    /// instead of [`Config::timeout`].
+    ///
+    /// The returned client shares the connection pool and the authentication
+    /// of this one:
+    ///
+    /// ```no_run
+    /// # async fn example(client: rive_http::Client) -> Result<(), rive_http::Error> {
+    /// use std::time::Duration;
+    ///
+    /// let user = client
+    ///     .with_timeout(Duration::from_secs(2))
+    ///     .fetch_self()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_timeout(&self, timeout: Duration) -> Self {
+        Self {
+            request_timeout: Some(timeout),
+            ..self.clone()
+        }
+    }
+
+    /// Replace the authentication attached to requests.
+    pub fn set_authentication(&self, authentication: Authentication) {
+        *self
+            .authentication
+            .write()
+            .expect("authentication poisoned") = authentication;
+    }
+
+    /// Fail if the payload exceeds a limit of the API.
+    pub(crate) fn validate(&self, data: &impl Validate) -> Result<()> {
+        if self.validate_payloads {
+            data.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use rive_models::authentication::Authentication;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+        sync::mpsc,
+    };
+
+    use crate::{Client, Config, Error};
+
+    /// Start a server accepting connections but never responding.
+    async fn stalled_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                connections.push(stream);
+            }
+        });
+
+        url
+    }
+
+    #[tokio::test]
+    async fn timeouts() {
+        let url = stalled_server().await;
+        let client = Client::with_config(Config {
+            base_url: url,
+            timeout: Some(Duration::from_millis(100)),
+            ..Config::new(Authentication::None)
+        });
+
+        let result = client.fetch_self().await;
+        assert!(matches!(result, Err(Error::Timeout(_))));
+
+        let client = Client::with_config(Config {
+            base_url: stalled_server().await,
+            timeout: None,
+            ..Config::new(Authentication::None)
+        })
+        .with_timeout(Duration::from_millis(100));
+        let result = tokio::time::timeout(Duration::from_secs(5), client.fetch_self()).await;
+        assert!(matches!(result, Ok(Err(Error::Timeout(_)))));
+    }
+
+    /// Start a server answering every request with an empty array, and
+    /// forwarding the head of each request, in lowercase.
+    async fn recording_server() -> (String, mpsc::UnboundedReceiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::unbounded_channel();
+
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let sender = sender.clone();
+                tokio::spawn(async move {
+                    let mut request = [0; 4096];
+                    let read = stream.read(&mut request).await.unwrap();
+                    let _ = sender.send(String::from_utf8_lossy(&request[..read]).to_lowercase());
+                    let _ = stream
+                        .write_all(
+                            b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\n[]",
+                        )
+                        .await;
+                });
+            }
+        });
+
+        (url, receiver)
+    }
+
+    #[tokio::test]
+    async fn authentication_headers() {
+        let (url, mut requests) = recording_server().await;
+        let client = Client::new_base_url(Authentication::SessionToken("token".to_string()), url);
+
+        client
+            .with_mfa_ticket("ticket")
+            .generate_recovery_codes()
+            .await
+            .unwrap();
+        let request = requests.recv().await.unwrap();
+        assert!(request.contains("\r\nx-session-token: token\r\n"));
+        assert!(request.contains("\r\nx-mfa-ticket: ticket\r\n"));
+
+        client.generate_recovery_codes().await.unwrap();
+        let request = requests.recv().await.unwrap();
+        assert!(request.contains("\r\nx-session-token: token\r\n"));
+        assert!(!request.contains("x-mfa-ticket"));
+
+        client
+            .without_authentication()
+            .generate_recovery_codes()
+            .await
+            .unwrap();
+        let request = requests.recv().await.unwrap();
+        assert!(!request.contains("x-session-token"));
+        assert!(!request.contains("\r\n: "));
     }
 }
@@ -9,7 +9,7 @@ impl Client {
             .client
             .post(ep!(self, "/sync/settings/fetch"))
             .json(&data)
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -23,7 +23,7 @@ impl Client {
         self.client
             .post(ep!(self, "/sync/settings/set"))
             .json(&data)
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -33,15 +33,7 @@ impl Client {
 
     /// Fetch information about unread state on channels.
     pub async fn fetch_unreads(&self) -> Result<Vec<ChannelUnread>> {
-        Ok(self
-            .client
-            .get(ep!(self, "/sync/unreads"))
-            .auth(&self.authentication)
-            .send()
-            .await?
-            .process_error()
-            .await?
-            .json()
-            .await?)
+        self.get_coalesced(self.client.get(ep!(self, "/sync/unreads")))
+            .await
     }
 }
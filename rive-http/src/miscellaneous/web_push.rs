@@ -9,7 +9,7 @@ impl Client {
         self.client
             .post(ep!(self, "/push/subscribe"))
             .json(&data)
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -21,7 +21,7 @@ impl Client {
     pub async fn push_unsubscribe(&self) -> Result<()> {
         self.client
             .post(ep!(self, "/push/unsubscribe"))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -0,0 +1,20 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rive_models::id::{marker::MessageMarker, Id};
+
+/// Generate a ULID to be used as the idempotency key of a message send.
+///
+/// The API stores it as the nonce of the created message, so the
+/// [`ServerEvent::Message`] echoed by the gateway can be matched with the
+/// send it originates from.
+///
+/// [`ServerEvent::Message`]: rive_models::event::ServerEvent::Message
+pub fn generate_nonce() -> String {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock may have gone backwards")
+        .as_millis();
+    let randomness = fastrand::u128(..) & ((1 << 80) - 1);
+
+    Id::<MessageMarker>::from_ulid((millis << 80) | randomness).value()
+}
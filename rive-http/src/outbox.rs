@@ -0,0 +1,725 @@
+//! Persistent queue of outgoing messages.
+//!
+//! See [`Outbox`].
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+
+use http::StatusCode;
+use rive_models::{
+    data::SendMessageData,
+    embed::SendableEmbed,
+    error::ApiError,
+    id::{
+        marker::{AttachmentMarker, ChannelMarker, MessageMarker},
+        Id,
+    },
+    message::{Interactions, Masquerade, Reply},
+};
+use serde::{Deserialize, Serialize};
+use tokio::{
+    sync::{Mutex as AsyncMutex, Notify},
+    task,
+    time::sleep,
+};
+use tracing::warn;
+
+use crate::{generate_nonce, Client, Error};
+
+/// Default delay before the first retry of a failed send.
+pub const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Default maximum delay between retries of a failed send.
+pub const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Owned message queued in an [`Outbox`].
+///
+/// It mirrors [`SendMessageData`].
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct OutgoingMessage {
+    /// Message content to send
+    pub content: Option<String>,
+    /// Attachments to include in message
+    pub attachments: Option<Vec<Id<AttachmentMarker>>>,
+    /// Messages to reply to
+    pub replies: Option<Vec<Reply>>,
+    /// Embeds to include in message
+    pub embeds: Option<Vec<SendableEmbed>>,
+    /// Masquerade to apply to this message
+    pub masquerade: Option<Masquerade>,
+    /// Information about how this message should be interacted with
+    pub interactions: Option<Interactions>,
+}
+
+/// Delivery status of a message queued in an [`Outbox`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeliveryStatus {
+    /// The message is waiting to be sent.
+    Pending {
+        /// Amount of failed attempts so far.
+        attempts: u32,
+        /// Error of the last failed attempt.
+        last_error: Option<String>,
+    },
+    /// The message was sent.
+    ///
+    /// The message ID is unknown if the message was created by an attempt
+    /// whose response was lost.
+    Delivered {
+        /// ID of the created message.
+        message_id: Option<Id<MessageMarker>>,
+    },
+    /// The message was rejected by the API or ran out of attempts, and was
+    /// removed from the outbox.
+    Failed {
+        /// Error of the last attempt.
+        error: String,
+    },
+}
+
+impl DeliveryStatus {
+    /// Whether the message is still queued.
+    pub const fn is_pending(&self) -> bool {
+        matches!(self, Self::Pending { .. })
+    }
+}
+
+/// [`Outbox`] configuration
+#[derive(Debug, Clone)]
+pub struct OutboxConfig {
+    /// Delay before the first retry of a failed send.
+    ///
+    /// The delay doubles after every failed attempt.
+    pub initial_backoff: Duration,
+    /// Maximum delay between retries of a failed send.
+    pub max_backoff: Duration,
+    /// Maximum amount of attempts of a send.
+    ///
+    /// If [`None`], a send is retried until it succeeds or is rejected by the
+    /// API.
+    pub max_attempts: Option<u32>,
+}
+
+impl Default for OutboxConfig {
+    /// Create a new [`OutboxConfig`] with [`DEFAULT_INITIAL_BACKOFF`] and
+    /// [`DEFAULT_MAX_BACKOFF`], retrying without limit.
+    fn default() -> Self {
+        Self {
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            max_attempts: None,
+        }
+    }
+}
+
+/// Message waiting in the outbox.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct Entry {
+    /// Idempotency key of the send.
+    nonce: String,
+    channel_id: Id<ChannelMarker>,
+    message: OutgoingMessage,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    /// Queued messages by channel, in sending order.
+    queues: HashMap<Id<ChannelMarker>, VecDeque<Entry>>,
+    /// Channels whose queue is being sent.
+    running: HashSet<Id<ChannelMarker>>,
+    /// Delivery statuses by nonce.
+    statuses: HashMap<String, DeliveryStatus>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    client: Client,
+    config: OutboxConfig,
+    path: PathBuf,
+    state: Mutex<State>,
+    /// Held while the backing file is written, so writes happen in the order
+    /// of the changes they save.
+    writing: AsyncMutex<()>,
+    /// Notified whenever a delivery status changes.
+    updated: Notify,
+}
+
+/// Persistent queue of outgoing messages.
+///
+/// Queued messages are saved to a JSON file, so they survive restarts, and
+/// are sent in the background. Failed sends are retried with exponential
+/// backoff, using the same idempotency key so a message is never created
+/// twice. Messages to the same channel are sent in the order they were
+/// queued, and a channel with failing sends does not hold back the others.
+///
+/// Dropping the outbox does not stop the sending of queued messages.
+#[derive(Debug, Clone)]
+pub struct Outbox {
+    inner: Arc<Inner>,
+}
+
+impl Outbox {
+    /// Open an outbox backed by the file at the given path, with default
+    /// configuration.
+    ///
+    /// Messages left in the file are sent again.
+    pub fn open(client: Client, path: impl Into<PathBuf>) -> io::Result<Self> {
+        Self::with_config(client, path, OutboxConfig::default())
+    }
+
+    /// Open an outbox backed by the file at the given path, with given
+    /// configuration.
+    ///
+    /// Messages left in the file are sent again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime and the file contains
+    /// messages.
+    pub fn with_config(
+        client: Client,
+        path: impl Into<PathBuf>,
+        config: OutboxConfig,
+    ) -> io::Result<Self> {
+        let path = path.into();
+        let entries: Vec<Entry> = match fs::read(&path) {
+            Ok(contents) => serde_json::from_slice(&contents)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(error) => return Err(error),
+        };
+
+        let mut state = State::default();
+        for entry in entries {
+            state.statuses.insert(
+                entry.nonce.clone(),
+                DeliveryStatus::Pending {
+                    attempts: 0,
+                    last_error: None,
+                },
+            );
+            state
+                .queues
+                .entry(entry.channel_id.clone())
+                .or_default()
+                .push_back(entry);
+        }
+
+        state.running = state.queues.keys().cloned().collect();
+        let channels = state.running.iter().cloned().collect::<Vec<_>>();
+
+        let inner = Arc::new(Inner {
+            client,
+            config,
+            path,
+            state: Mutex::new(state),
+            writing: AsyncMutex::new(()),
+            updated: Notify::new(),
+        });
+        for channel_id in channels {
+            tokio::spawn(Arc::clone(&inner).drain(channel_id));
+        }
+
+        Ok(Self { inner })
+    }
+
+    /// Queue a message to be sent to a channel.
+    ///
+    /// The message is saved to the backing file before being queued. Returns
+    /// the idempotency key of the send, which identifies the message in the
+    /// outbox and becomes the nonce of the created message.
+    pub async fn enqueue(
+        &self,
+        channel_id: &Id<ChannelMarker>,
+        message: OutgoingMessage,
+    ) -> io::Result<String> {
+        let nonce = generate_nonce();
+        let entry = Entry {
+            nonce: nonce.clone(),
+            channel_id: channel_id.clone(),
+            message,
+        };
+
+        // the message is only queued once saved, and other writes wait for
+        // it to be queued so that they save it as well
+        let _writing = self.inner.writing.lock().await;
+        let contents = {
+            let state = self.inner.lock();
+            let entries = state
+                .queues
+                .values()
+                .flatten()
+                .chain([&entry])
+                .collect::<Vec<_>>();
+
+            serde_json::to_vec(&entries)?
+        };
+        self.inner.write(contents).await?;
+
+        let mut state = self.inner.lock();
+        state.statuses.insert(
+            nonce.clone(),
+            DeliveryStatus::Pending {
+                attempts: 0,
+                last_error: None,
+            },
+        );
+        state
+            .queues
+            .entry(channel_id.clone())
+            .or_default()
+            .push_back(entry);
+        if state.running.insert(channel_id.clone()) {
+            tokio::spawn(Arc::clone(&self.inner).drain(channel_id.clone()));
+        }
+
+        Ok(nonce)
+    }
+
+    /// Delivery status of a message, if it was queued in this outbox.
+    pub fn status(&self, nonce: &str) -> Option<DeliveryStatus> {
+        self.inner.lock().statuses.get(nonce).cloned()
+    }
+
+    /// Wait until a message is delivered or fails.
+    ///
+    /// Returns [`None`] if the message was not queued in this outbox.
+    pub async fn wait_for(&self, nonce: &str) -> Option<DeliveryStatus> {
+        loop {
+            let updated = self.inner.updated.notified();
+            tokio::pin!(updated);
+            updated.as_mut().enable();
+
+            match self.status(nonce) {
+                Some(status) if status.is_pending() => updated.await,
+                status => return status,
+            }
+        }
+    }
+
+    /// Amount of messages waiting to be sent.
+    pub fn pending(&self) -> usize {
+        self.inner.lock().queues.values().map(VecDeque::len).sum()
+    }
+
+    /// Forget the statuses of delivered and failed messages.
+    pub fn clear_finished(&self) {
+        self.inner
+            .lock()
+            .statuses
+            .retain(|_, status| status.is_pending());
+    }
+}
+
+impl Inner {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().expect("outbox state poisoned")
+    }
+
+    /// Replace the backing file with the queued messages.
+    async fn persist(&self) -> io::Result<()> {
+        let _writing = self.writing.lock().await;
+        let contents = {
+            let state = self.lock();
+            let entries = state.queues.values().flatten().collect::<Vec<_>>();
+
+            serde_json::to_vec(&entries)?
+        };
+
+        self.write(contents).await
+    }
+
+    /// Atomically replace the backing file with the given contents, off the
+    /// async runtime.
+    async fn write(&self, contents: Vec<u8>) -> io::Result<()> {
+        let path = self.path.clone();
+
+        task::spawn_blocking(move || write_atomically(&path, &contents))
+            .await
+            .map_err(|error| io::Error::new(io::ErrorKind::Other, error))?
+    }
+
+    /// Send the queued messages of a channel in order, until the queue is
+    /// empty.
+    async fn drain(self: Arc<Self>, channel_id: Id<ChannelMarker>) {
+        let mut attempts = 0;
+        let mut backoff = self.config.initial_backoff;
+
+        loop {
+            let entry = {
+                let mut state = self.lock();
+
+                match state.queues.get(&channel_id).and_then(VecDeque::front) {
+                    Some(entry) => entry.clone(),
+                    None => {
+                        state.queues.remove(&channel_id);
+                        state.running.remove(&channel_id);
+                        return;
+                    }
+                }
+            };
+
+            let status = match self.send(&entry).await {
+                Ok(message_id) => DeliveryStatus::Delivered { message_id },
+                Err(error) if is_transient(&error) => {
+                    attempts += 1;
+
+                    if self
+                        .config
+                        .max_attempts
+                        .map_or(true, |max_attempts| attempts < max_attempts)
+                    {
+                        self.update(
+                            &entry.nonce,
+                            DeliveryStatus::Pending {
+                                attempts,
+                                last_error: Some(error.to_string()),
+                            },
+                        );
+
+                        sleep(backoff).await;
+                        backoff = (backoff * 2).min(self.config.max_backoff);
+                        continue;
+                    }
+
+                    DeliveryStatus::Failed {
+                        error: error.to_string(),
+                    }
+                }
+                Err(error) => DeliveryStatus::Failed {
+                    error: error.to_string(),
+                },
+            };
+
+            if let Some(queue) = self.lock().queues.get_mut(&channel_id) {
+                queue.pop_front();
+            }
+            if let Err(error) = self.persist().await {
+                warn!(?error, "failed to persist the outbox");
+            }
+            self.update(&entry.nonce, status);
+
+            attempts = 0;
+            backoff = self.config.initial_backoff;
+        }
+    }
+
+    async fn send(&self, entry: &Entry) -> Result<Option<Id<MessageMarker>>, Error> {
+        let message = &entry.message;
+        let replies = message
+            .replies
+            .as_ref()
+            .map(|replies| replies.iter().map(Reply::as_borrowed).collect::<Vec<_>>());
+        let embeds = message.embeds.as_ref().map(|embeds| {
+            embeds
+                .iter()
+                .map(SendableEmbed::as_borrowed)
+                .collect::<Vec<_>>()
+        });
+        let masquerade = message.masquerade.as_ref().map(Masquerade::as_borrowed);
+        let interactions = message.interactions.as_ref().map(Interactions::as_borrowed);
+
+        let data = SendMessageData {
+            content: message.content.as_deref(),
+            attachments: message.attachments.as_deref(),
+            replies: replies.as_deref(),
+            embeds: embeds.as_deref(),
+            masquerade: masquerade.as_ref(),
+            interactions: interactions.as_ref(),
+        };
+
+        match self
+            .client
+            .send_message_with_nonce(&entry.channel_id, &data, &entry.nonce)
+            .await
+        {
+            Ok(message) => Ok(Some(message.id)),
+            // an earlier attempt created the message, which could not be
+            // found again
+            Err(Error::Api(ApiError::DuplicateNonce) | Error::AlreadySent { .. }) => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    fn update(&self, nonce: &str, status: DeliveryStatus) {
+        self.lock().statuses.insert(nonce.to_string(), status);
+        self.updated.notify_waiters();
+    }
+}
+
+/// Write a file through a temporary one, so it is never left half written.
+fn write_atomically(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut temporary = path.to_path_buf().into_os_string();
+    temporary.push(".tmp");
+
+    fs::write(&temporary, contents)?;
+    fs::rename(&temporary, path)
+}
+
+/// Whether a send may succeed if retried.
+///
+/// Only failures of the connection and of the API itself are transient: a
+/// response that cannot be deserialized would be the same on every attempt.
+fn is_transient(error: &Error) -> bool {
+    match error {
+        Error::HttpRequest(_) | Error::Timeout(_) => true,
+        Error::Api(error) => matches!(
+            error,
+            ApiError::InternalError | ApiError::DatabaseError { .. } | ApiError::VosoUnavailable
+        ),
+        Error::UnexpectedStatus(status) => {
+            *status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::PathBuf, time::Duration};
+
+    use http::StatusCode;
+    use rive_models::{
+        data::{CreateServerData, FetchMessagesData, SendMessageData},
+        id::{marker::ChannelMarker, Id},
+        message::BulkMessageResponse,
+    };
+
+    use super::{is_transient, DeliveryStatus, Outbox, OutboxConfig, OutgoingMessage};
+    use crate::{test_util::FakeServer, Client, Config, Error};
+
+    fn path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!(
+            "rive-http-outbox-{name}-{}.json",
+            std::process::id()
+        ))
+    }
+
+    async fn enqueue(outbox: &Outbox, channel_id: &Id<ChannelMarker>, content: &str) -> String {
+        let message = OutgoingMessage {
+            content: Some(content.to_string()),
+            ..Default::default()
+        };
+
+        outbox.enqueue(channel_id, message).await.unwrap()
+    }
+
+    async fn contents(client: &Client, channel_id: &Id<ChannelMarker>) -> Vec<String> {
+        let messages = client
+            .fetch_messages(channel_id, &FetchMessagesData::default())
+            .await
+            .unwrap();
+        let BulkMessageResponse::JustMessages(messages) = messages else {
+            panic!("expected messages only");
+        };
+
+        messages
+            .into_iter()
+            .rev()
+            .filter_map(|message| message.content)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn delivers_in_order() {
+        let server = FakeServer::start().await.unwrap();
+        let account = server.add_bot("outbox");
+        let client = server.client(account.authentication);
+        let created = client
+            .create_server(&CreateServerData {
+                name: "Outbox".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let channel_id = &created.channels[0];
+
+        let path = path("order");
+        let outbox = Outbox::with_config(
+            client.clone(),
+            &path,
+            OutboxConfig {
+                initial_backoff: Duration::from_millis(10),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let mut nonces = Vec::new();
+        for content in ["first", "second", "third"] {
+            nonces.push(enqueue(&outbox, channel_id, content).await);
+        }
+        let empty = outbox
+            .enqueue(channel_id, OutgoingMessage::default())
+            .await
+            .unwrap();
+
+        for nonce in &nonces {
+            let status = outbox.wait_for(nonce).await.unwrap();
+            assert!(matches!(
+                status,
+                DeliveryStatus::Delivered {
+                    message_id: Some(_)
+                }
+            ));
+        }
+        let status = outbox.wait_for(&empty).await.unwrap();
+        assert!(matches!(status, DeliveryStatus::Failed { .. }));
+        assert_eq!(outbox.pending(), 0);
+
+        assert_eq!(
+            contents(&client, channel_id).await,
+            ["first", "second", "third"]
+        );
+        fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn retries_transient_failures() {
+        let serialization = serde_json::from_str::<u8>("{}").unwrap_err();
+        assert!(!is_transient(&Error::Serialization(serialization)));
+        assert!(!is_transient(&Error::UnexpectedStatus(
+            StatusCode::NOT_FOUND
+        )));
+        assert!(is_transient(&Error::UnexpectedStatus(
+            StatusCode::BAD_GATEWAY
+        )));
+
+        let server = FakeServer::start().await.unwrap();
+        let account = server.add_bot("outbox");
+        let client = server.client(account.authentication.clone());
+        let created = client
+            .create_server(&CreateServerData {
+                name: "Outbox".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let channel_id = &created.channels[0];
+
+        let impatient = Client::with_config(Config {
+            base_url: server.url(),
+            timeout: Some(Duration::from_millis(100)),
+            send_retries: 0,
+            ..Config::new(account.authentication)
+        });
+        let path = path("retries");
+        let config = OutboxConfig {
+            initial_backoff: Duration::from_millis(50),
+            max_attempts: Some(2),
+            ..Default::default()
+        };
+        let outbox = Outbox::with_config(impatient, &path, config).unwrap();
+
+        // both attempts time out, the first one having created the message
+        server.stall_responses(2, Duration::from_secs(1));
+        let nonce = enqueue(&outbox, channel_id, "first").await;
+        let status = outbox.wait_for(&nonce).await.unwrap();
+        assert!(matches!(status, DeliveryStatus::Failed { .. }));
+
+        // the second attempt finds the message created by the first one
+        server.stall_responses(1, Duration::from_secs(1));
+        let requests = server.request_count();
+        let nonce = enqueue(&outbox, channel_id, "second").await;
+        let status = outbox.wait_for(&nonce).await.unwrap();
+        assert_eq!(status, DeliveryStatus::Delivered { message_id: None });
+        assert_eq!(server.request_count(), requests + 2);
+
+        assert_eq!(contents(&client, channel_id).await, ["first", "second"]);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn delivers_buried_messages() {
+        let server = FakeServer::start().await.unwrap();
+        server.set_ratelimits_enforced(false);
+        let account = server.add_bot("outbox");
+        let client = server.client(account.authentication.clone());
+        let created = client
+            .create_server(&CreateServerData {
+                name: "Outbox".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let channel_id = &created.channels[0];
+
+        let impatient = Client::with_config(Config {
+            base_url: server.url(),
+            timeout: Some(Duration::from_millis(200)),
+            send_retries: 1,
+            send_retry_backoff: Duration::from_secs(1),
+            ..Config::new(account.authentication)
+        });
+        let path = path("buried");
+        let outbox = Outbox::with_config(impatient, &path, OutboxConfig::default()).unwrap();
+
+        // the first attempt creates the message but its response is lost,
+        // then the message is buried under newer ones before the retry
+        server.stall_responses(1, Duration::from_secs(2));
+        let nonce = enqueue(&outbox, channel_id, "buried").await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let filler = SendMessageData {
+            content: Some("filler"),
+            ..Default::default()
+        };
+        for _ in 0..100 {
+            client.send_message(channel_id, &filler).await.unwrap();
+        }
+
+        let status = outbox.wait_for(&nonce).await.unwrap();
+        assert_eq!(status, DeliveryStatus::Delivered { message_id: None });
+        fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn resends_persisted_messages() {
+        let server = FakeServer::start().await.unwrap();
+        let account = server.add_bot("outbox");
+        let client = server.client(account.authentication.clone());
+        let created = client
+            .create_server(&CreateServerData {
+                name: "Outbox".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let channel_id = &created.channels[0];
+
+        // messages stay queued while the API cannot be reached
+        let unreachable = Client::with_config(Config {
+            base_url: "http://127.0.0.1:1".to_string(),
+            send_retries: 0,
+            ..Config::new(account.authentication)
+        });
+        let path = path("reload");
+        let config = OutboxConfig {
+            initial_backoff: Duration::from_secs(60),
+            ..Default::default()
+        };
+        let outbox = Outbox::with_config(unreachable, &path, config.clone()).unwrap();
+        let first = enqueue(&outbox, channel_id, "first").await;
+        let second = enqueue(&outbox, channel_id, "second").await;
+        assert_eq!(outbox.pending(), 2);
+
+        let outbox = Outbox::with_config(client.clone(), &path, config).unwrap();
+        for nonce in [first, second] {
+            let status = outbox.wait_for(&nonce).await.unwrap();
+            assert!(matches!(
+                status,
+                DeliveryStatus::Delivered {
+                    message_id: Some(_)
+                }
+            ));
+        }
+
+        assert_eq!(contents(&client, channel_id).await, ["first", "second"]);
+        assert_eq!(fs::read(&path).unwrap(), b"[]");
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -7,7 +7,7 @@ impl Client {
         Ok(self
             .client
             .get(ep!(self, "/admin/stats"))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .json()
@@ -23,7 +23,7 @@ impl Client {
             .client
             .get(ep!(self, "/admin/messages"))
             .json(&data)
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .json()
@@ -20,7 +20,7 @@ impl Client {
         Ok(self
             .client
             .patch(ep!(self, "/safety/reports/{}", report.value_ref()))
-            .auth(&self.authentication)
+            .auth(self)
             .json(&data)
             .send()
             .await?
@@ -32,30 +32,17 @@ impl Client {
 
     /// Fetch a report by its ID.
     pub async fn fetch_report(&self, id: &Id<ReportMarker>) -> Result<Report> {
-        Ok(self
-            .client
-            .get(ep!(self, "/safety/report/{}", id.value_ref()))
-            .auth(&self.authentication)
-            .send()
-            .await?
-            .process_error()
-            .await?
-            .json()
-            .await?)
+        self.get_coalesced(
+            self.client
+                .get(ep!(self, "/safety/report/{}", id.value_ref())),
+        )
+        .await
     }
 
     /// Fetch all available reports
     pub async fn fetch_reports(&self) -> Result<Vec<Report>> {
-        Ok(self
-            .client
-            .get(ep!(self, "/safety/reports"))
-            .auth(&self.authentication)
-            .send()
-            .await?
-            .process_error()
-            .await?
-            .json()
-            .await?)
+        self.get_coalesced(self.client.get(ep!(self, "/safety/reports")))
+            .await
     }
 
     /// Report a piece of content to the moderation team.
@@ -63,7 +50,7 @@ impl Client {
         self.client
             .post(ep!(self, "/safety/report"))
             .json(&data)
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -73,16 +60,11 @@ impl Client {
 
     /// Fetch a snapshot for a given report.
     pub async fn fetch_snapshot(&self, report_id: &Id<ReportMarker>) -> Result<Snapshot> {
-        Ok(self
-            .client
-            .get(ep!(self, "/safety/snapshot/{}", report_id.value_ref()))
-            .auth(&self.authentication)
-            .send()
-            .await?
-            .process_error()
-            .await?
-            .json()
-            .await?)
+        self.get_coalesced(
+            self.client
+                .get(ep!(self, "/safety/snapshot/{}", report_id.value_ref())),
+        )
+        .await
     }
 
     /// Create a new account strike.
@@ -90,7 +72,7 @@ impl Client {
         Ok(self
             .client
             .post(ep!(self, "/safety/strikes"))
-            .auth(&self.authentication)
+            .auth(self)
             .json(&data)
             .send()
             .await?
@@ -103,7 +85,7 @@ impl Client {
         Ok(self
             .client
             .get(ep!(self, "/safety/strikes/{}", user_id.value_ref()))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .json()
@@ -118,11 +100,11 @@ impl Client {
     ) -> Result<()> {
         self.client
             .patch(ep!(self, "/safety/strikes/{}", strike_id.value_ref()))
-            .auth(&self.authentication)
+            .auth(self)
             .json(&data)
             .send()
             .await?
-            .json()
+            .json::<()>()
             .await?;
         Ok(())
     }
@@ -131,10 +113,10 @@ impl Client {
     pub async fn delete_strike(&self, strike_id: &Id<StrikeMarker>) -> Result<()> {
         self.client
             .delete(ep!(self, "/safety/strikes/{}", strike_id.value_ref()))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
-            .json()
+            .json::<()>()
             .await?;
         Ok(())
     }
@@ -0,0 +1,311 @@
+use std::{fmt::Debug, sync::Arc};
+
+use rive_models::{
+    error::ApiError,
+    id::{
+        marker::{ChannelMarker, MessageMarker, ServerMarker, UserMarker},
+        Id,
+    },
+    permission::Permission,
+};
+
+use crate::{prelude::*, Error};
+
+/// Source of the effective permissions of users, such as a cache.
+///
+/// Methods return [`None`] when the answer is unknown, in which case the
+/// request is sent unchecked.
+pub trait PermissionSource: Debug + Send + Sync {
+    /// Effective permissions of a user in a server.
+    fn in_server(
+        &self,
+        user_id: &Id<UserMarker>,
+        server_id: &Id<ServerMarker>,
+    ) -> Option<Permission>;
+
+    /// Effective permissions of a user in a channel.
+    fn in_channel(
+        &self,
+        user_id: &Id<UserMarker>,
+        channel_id: &Id<ChannelMarker>,
+    ) -> Option<Permission>;
+
+    /// Author of a message.
+    ///
+    /// Used to let users delete their own messages without
+    /// [`Permission::ManageMessages`].
+    fn message_author(&self, _message_id: &Id<MessageMarker>) -> Option<Id<UserMarker>> {
+        None
+    }
+}
+
+/// Permission checks made before sending requests.
+///
+/// Requests the authenticated user is known to lack permissions for fail
+/// without reaching the API, with the [`ApiError::MissingPermission`] error
+/// the API would have returned.
+///
+/// The following calls are checked:
+///
+/// - [`send_message`](Client::send_message): [`Permission::SendMessage`],
+///   and [`Permission::UploadFiles`], [`Permission::SendEmbeds`] or
+///   [`Permission::Masquerade`] depending on the message
+/// - [`delete_message`](Client::delete_message):
+///   [`Permission::ManageMessages`], for messages of other users
+/// - [`kick_member`](Client::kick_member): [`Permission::KickMembers`]
+/// - [`ban_user`](Client::ban_user): [`Permission::BanMembers`]
+/// - [`edit_role`](Client::edit_role): [`Permission::ManageRole`]
+#[derive(Debug, Clone)]
+pub struct Preflight {
+    user_id: Id<UserMarker>,
+    source: Arc<dyn PermissionSource>,
+}
+
+impl Preflight {
+    /// Check permissions of the given user, which should be the
+    /// authenticated one, against a source.
+    pub fn new(user_id: Id<UserMarker>, source: Arc<dyn PermissionSource>) -> Self {
+        Self { user_id, source }
+    }
+}
+
+impl Client {
+    /// Fail if the user is known to lack permissions in a server.
+    pub(crate) fn preflight_server(
+        &self,
+        server_id: &Id<ServerMarker>,
+        required: Permission,
+    ) -> Result<()> {
+        self.preflight(required, |preflight| {
+            preflight.source.in_server(&preflight.user_id, server_id)
+        })
+    }
+
+    /// Fail if the user is known to lack permissions in a channel.
+    pub(crate) fn preflight_channel(
+        &self,
+        channel_id: &Id<ChannelMarker>,
+        required: Permission,
+    ) -> Result<()> {
+        self.preflight(required, |preflight| {
+            preflight.source.in_channel(&preflight.user_id, channel_id)
+        })
+    }
+
+    /// Whether the message is known to be authored by someone else than the
+    /// user.
+    pub(crate) fn preflight_foreign_message(&self, message_id: &Id<MessageMarker>) -> bool {
+        self.preflight.as_ref().map_or(false, |preflight| {
+            preflight
+                .source
+                .message_author(message_id)
+                .map_or(false, |author| author != preflight.user_id)
+        })
+    }
+
+    fn preflight(
+        &self,
+        required: Permission,
+        permissions: impl FnOnce(&Preflight) -> Option<Permission>,
+    ) -> Result<()> {
+        let Some(permissions) = self.preflight.as_ref().and_then(permissions) else {
+            return Ok(());
+        };
+
+        match required.difference(permissions).iter().next() {
+            Some(permission) => Err(Error::Api(ApiError::MissingPermission { permission })),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(feature = "cache-inmemory")]
+impl PermissionSource for rive_cache_inmemory::InMemoryCache {
+    fn in_server(
+        &self,
+        user_id: &Id<UserMarker>,
+        server_id: &Id<ServerMarker>,
+    ) -> Option<Permission> {
+        self.permissions().in_server(user_id, server_id)
+    }
+
+    fn in_channel(
+        &self,
+        user_id: &Id<UserMarker>,
+        channel_id: &Id<ChannelMarker>,
+    ) -> Option<Permission> {
+        self.permissions().in_channel(user_id, channel_id)
+    }
+
+    fn message_author(&self, message_id: &Id<MessageMarker>) -> Option<Id<UserMarker>> {
+        self.message(message_id)
+            .map(|message| message.author.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use rive_models::{
+        data::{CreateServerData, EditMemberData, SendMessageData},
+        error::ApiError,
+        id::{
+            marker::{ChannelMarker, ServerMarker, UserMarker},
+            Id,
+        },
+        member::FieldsMember,
+        message::MasqueradeBorrowed,
+        permission::Permission,
+        server::Server,
+    };
+
+    use super::{PermissionSource, Preflight};
+    use crate::{test_util::FakeServer, Client, Config, Error};
+
+    /// Grants a fixed set of permissions everywhere.
+    #[derive(Debug)]
+    struct Fixed(Permission);
+
+    impl PermissionSource for Fixed {
+        fn in_server(&self, _: &Id<UserMarker>, _: &Id<ServerMarker>) -> Option<Permission> {
+            Some(self.0)
+        }
+
+        fn in_channel(&self, _: &Id<UserMarker>, _: &Id<ChannelMarker>) -> Option<Permission> {
+            Some(self.0)
+        }
+    }
+
+    /// Start a server with a server in it, and a client that assumes it has
+    /// the given permissions there.
+    async fn setup(permissions: Permission) -> (FakeServer, Client, Server) {
+        let server = FakeServer::start().await.unwrap();
+        let account = server.add_bot("preflight");
+        let created = server
+            .client(account.authentication.clone())
+            .create_server(&CreateServerData {
+                name: "Preflight".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let client = Client::with_config(Config {
+            base_url: server.url(),
+            preflight: Some(Preflight::new(
+                account.id.clone(),
+                Arc::new(Fixed(permissions)),
+            )),
+            ..Config::new(account.authentication)
+        });
+
+        (server, client, created)
+    }
+
+    fn missing(error: &Error) -> Option<Permission> {
+        match error {
+            Error::Api(ApiError::MissingPermission { permission }) => Some(*permission),
+            _ => None,
+        }
+    }
+
+    #[tokio::test]
+    async fn fails_locally() {
+        let (server, client, created) =
+            setup(Permission::ViewChannel | Permission::SendMessage).await;
+
+        let data = SendMessageData {
+            content: Some("hello"),
+            ..Default::default()
+        };
+        client
+            .send_message(&created.channels[0], &data)
+            .await
+            .unwrap();
+
+        let requests = server.request_count();
+        let error = client
+            .kick_member(
+                &created.id,
+                &Id::new("01H9F3XJ2C2K6G8B4Y7Q9M0S1T".to_string()),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(missing(&error), Some(Permission::KickMembers));
+        assert_eq!(server.request_count(), requests);
+    }
+
+    #[tokio::test]
+    async fn send_message_denied() {
+        let (server, client, created) = setup(Permission::ViewChannel).await;
+        let requests = server.request_count();
+
+        let data = SendMessageData {
+            content: Some("hello"),
+            ..Default::default()
+        };
+        let error = client
+            .send_message(&created.channels[0], &data)
+            .await
+            .unwrap_err();
+        assert_eq!(missing(&error), Some(Permission::SendMessage));
+        assert_eq!(server.request_count(), requests);
+
+        let (server, client, created) =
+            setup(Permission::ViewChannel | Permission::SendMessage).await;
+        let requests = server.request_count();
+        let masquerade = MasqueradeBorrowed {
+            name: Some("Masked"),
+            avatar: None,
+            colour: None,
+        };
+        let data = SendMessageData {
+            content: Some("hello"),
+            masquerade: Some(&masquerade),
+            ..Default::default()
+        };
+        let error = client
+            .send_message(&created.channels[0], &data)
+            .await
+            .unwrap_err();
+        assert_eq!(missing(&error), Some(Permission::Masquerade));
+        assert_eq!(server.request_count(), requests);
+    }
+
+    #[tokio::test]
+    async fn assign_roles_denied() {
+        let (server, client, created) =
+            setup(Permission::ViewChannel | Permission::TimeoutMembers).await;
+        let requests = server.request_count();
+
+        let data = EditMemberData {
+            roles: Some(vec![Id::new("01H9F3XJ2C2K6G8B4Y7Q9M0S1V".to_string())]),
+            ..Default::default()
+        };
+        let error = client
+            .edit_member(
+                &created.id,
+                &Id::new("01H9F3XJ2C2K6G8B4Y7Q9M0S1T".to_string()),
+                &data,
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(missing(&error), Some(Permission::AssignRoles));
+
+        let data = EditMemberData {
+            remove: Some(vec![FieldsMember::Roles]),
+            ..Default::default()
+        };
+        let error = client
+            .edit_member(
+                &created.id,
+                &Id::new("01H9F3XJ2C2K6G8B4Y7Q9M0S1T".to_string()),
+                &data,
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(missing(&error), Some(Permission::AssignRoles));
+        assert_eq!(server.request_count(), requests);
+    }
+}
@@ -9,11 +9,13 @@ use rive_models::{
 impl Client {
     /// Create a new server.
     pub async fn create_server(&self, data: &CreateServerData) -> Result<Server> {
+        self.validate(data)?;
+
         Ok(self
             .client
             .post(ep!(self, "/servers/create"))
             .json(&data)
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -24,28 +26,20 @@ impl Client {
 
     /// Deletes a server if owner otherwise leaves.
     pub async fn fetch_server(&self, id: &Id<ServerMarker>) -> Result<Server> {
-        Ok(self
-            .client
-            .get(ep!(self, "/servers/{}", id.value_ref()))
-            .auth(&self.authentication)
-            .send()
-            .await?
-            .process_error()
-            .await?
-            .json()
-            .await?)
+        self.get_coalesced(self.client.get(ep!(self, "/servers/{}", id.value_ref())))
+            .await
     }
 
     /// Deletes a server if owner otherwise leaves.
     pub async fn delete_or_leave_server(&self, id: &Id<ServerMarker>) -> Result<()> {
         self.client
             .delete(ep!(self, "/servers/{}", id.value_ref()))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
             .await?
-            .json()
+            .json::<()>()
             .await?;
         Ok(())
     }
@@ -56,11 +50,13 @@ impl Client {
         id: &Id<ServerMarker>,
         data: &EditServerData,
     ) -> Result<Server> {
+        self.validate(data)?;
+
         Ok(self
             .client
             .patch(ep!(self, "/servers/{}", id.value_ref()))
             .json(&data)
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -73,12 +69,12 @@ impl Client {
     pub async fn mark_server_as_read(&self, id: &Id<ServerMarker>) -> Result<()> {
         self.client
             .put(ep!(self, "/servers/{}/ack", id.value_ref()))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
             .await?
-            .json()
+            .json::<()>()
             .await?;
         Ok(())
     }
@@ -89,11 +85,13 @@ impl Client {
         server_id: &Id<ServerMarker>,
         data: &CreateChannelData,
     ) -> Result<Channel> {
+        self.validate(data)?;
+
         Ok(self
             .client
             .post(ep!(self, "/servers/{}/channels", server_id.value_ref()))
             .json(&data)
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -6,7 +6,8 @@ use rive_models::{
         marker::{ServerMarker, UserMarker},
         Id,
     },
-    member::{Member, MemberList},
+    member::{FieldsMember, Member, MemberList},
+    permission::Permission,
     server::{BanList, ServerBan},
 };
 
@@ -17,17 +18,12 @@ impl Client {
         server_id: &Id<ServerMarker>,
         data: &FetchMembersData,
     ) -> Result<MemberList> {
-        Ok(self
-            .client
-            .get(ep!(self, "/servers/{}/members", server_id.value_ref()))
-            .query(&data)
-            .auth(&self.authentication)
-            .send()
-            .await?
-            .process_error()
-            .await?
-            .json()
-            .await?)
+        self.get_coalesced(
+            self.client
+                .get(ep!(self, "/servers/{}/members", server_id.value_ref()))
+                .query(&data),
+        )
+        .await
     }
 
     /// Retrieve a member.
@@ -36,21 +32,13 @@ impl Client {
         server_id: &Id<ServerMarker>,
         member_id: &Id<UserMarker>,
     ) -> Result<Member> {
-        Ok(self
-            .client
-            .get(ep!(
-                self,
-                "/servers/{}/members/{}",
-                server_id.value_ref(),
-                member_id.value_ref()
-            ))
-            .auth(&self.authentication)
-            .send()
-            .await?
-            .process_error()
-            .await?
-            .json()
-            .await?)
+        self.get_coalesced(self.client.get(ep!(
+            self,
+            "/servers/{}/members/{}",
+            server_id.value_ref(),
+            member_id.value_ref()
+        )))
+        .await
     }
 
     /// Remove a member from the server.
@@ -59,6 +47,8 @@ impl Client {
         server_id: &Id<ServerMarker>,
         member_id: &Id<UserMarker>,
     ) -> Result<()> {
+        self.preflight_server(server_id, Permission::KickMembers)?;
+
         self.client
             .delete(ep!(
                 self,
@@ -66,7 +56,7 @@ impl Client {
                 server_id.value_ref(),
                 member_id.value_ref()
             ))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -81,6 +71,22 @@ impl Client {
         member_id: &Id<UserMarker>,
         data: &EditMemberData,
     ) -> Result<Member> {
+        self.validate(data)?;
+
+        let removes = |field| {
+            data.remove
+                .as_ref()
+                .map_or(false, |remove| remove.contains(&field))
+        };
+        let mut required = Permission::empty();
+        if data.roles.is_some() || removes(FieldsMember::Roles) {
+            required |= Permission::AssignRoles;
+        }
+        if data.timeout.is_some() || removes(FieldsMember::Timeout) {
+            required |= Permission::TimeoutMembers;
+        }
+        self.preflight_server(server_id, required)?;
+
         Ok(self
             .client
             .patch(ep!(
@@ -90,7 +96,7 @@ impl Client {
                 member_id.value_ref()
             ))
             .json(&data)
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -106,6 +112,8 @@ impl Client {
         user_id: &Id<UserMarker>,
         data: &BanUserData,
     ) -> Result<ServerBan> {
+        self.preflight_server(server_id, Permission::BanMembers)?;
+
         Ok(self
             .client
             .put(ep!(
@@ -115,7 +123,7 @@ impl Client {
                 user_id.value_ref()
             ))
             .json(&data)
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -137,41 +145,31 @@ impl Client {
                 server_id.value_ref(),
                 user_id.value_ref()
             ))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
             .await?
-            .json()
+            .json::<()>()
             .await?;
         Ok(())
     }
 
     /// Fetch all bans on a server.
     pub async fn fetch_bans(&self, server_id: &Id<ServerMarker>) -> Result<BanList> {
-        Ok(self
-            .client
-            .get(ep!(self, "/servers/{}/bans", server_id.value_ref()))
-            .auth(&self.authentication)
-            .send()
-            .await?
-            .process_error()
-            .await?
-            .json()
-            .await?)
+        self.get_coalesced(
+            self.client
+                .get(ep!(self, "/servers/{}/bans", server_id.value_ref())),
+        )
+        .await
     }
 
     /// Fetch all server invites.
     pub async fn fetch_invites(&self, server_id: &Id<ServerMarker>) -> Result<PartialInvite> {
-        Ok(self
-            .client
-            .get(ep!(self, "/servers/{}/invites", server_id.value_ref()))
-            .auth(&self.authentication)
-            .send()
-            .await?
-            .process_error()
-            .await?
-            .json()
-            .await?)
+        self.get_coalesced(
+            self.client
+                .get(ep!(self, "/servers/{}/invites", server_id.value_ref())),
+        )
+        .await
     }
 }
@@ -7,6 +7,7 @@ use rive_models::{
         marker::{RoleMarker, ServerMarker},
         Id,
     },
+    permission::Permission,
     server::{NewRole, Role, Server},
 };
 
@@ -17,11 +18,13 @@ impl Client {
         server_id: &Id<ServerMarker>,
         data: &CreateRoleData,
     ) -> Result<NewRole> {
+        self.validate(data)?;
+
         Ok(self
             .client
             .post(ep!(self, "/servers/{}/roles", server_id.value_ref()))
             .json(&data)
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -43,7 +46,7 @@ impl Client {
                 server_id.value_ref(),
                 role_id.value_ref()
             ))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -58,6 +61,9 @@ impl Client {
         role_id: &Id<RoleMarker>,
         data: &EditRoleData,
     ) -> Result<Role> {
+        self.validate(data)?;
+        self.preflight_server(server_id, Permission::ManageRole)?;
+
         Ok(self
             .client
             .patch(ep!(
@@ -67,7 +73,7 @@ impl Client {
                 role_id.value_ref()
             ))
             .json(&data)
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -92,7 +98,7 @@ impl Client {
                 role_id.value_ref()
             ))
             .json(&data)
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -115,7 +121,7 @@ impl Client {
                 server_id.value_ref(),
             ))
             .json(&data)
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
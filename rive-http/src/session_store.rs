@@ -0,0 +1,207 @@
+use std::{
+    fmt::Debug,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use rive_models::{
+    event::AuthifierEvent,
+    id::{
+        marker::{SessionMarker, UserMarker},
+        Id,
+    },
+    session::Session,
+};
+use serde::{Deserialize, Serialize};
+
+/// Session persisted by a [`SessionStore`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct StoredSession {
+    /// Session ID
+    pub id: Id<SessionMarker>,
+    /// ID of the user the session belongs to
+    pub user_id: Id<UserMarker>,
+    /// Session token
+    pub token: String,
+}
+
+impl StoredSession {
+    /// Whether the event revokes this session.
+    pub fn is_revoked_by(&self, event: &AuthifierEvent) -> bool {
+        match event {
+            AuthifierEvent::DeleteSession {
+                user_id,
+                session_id,
+            } => *user_id == self.user_id && *session_id == self.id,
+            AuthifierEvent::DeleteAllSessions {
+                user_id,
+                exclude_session_id,
+            } => *user_id == self.user_id && exclude_session_id.as_ref() != Some(&self.id),
+        }
+    }
+}
+
+impl From<Session> for StoredSession {
+    fn from(session: Session) -> Self {
+        Self {
+            id: session.id,
+            user_id: session.user_id,
+            token: session.token,
+        }
+    }
+}
+
+/// Storage of the session of a user account.
+///
+/// A [`Client`] configured with a store authenticates with the stored
+/// session, saves the session of a successful login and clears it on logout
+/// or when the session is revoked.
+///
+/// [`Client`]: crate::Client
+pub trait SessionStore: Debug + Send + Sync {
+    /// Load the stored session, if any.
+    fn load(&self) -> io::Result<Option<StoredSession>>;
+
+    /// Replace the stored session.
+    fn save(&self, session: &StoredSession) -> io::Result<()>;
+
+    /// Remove the stored session.
+    fn clear(&self) -> io::Result<()>;
+}
+
+/// Session store keeping the session in memory.
+#[derive(Debug, Default)]
+pub struct InMemorySessionStore(Mutex<Option<StoredSession>>);
+
+impl InMemorySessionStore {
+    /// Create a new empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SessionStore for InMemorySessionStore {
+    fn load(&self) -> io::Result<Option<StoredSession>> {
+        Ok(self.0.lock().expect("session store poisoned").clone())
+    }
+
+    fn save(&self, session: &StoredSession) -> io::Result<()> {
+        *self.0.lock().expect("session store poisoned") = Some(session.clone());
+        Ok(())
+    }
+
+    fn clear(&self) -> io::Result<()> {
+        *self.0.lock().expect("session store poisoned") = None;
+        Ok(())
+    }
+}
+
+/// Session store keeping the session in a JSON file.
+///
+/// The file is replaced atomically on save and removed on clear. On unix, it
+/// is only readable and writable by its owner.
+#[derive(Debug, Clone)]
+pub struct FileSessionStore {
+    path: PathBuf,
+}
+
+impl FileSessionStore {
+    /// Create a store backed by the file at the given path.
+    ///
+    /// The file does not need to exist.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Path of the backing file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl SessionStore for FileSessionStore {
+    fn load(&self) -> io::Result<Option<StoredSession>> {
+        match fs::read(&self.path) {
+            Ok(contents) => serde_json::from_slice(&contents)
+                .map(Some)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    fn save(&self, session: &StoredSession) -> io::Result<()> {
+        let contents = serde_json::to_vec(session)?;
+        let mut temporary = self.path.clone().into_os_string();
+        temporary.push(".tmp");
+
+        // a leftover file would keep its permissions, so always create a new one
+        match fs::remove_file(&temporary) {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => return Err(error),
+            _ => {}
+        }
+
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let mut file = options.open(&temporary)?;
+        file.write_all(&contents)?;
+        file.sync_all()?;
+        fs::rename(&temporary, &self.path)
+    }
+
+    fn clear(&self) -> io::Result<()> {
+        match fs::remove_file(&self.path) {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use rive_models::{event::AuthifierEvent, id::Id};
+
+    use super::{FileSessionStore, SessionStore, StoredSession};
+
+    #[test]
+    fn file_store() {
+        let path = env::temp_dir().join(format!("rive-http-session-{}.json", std::process::id()));
+        let store = FileSessionStore::new(&path);
+        let session = StoredSession {
+            id: Id::new("01H9F3XJ2C2K6G8B4Y7Q9M0S1T".to_string()),
+            user_id: Id::new("01H9F3XJ2C2K6G8B4Y7Q9M0S1U".to_string()),
+            token: "token".to_string(),
+        };
+
+        assert_eq!(store.load().unwrap(), None);
+        store.save(&session).unwrap();
+        assert_eq!(store.load().unwrap(), Some(session.clone()));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        store.save(&session).unwrap();
+        store.clear().unwrap();
+        assert_eq!(store.load().unwrap(), None);
+        store.clear().unwrap();
+
+        assert!(session.is_revoked_by(&AuthifierEvent::DeleteAllSessions {
+            user_id: session.user_id.clone(),
+            exclude_session_id: None,
+        }));
+        assert!(!session.is_revoked_by(&AuthifierEvent::DeleteAllSessions {
+            user_id: session.user_id.clone(),
+            exclude_session_id: Some(session.id.clone()),
+        }));
+    }
+}
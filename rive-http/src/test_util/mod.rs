@@ -0,0 +1,634 @@
+//! In-process fake of the Revolt REST API for testing code built on [`Client`].
+//!
+//! [`FakeServer`] emulates a subset of the API: logins, users, servers,
+//! channels, messages, members, roles and invites. State is kept in memory,
+//! errors are returned with the same bodies as the API and every response
+//! carries the ratelimit headers of the official instance.
+//!
+//! Permissions are checked on a server-wide basis only: channel overrides are
+//! not taken into account.
+//!
+//! Routes such as the instance configuration and invite previews may be used
+//! without authentication, like on the API.
+//!
+//! ```
+//! # #[tokio::main]
+//! # async fn main() -> std::io::Result<()> {
+//! use rive_http::test_util::FakeServer;
+//! use rive_models::data::CreateServerData;
+//!
+//! let server = FakeServer::start().await?;
+//! let account = server.add_bot("ferris");
+//! let client = server.client(account.authentication.clone());
+//!
+//! let created = client
+//!     .create_server(&CreateServerData {
+//!         name: "Rustaceans".to_string(),
+//!         ..Default::default()
+//!     })
+//!     .await
+//!     .unwrap();
+//! assert_eq!(created.owner, account.id);
+//! # Ok(())
+//! # }
+//! ```
+
+mod ratelimit;
+mod routes;
+mod state;
+
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    io,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use http::{header::CONTENT_TYPE, HeaderValue, Method, StatusCode};
+use http_body_util::{BodyExt as _, Full};
+use hyper::{
+    body::{Bytes, Incoming},
+    server::conn::http1,
+    service::service_fn,
+};
+use hyper_util::rt::TokioIo;
+use rive_models::{
+    authentication::Authentication,
+    id::{marker::UserMarker, Id},
+};
+use serde_json::{json, Value};
+use tokio::{net::TcpListener, task::JoinHandle};
+
+use crate::Client;
+
+use self::{
+    routes::{Rejection, Request},
+    state::{Login, State},
+};
+
+/// Account registered on a [`FakeServer`].
+#[derive(Debug, Clone)]
+pub struct FakeAccount {
+    /// ID of the account user.
+    pub id: Id<UserMarker>,
+    /// Authentication accepted by the server for this account.
+    pub authentication: Authentication,
+}
+
+/// Local HTTP server emulating the Revolt REST API.
+///
+/// The server runs on a random port of the loopback interface until it is
+/// dropped.
+#[derive(Debug)]
+pub struct FakeServer {
+    address: SocketAddr,
+    state: Arc<Mutex<State>>,
+    task: JoinHandle<()>,
+}
+
+impl FakeServer {
+    /// Start a server with no accounts.
+    pub async fn start() -> io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+        let address = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(State::default()));
+
+        let task = tokio::spawn({
+            let state = Arc::clone(&state);
+
+            async move {
+                let url: Arc<str> = format!("http://{address}").into();
+
+                while let Ok((stream, _)) = listener.accept().await {
+                    let state = Arc::clone(&state);
+                    let url = Arc::clone(&url);
+                    let service = service_fn(move |request| {
+                        let state = Arc::clone(&state);
+                        let url = Arc::clone(&url);
+                        async move { Ok::<_, Infallible>(handle(&state, &url, request).await) }
+                    });
+
+                    tokio::spawn(
+                        http1::Builder::new().serve_connection(TokioIo::new(stream), service),
+                    );
+                }
+            }
+        });
+
+        Ok(Self {
+            address,
+            state,
+            task,
+        })
+    }
+
+    /// Base URL of the server.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    /// Create a client pointing at this server.
+    pub fn client(&self, authentication: Authentication) -> Client {
+        Client::new_base_url(authentication, self.url())
+    }
+
+    /// Register a user account, authenticated with a session token.
+    pub fn add_user(&self, username: impl Into<String>) -> FakeAccount {
+        let (id, token) = self.lock().add_user(username.into(), false);
+
+        FakeAccount {
+            id: Id::new(id),
+            authentication: Authentication::SessionToken(token),
+        }
+    }
+
+    /// Register a bot account, authenticated with a bot token.
+    pub fn add_bot(&self, username: impl Into<String>) -> FakeAccount {
+        let (id, token) = self.lock().add_user(username.into(), true);
+
+        FakeAccount {
+            id: Id::new(id),
+            authentication: Authentication::BotToken(token),
+        }
+    }
+
+    /// Register a user account that logs in with an email and a password.
+    ///
+    /// The returned authentication is that of an initial session; logging in
+    /// creates new ones.
+    pub fn add_login(
+        &self,
+        username: impl Into<String>,
+        email: impl Into<String>,
+        password: impl Into<String>,
+    ) -> FakeAccount {
+        let mut state = self.lock();
+        let (id, token) = state.add_user(username.into(), false);
+        state.logins.insert(
+            email.into(),
+            Login {
+                user_id: id.clone(),
+                password: password.into(),
+                recovery_code: None,
+            },
+        );
+
+        FakeAccount {
+            id: Id::new(id),
+            authentication: Authentication::SessionToken(token),
+        }
+    }
+
+    /// Require the account registered with the given email to complete
+    /// multi-factor authentication with a recovery code when logging in.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no account was registered with the email by
+    /// [`add_login`](Self::add_login).
+    pub fn enable_mfa(&self, email: &str, recovery_code: impl Into<String>) {
+        self.lock()
+            .logins
+            .get_mut(email)
+            .expect("no account registered with this email")
+            .recovery_code = Some(recovery_code.into());
+    }
+
+    /// Set whether requests exceeding a ratelimit are rejected.
+    ///
+    /// Ratelimit headers are returned either way. Enabled by default.
+    pub fn set_ratelimits_enforced(&self, enforced: bool) {
+        self.lock().ratelimits.enforced = enforced;
+    }
+
+    /// Set whether the file server is advertised as enabled in the instance
+    /// configuration.
+    ///
+    /// Enabled by default.
+    pub fn set_autumn_enabled(&self, enabled: bool) {
+        self.lock().autumn_disabled = !enabled;
+    }
+
+    /// Delay the responses to the next `count` requests by `delay`.
+    ///
+    /// The requests are handled before the delay, which emulates a response
+    /// lost to a timeout after the API applied the request.
+    pub fn stall_responses(&self, count: usize, delay: Duration) {
+        let mut state = self.lock();
+        state.stalled_responses = count;
+        state.stall = delay;
+    }
+
+    /// Amount of requests received so far.
+    pub fn request_count(&self) -> usize {
+        self.lock().requests
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().expect("fake server state poisoned")
+    }
+}
+
+impl Drop for FakeServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+type Response = http::Response<Full<Bytes>>;
+
+fn respond(status: StatusCode, body: &Value) -> Response {
+    let mut response = if body.is_null() {
+        http::Response::new(Full::default())
+    } else {
+        let mut response = http::Response::new(Full::from(body.to_string()));
+        response
+            .headers_mut()
+            .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        response
+    };
+    *response.status_mut() = status;
+
+    response
+}
+
+fn reject(rejection: &Rejection) -> Response {
+    respond(rejection.status, &rejection.body)
+}
+
+async fn handle(state: &Mutex<State>, url: &str, request: http::Request<Incoming>) -> Response {
+    let (parts, body) = request.into_parts();
+    let Ok(body) = body.collect().await.map(|body| body.to_bytes()) else {
+        return respond(
+            StatusCode::BAD_REQUEST,
+            &json!({ "type": "FailedValidation" }),
+        );
+    };
+
+    let body = if body.is_empty() {
+        Value::Null
+    } else {
+        match serde_json::from_slice(&body) {
+            Ok(body) => body,
+            Err(_) => {
+                return respond(
+                    StatusCode::BAD_REQUEST,
+                    &json!({ "type": "FailedValidation" }),
+                )
+            }
+        }
+    };
+
+    let segments = parts
+        .uri
+        .path()
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+    let query = parts
+        .uri
+        .query()
+        .into_iter()
+        .flat_map(|query| query.split('&'))
+        .filter_map(|pair| pair.split_once('='))
+        .collect::<HashMap<_, _>>();
+
+    let (response, stall) = {
+        let mut state = state.lock().expect("fake server state poisoned");
+        state.requests += 1;
+
+        let token = ["x-session-token", "x-bot-token"]
+            .iter()
+            .find_map(|header| parts.headers.get(*header)?.to_str().ok());
+        let user = token.and_then(|token| state.tokens.get(token)).cloned();
+
+        // anonymous requests share a bucket, as the API counts them per address
+        let ratelimit = state.ratelimits.hit(
+            user.as_deref().unwrap_or_default(),
+            &parts.method,
+            &segments,
+        );
+        let mut response = if ratelimit.exceeded {
+            respond(
+                StatusCode::TOO_MANY_REQUESTS,
+                &json!({ "retry_after": ratelimit.reset_after.as_millis() }),
+            )
+        } else {
+            let outcome =
+                match routes::public_route(&mut state, &parts.method, &segments, &body, url) {
+                    Some(outcome) => outcome,
+                    None => match &user {
+                        Some(user) => {
+                            let request = Request {
+                                user,
+                                token: token.unwrap_or_default(),
+                                method: &parts.method,
+                                segments: &segments,
+                                query,
+                                idempotency_key: parts
+                                    .headers
+                                    .get("idempotency-key")
+                                    .and_then(|key| key.to_str().ok()),
+                                body,
+                            };
+
+                            routes::route(&mut state, &request)
+                        }
+                        None => Err(Rejection::unauthenticated()),
+                    },
+                };
+
+            match outcome {
+                Ok(body) if body.is_null() && parts.method != Method::GET => {
+                    respond(StatusCode::NO_CONTENT, &body)
+                }
+                Ok(body) => respond(StatusCode::OK, &body),
+                Err(rejection) => reject(&rejection),
+            }
+        };
+
+        let headers = response.headers_mut();
+        headers.insert(
+            "x-ratelimit-bucket",
+            HeaderValue::from_static(ratelimit.bucket),
+        );
+        headers.insert("x-ratelimit-limit", ratelimit.limit.into());
+        headers.insert("x-ratelimit-remaining", ratelimit.remaining.into());
+        headers.insert(
+            "x-ratelimit-reset-after",
+            (ratelimit.reset_after.as_millis() as u64).into(),
+        );
+
+        let stall = (state.stalled_responses > 0).then(|| {
+            state.stalled_responses -= 1;
+            state.stall
+        });
+
+        (response, stall)
+    };
+
+    if let Some(stall) = stall {
+        tokio::time::sleep(stall).await;
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use rive_models::{
+        channel::PartialInvite,
+        data::{
+            CreateRoleData, CreateServerData, EditMemberData, EditMessageData, EditRoleData,
+            FetchMessagesData, SendMessageData, SetServerRolePermissionData,
+        },
+        error::ApiError,
+        invite::Invite,
+        message::BulkMessageResponse,
+        permission::Permission,
+    };
+    use serde_json::json;
+
+    use super::FakeServer;
+    use crate::Error;
+
+    #[tokio::test]
+    async fn end_to_end() {
+        let server = FakeServer::start().await.unwrap();
+        let owner = server.add_bot("owner");
+        let guest = server.add_user("guest");
+        let owner_client = server.client(owner.authentication.clone());
+        let guest_client = server.client(guest.authentication.clone());
+
+        let created = owner_client
+            .create_server(&CreateServerData {
+                name: "Test".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let channel_id = created.channels[0].clone();
+
+        let message = owner_client
+            .send_message(
+                &channel_id,
+                &SendMessageData {
+                    content: Some("hello"),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(message.author, owner.id);
+        assert_eq!(message.content.as_deref(), Some("hello"));
+        assert!(message.nonce.is_some());
+
+        let error = owner_client
+            .send_message_with_nonce(
+                &channel_id,
+                &SendMessageData {
+                    content: Some("hello again"),
+                    ..Default::default()
+                },
+                message.nonce.as_deref().unwrap(),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(error, Error::Api(ApiError::DuplicateNonce)));
+
+        let error = guest_client.fetch_channel(&channel_id).await.unwrap_err();
+        assert!(matches!(error, Error::Api(ApiError::UnknownChannel)));
+
+        let invite = owner_client
+            .create_invite(&channel_id.clone().cast())
+            .await
+            .unwrap();
+        let rive_models::channel::PartialInvite::Server { code, .. } = invite else {
+            panic!("expected a server invite");
+        };
+        guest_client.join_invite(&code).await.unwrap();
+
+        let error = guest_client
+            .edit_message(
+                &channel_id,
+                &message.id,
+                &EditMessageData {
+                    content: Some("hijacked".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(error, Error::Api(ApiError::CannotEditMessage)));
+
+        let error = guest_client
+            .kick_member(&created.id, &owner.id)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            Error::Api(ApiError::MissingPermission { permission }) if permission == Permission::KickMembers
+        ));
+
+        let messages = guest_client
+            .fetch_messages(&channel_id, &FetchMessagesData::default())
+            .await
+            .unwrap();
+        let BulkMessageResponse::JustMessages(messages) = messages else {
+            panic!("expected messages only");
+        };
+        assert_eq!(messages.len(), 1);
+
+        let error = server
+            .client(rive_models::authentication::Authentication::SessionToken(
+                "invalid".to_string(),
+            ))
+            .fetch_self()
+            .await
+            .unwrap_err();
+        assert!(matches!(error, Error::Api(ApiError::Unauthenticated)));
+    }
+
+    #[tokio::test]
+    async fn anonymous_routes() {
+        let server = FakeServer::start().await.unwrap();
+        let owner = server.add_user("owner");
+        let owner_client = server.client(owner.authentication.clone());
+        let anonymous = owner_client.without_authentication();
+
+        let configuration = anonymous.query_node().await.unwrap();
+        assert!(configuration.features.autumn.enabled);
+        server.set_autumn_enabled(false);
+        let configuration = anonymous.query_node().await.unwrap();
+        assert!(!configuration.features.autumn.enabled);
+
+        let created = owner_client
+            .create_server(&CreateServerData {
+                name: "Public".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let invite = owner_client
+            .create_invite(&created.channels[0].clone().cast())
+            .await
+            .unwrap();
+        let PartialInvite::Server { code, .. } = invite else {
+            panic!("expected a server invite");
+        };
+
+        let invite = anonymous.fetch_invite(&code).await.unwrap();
+        assert!(matches!(invite, Invite::Server { server_name, .. } if server_name == "Public"));
+
+        let error = anonymous.join_invite(&code).await.unwrap_err();
+        assert!(matches!(error, Error::Api(ApiError::Unauthenticated)));
+    }
+
+    #[tokio::test]
+    async fn roles_apply_in_rank_order() {
+        let server = FakeServer::start().await.unwrap();
+        server.set_ratelimits_enforced(false);
+        let owner = server.add_user("owner");
+        let moderator = server.add_user("moderator");
+        let guest = server.add_user("guest");
+        let owner_client = server.client(owner.authentication.clone());
+        let moderator_client = server.client(moderator.authentication.clone());
+
+        let created = owner_client
+            .create_server(&CreateServerData {
+                name: "Ranks".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let invite = owner_client
+            .create_invite(&created.channels[0].clone().cast())
+            .await
+            .unwrap();
+        let PartialInvite::Server { code, .. } = invite else {
+            panic!("expected a server invite");
+        };
+        for account in [&moderator, &guest] {
+            server
+                .client(account.authentication.clone())
+                .join_invite(&code)
+                .await
+                .unwrap();
+        }
+
+        let mut roles = Vec::new();
+        for (name, rank, permissions) in [
+            (
+                "allow",
+                1,
+                json!({ "allow": Permission::KickMembers, "deny": 0 }),
+            ),
+            (
+                "deny",
+                0,
+                json!({ "allow": 0, "deny": Permission::KickMembers }),
+            ),
+        ] {
+            let role = owner_client
+                .create_role(
+                    &created.id,
+                    &CreateRoleData {
+                        name: name.to_string(),
+                        rank: Some(rank),
+                    },
+                )
+                .await
+                .unwrap();
+            owner_client
+                .set_role_permission(
+                    &created.id,
+                    &role.id,
+                    &SetServerRolePermissionData {
+                        permissions: serde_json::from_value(permissions).unwrap(),
+                    },
+                )
+                .await
+                .unwrap();
+            roles.push(role.id);
+        }
+        owner_client
+            .edit_member(
+                &created.id,
+                &moderator.id,
+                &EditMemberData {
+                    roles: Some(roles.clone()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        // the role with the smallest rank takes priority
+        let error = moderator_client
+            .kick_member(&created.id, &guest.id)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            Error::Api(ApiError::MissingPermission { permission }) if permission == Permission::KickMembers
+        ));
+
+        owner_client
+            .edit_role(
+                &created.id,
+                &roles[1],
+                &EditRoleData {
+                    rank: Some(2),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        moderator_client
+            .kick_member(&created.id, &guest.id)
+            .await
+            .unwrap();
+    }
+}
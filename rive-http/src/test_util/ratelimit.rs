@@ -0,0 +1,92 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use http::Method;
+
+/// Length of a ratelimit window.
+const WINDOW: Duration = Duration::from_secs(10);
+
+/// State of a bucket for a single user.
+#[derive(Debug)]
+struct Window {
+    remaining: u64,
+    reset_at: Instant,
+}
+
+/// Outcome of counting a request against its bucket.
+#[derive(Debug)]
+pub(super) struct Ratelimit {
+    /// Name of the bucket.
+    pub bucket: &'static str,
+    /// Maximum amount of requests in the bucket.
+    pub limit: u64,
+    /// Amount of requests left in the bucket.
+    pub remaining: u64,
+    /// Time until the bucket resets.
+    pub reset_after: Duration,
+    /// Whether the request exceeded the limit.
+    pub exceeded: bool,
+}
+
+/// Per-user ratelimit buckets, following the limits of the official instance.
+#[derive(Debug)]
+pub(super) struct Ratelimits {
+    /// Whether exceeding a limit rejects the request.
+    pub enforced: bool,
+    windows: HashMap<(String, String), Window>,
+}
+
+impl Default for Ratelimits {
+    fn default() -> Self {
+        Self {
+            enforced: true,
+            windows: HashMap::new(),
+        }
+    }
+}
+
+impl Ratelimits {
+    /// Count a request of the user against the bucket of the route.
+    pub fn hit(&mut self, user: &str, method: &Method, segments: &[&str]) -> Ratelimit {
+        let (bucket, limit, resource) = bucket(method, segments);
+        let key = (user.to_string(), format!("{bucket}:{resource}"));
+        let now = Instant::now();
+
+        let window = self.windows.entry(key).or_insert(Window {
+            remaining: limit,
+            reset_at: now + WINDOW,
+        });
+        if now >= window.reset_at {
+            window.remaining = limit;
+            window.reset_at = now + WINDOW;
+        }
+
+        let exceeded = window.remaining == 0;
+        window.remaining = window.remaining.saturating_sub(1);
+
+        Ratelimit {
+            bucket,
+            limit,
+            remaining: window.remaining,
+            reset_after: window.reset_at.saturating_duration_since(now),
+            exceeded: exceeded && self.enforced,
+        }
+    }
+}
+
+/// Bucket name, limit and resource a route is counted against.
+fn bucket<'a>(method: &Method, segments: &[&'a str]) -> (&'static str, u64, &'a str) {
+    match segments {
+        ["users", ..] if method == Method::PATCH => ("user_edit", 2, ""),
+        ["users", ..] => ("users", 20, ""),
+        ["bots", ..] => ("bots", 10, ""),
+        ["channels", id, "messages"] if method == Method::POST => ("messaging", 10, *id),
+        ["channels", ..] => ("channels", 15, ""),
+        ["servers", ..] => ("servers", 5, ""),
+        ["auth", ..] if method == Method::DELETE => ("auth_delete", 255, ""),
+        ["auth", ..] => ("auth", 15, ""),
+        _ => ("any", 20, ""),
+    }
+}
@@ -0,0 +1,1019 @@
+use std::collections::HashMap;
+
+use http::{Method, StatusCode};
+use rive_models::{
+    permission::Permission,
+    validate::{MAX_MESSAGE_LENGTH, MAX_REPLIES},
+};
+use serde_json::{json, Map, Value};
+
+use super::state::{now, State};
+
+/// Error response of the fake API.
+#[derive(Debug)]
+pub(super) struct Rejection {
+    pub status: StatusCode,
+    pub body: Value,
+}
+
+impl Rejection {
+    fn new(status: StatusCode, kind: &str) -> Self {
+        Self {
+            status,
+            body: json!({ "type": kind }),
+        }
+    }
+
+    fn not_found() -> Self {
+        Self::new(StatusCode::NOT_FOUND, "NotFound")
+    }
+
+    fn validation() -> Self {
+        Self::new(StatusCode::BAD_REQUEST, "FailedValidation")
+    }
+
+    pub fn unauthenticated() -> Self {
+        Self::new(StatusCode::UNAUTHORIZED, "InvalidSession")
+    }
+
+    fn missing_permission(permission: Permission) -> Self {
+        Self {
+            status: StatusCode::FORBIDDEN,
+            body: json!({ "type": "MissingPermission", "permission": permission.bits() }),
+        }
+    }
+}
+
+/// Successful response body, or [`Value::Null`] for an empty response.
+type Outcome = Result<Value, Rejection>;
+
+/// Authenticated request to the fake API.
+#[derive(Debug)]
+pub(super) struct Request<'a> {
+    /// ID of the requesting user.
+    pub user: &'a str,
+    /// Token the request was authenticated with.
+    pub token: &'a str,
+    pub method: &'a Method,
+    /// Non-empty path segments.
+    pub segments: &'a [&'a str],
+    pub query: HashMap<&'a str, &'a str>,
+    /// Value of the `Idempotency-Key` header, if any.
+    pub idempotency_key: Option<&'a str>,
+    pub body: Value,
+}
+
+/// Dispatch a request to the handler of a route usable without
+/// authentication.
+///
+/// Returns [`None`] if the route requires authentication, in which case the
+/// request is dispatched with [`route`] once authenticated.
+pub(super) fn public_route(
+    state: &mut State,
+    method: &Method,
+    segments: &[&str],
+    body: &Value,
+    server_url: &str,
+) -> Option<Outcome> {
+    let outcome = match segments {
+        [] if method == Method::GET => Ok(query_node(state, server_url)),
+        ["auth", "session", "login"] if method == Method::POST => login(state, body),
+        ["invites", code] if method == Method::GET => fetch_invite(state, code),
+        _ => return None,
+    };
+
+    Some(outcome)
+}
+
+/// Dispatch an authenticated request to its handler.
+pub(super) fn route(state: &mut State, request: &Request) -> Outcome {
+    let method = request.method;
+
+    match request.segments {
+        ["auth", "session", "logout"] if method == Method::POST => {
+            state.tokens.remove(request.token);
+            Ok(Value::Null)
+        }
+
+        ["users", "@me"] if method == Method::GET => fetch_user(state, request.user),
+        ["users", "@me"] if method == Method::PATCH => edit_user(state, request),
+        ["users", id] if method == Method::GET => fetch_user(state, id),
+
+        ["servers", "create"] if method == Method::POST => create_server(state, request),
+        ["servers", id] if method == Method::GET => fetch_server(state, request, id),
+        ["servers", id] if method == Method::PATCH => edit_server(state, request, id),
+        ["servers", id] if method == Method::DELETE => delete_server(state, request, id),
+        ["servers", id, "channels"] if method == Method::POST => create_channel(state, request, id),
+
+        ["servers", id, "members"] if method == Method::GET => fetch_members(state, request, id),
+        ["servers", id, "members", user] if method == Method::GET => {
+            fetch_member(state, request, id, user)
+        }
+        ["servers", id, "members", user] if method == Method::PATCH => {
+            edit_member(state, request, id, user)
+        }
+        ["servers", id, "members", user] if method == Method::DELETE => {
+            kick_member(state, request, id, user)
+        }
+
+        ["servers", id, "roles"] if method == Method::POST => create_role(state, request, id),
+        ["servers", id, "roles", role] if method == Method::PATCH => {
+            edit_role(state, request, id, role)
+        }
+        ["servers", id, "roles", role] if method == Method::DELETE => {
+            delete_role(state, request, id, role)
+        }
+        ["servers", id, "permissions", role] if method == Method::PUT && *role != "default" => {
+            set_role_permission(state, request, id, role)
+        }
+
+        ["channels", id] if method == Method::GET => fetch_channel(state, request, id),
+        ["channels", id] if method == Method::PATCH => edit_channel(state, request, id),
+        ["channels", id] if method == Method::DELETE => delete_channel(state, request, id),
+
+        ["channels", id, "messages"] if method == Method::GET => fetch_messages(state, request, id),
+        ["channels", id, "messages"] if method == Method::POST => send_message(state, request, id),
+        ["channels", id, "messages", message] if method == Method::GET => {
+            fetch_message(state, request, id, message)
+        }
+        ["channels", id, "messages", message] if method == Method::PATCH => {
+            edit_message(state, request, id, message)
+        }
+        ["channels", id, "messages", message] if method == Method::DELETE => {
+            delete_message(state, request, id, message)
+        }
+
+        ["channels", id, "invites"] if method == Method::POST => create_invite(state, request, id),
+        ["invites", code] if method == Method::POST => join_invite(state, request, code),
+        ["invites", code] if method == Method::DELETE => delete_invite(state, request, code),
+
+        _ => Err(Rejection::not_found()),
+    }
+}
+
+/// Copy the given fields of the request body into an entity.
+fn merge(target: &mut Value, body: &Value, fields: &[&str]) {
+    for field in fields {
+        if let Some(value) = body.get(field) {
+            target[*field] = value.clone();
+        }
+    }
+}
+
+/// Remove the fields listed in the `remove` array of the request body from an
+/// entity, given the mapping of the API field names to the JSON keys.
+fn remove(target: &mut Value, body: &Value, fields: &[(&str, &str)]) {
+    let Some(object) = target.as_object_mut() else {
+        return;
+    };
+
+    for name in body["remove"].as_array().into_iter().flatten() {
+        if let Some((_, key)) = fields.iter().find(|(field, _)| name == field) {
+            object.remove(*key);
+        }
+    }
+}
+
+/// Validate an optional string field of the request body.
+fn string_field<'a>(
+    body: &'a Value,
+    field: &str,
+    length: std::ops::RangeInclusive<usize>,
+) -> Result<Option<&'a str>, Rejection> {
+    match &body[field] {
+        Value::Null => Ok(None),
+        Value::String(value) if length.contains(&value.chars().count()) => Ok(Some(value)),
+        _ => Err(Rejection::validation()),
+    }
+}
+
+fn require(
+    state: &State,
+    server_id: &str,
+    user_id: &str,
+    permission: Permission,
+) -> Result<(), Rejection> {
+    if state
+        .server_permissions(server_id, user_id)
+        .contains(permission)
+    {
+        Ok(())
+    } else {
+        Err(Rejection::missing_permission(permission))
+    }
+}
+
+/// Server of a channel the user can see.
+fn visible_channel(state: &State, user_id: &str, channel_id: &str) -> Result<String, Rejection> {
+    let server_id = state
+        .channel_server(channel_id)
+        .ok_or_else(|| Rejection::new(StatusCode::NOT_FOUND, "UnknownChannel"))?;
+
+    if !state
+        .server_permissions(&server_id, user_id)
+        .contains(Permission::ViewChannel)
+    {
+        return Err(Rejection::new(StatusCode::NOT_FOUND, "UnknownChannel"));
+    }
+
+    Ok(server_id)
+}
+
+/// Server the user is a member of.
+fn joined_server(state: &State, user_id: &str, server_id: &str) -> Result<(), Rejection> {
+    if state.servers.contains_key(server_id) && state.is_member(server_id, user_id) {
+        Ok(())
+    } else {
+        Err(Rejection::new(StatusCode::NOT_FOUND, "UnknownServer"))
+    }
+}
+
+fn query_node(state: &State, server_url: &str) -> Value {
+    let autumn = if state.autumn_disabled {
+        json!({ "enabled": false, "url": "" })
+    } else {
+        json!({ "enabled": true, "url": format!("{server_url}/autumn") })
+    };
+
+    json!({
+        "revolt": "0.7.1",
+        "features": {
+            "captcha": { "enabled": false, "key": "" },
+            "email": false,
+            "invite_only": false,
+            "autumn": autumn,
+            "january": { "enabled": true, "url": format!("{server_url}/january") },
+            "voso": { "enabled": false, "url": "", "ws": "" },
+        },
+        "ws": format!("{}/ws", server_url.replacen("http", "ws", 1)),
+        "app": server_url,
+        "vapid": "",
+        "build": {
+            "commit_sha": "",
+            "commit_timestamp": "",
+            "semver": "0.7.1",
+            "origin_url": "",
+            "timestamp": "",
+        },
+    })
+}
+
+fn login(state: &mut State, body: &Value) -> Outcome {
+    let user_id = if let Some(ticket) = body["mfa_ticket"].as_str() {
+        let user_id = state
+            .mfa_tickets
+            .remove(ticket)
+            .ok_or_else(|| Rejection::new(StatusCode::UNAUTHORIZED, "InvalidToken"))?;
+        let code = body["mfa_response"]["recovery_code"].as_str();
+        let valid = state
+            .logins
+            .values()
+            .any(|login| login.user_id == user_id && login.recovery_code.as_deref() == code);
+        if code.is_none() || !valid {
+            return Err(Rejection::new(StatusCode::UNAUTHORIZED, "InvalidToken"));
+        }
+
+        user_id
+    } else {
+        let login = body["email"]
+            .as_str()
+            .and_then(|email| state.logins.get(email))
+            .filter(|login| body["password"] == login.password.as_str())
+            .ok_or_else(|| Rejection::new(StatusCode::UNAUTHORIZED, "InvalidCredentials"))?;
+
+        if login.recovery_code.is_some() {
+            let user_id = login.user_id.clone();
+            let ticket = state.next_id();
+            state.mfa_tickets.insert(ticket.clone(), user_id);
+
+            return Ok(json!({
+                "result": "MFA",
+                "ticket": ticket,
+                "allowed_methods": ["Recovery"],
+            }));
+        }
+
+        login.user_id.clone()
+    };
+
+    let session_id = state.next_id();
+    let token = format!("{session_id}.{}", state.next_id());
+    state.tokens.insert(token.clone(), user_id.clone());
+
+    Ok(json!({
+        "result": "Success",
+        "_id": session_id,
+        "user_id": user_id,
+        "token": token,
+        "name": body["friendly_name"].as_str().unwrap_or("Unknown"),
+    }))
+}
+
+fn fetch_user(state: &State, id: &str) -> Outcome {
+    state
+        .users
+        .get(id)
+        .cloned()
+        .ok_or_else(Rejection::not_found)
+}
+
+fn edit_user(state: &mut State, request: &Request) -> Outcome {
+    string_field(&request.body, "display_name", 2..=32)?;
+
+    let user = state
+        .users
+        .get_mut(request.user)
+        .ok_or_else(Rejection::not_found)?;
+    merge(user, &request.body, &["display_name", "status", "profile"]);
+    remove(
+        user,
+        &request.body,
+        &[
+            ("DisplayName", "display_name"),
+            ("StatusText", "status"),
+            ("StatusPresence", "status"),
+            ("ProfileContent", "profile"),
+        ],
+    );
+
+    Ok(user.clone())
+}
+
+fn create_server(state: &mut State, request: &Request) -> Outcome {
+    let name = string_field(&request.body, "name", 1..=32)?.ok_or_else(Rejection::validation)?;
+    string_field(&request.body, "description", 0..=1024)?;
+
+    let server_id = state.next_id();
+    let channel_id = state.next_id();
+
+    state.channels.insert(
+        channel_id.clone(),
+        json!({
+            "channel_type": "TextChannel",
+            "_id": channel_id,
+            "server": server_id,
+            "name": "General",
+        }),
+    );
+
+    let mut server = json!({
+        "_id": server_id,
+        "owner": request.user,
+        "name": name,
+        "channels": [channel_id],
+        "categories": [],
+        "roles": {},
+        "default_permissions": Permission::DEFAULT_SERVER.bits(),
+        "nsfw": request.body["nsfw"].as_bool().unwrap_or(false),
+    });
+    merge(&mut server, &request.body, &["description"]);
+
+    state.servers.insert(server_id.clone(), server.clone());
+    state.add_member(&server_id, request.user);
+
+    Ok(server)
+}
+
+fn fetch_server(state: &State, request: &Request, id: &str) -> Outcome {
+    joined_server(state, request.user, id)?;
+
+    Ok(state.servers[id].clone())
+}
+
+fn edit_server(state: &mut State, request: &Request, id: &str) -> Outcome {
+    joined_server(state, request.user, id)?;
+    require(state, id, request.user, Permission::ManageServer)?;
+    string_field(&request.body, "name", 1..=32)?;
+    string_field(&request.body, "description", 0..=1024)?;
+
+    let server = state.servers.get_mut(id).ok_or_else(Rejection::not_found)?;
+    merge(
+        server,
+        &request.body,
+        &[
+            "name",
+            "description",
+            "categories",
+            "system_messages",
+            "nsfw",
+            "discoverable",
+            "analytics",
+        ],
+    );
+    remove(
+        server,
+        &request.body,
+        &[
+            ("Description", "description"),
+            ("Categories", "categories"),
+            ("SystemMessages", "system_messages"),
+            ("Icon", "icon"),
+            ("Banner", "banner"),
+        ],
+    );
+
+    Ok(server.clone())
+}
+
+fn delete_server(state: &mut State, request: &Request, id: &str) -> Outcome {
+    joined_server(state, request.user, id)?;
+
+    if state.servers[id]["owner"] != request.user {
+        state
+            .members
+            .remove(&(id.to_string(), request.user.to_string()));
+
+        return Ok(Value::Null);
+    }
+
+    state.servers.remove(id);
+    state.members.retain(|(server, _), _| server != id);
+    state.invites.retain(|_, invite| invite["server"] != id);
+
+    let channels = state
+        .channels
+        .iter()
+        .filter(|(_, channel)| channel["server"] == id)
+        .map(|(channel_id, _)| channel_id.clone())
+        .collect::<Vec<_>>();
+    for channel_id in channels {
+        state.channels.remove(&channel_id);
+        state
+            .messages
+            .retain(|_, message| message["channel"] != channel_id.as_str());
+    }
+
+    Ok(Value::Null)
+}
+
+fn create_channel(state: &mut State, request: &Request, id: &str) -> Outcome {
+    joined_server(state, request.user, id)?;
+    require(state, id, request.user, Permission::ManageChannel)?;
+
+    let name = string_field(&request.body, "name", 1..=32)?.ok_or_else(Rejection::validation)?;
+    string_field(&request.body, "description", 0..=1024)?;
+    let channel_type = match request.body["type"].as_str() {
+        None | Some("Text") => "TextChannel",
+        Some("Voice") => "VoiceChannel",
+        Some(_) => return Err(Rejection::validation()),
+    };
+
+    let channel_id = state.next_id();
+    let mut channel = json!({
+        "channel_type": channel_type,
+        "_id": channel_id,
+        "server": id,
+        "name": name,
+        "nsfw": request.body["nsfw"].as_bool().unwrap_or(false),
+    });
+    merge(&mut channel, &request.body, &["description"]);
+
+    state.channels.insert(channel_id.clone(), channel.clone());
+    if let Some(channels) = state
+        .servers
+        .get_mut(id)
+        .and_then(|server| server["channels"].as_array_mut())
+    {
+        channels.push(json!(channel_id));
+    }
+
+    Ok(channel)
+}
+
+fn fetch_members(state: &State, request: &Request, id: &str) -> Outcome {
+    joined_server(state, request.user, id)?;
+
+    let members = state
+        .members
+        .iter()
+        .filter(|((server, _), _)| server == id)
+        .map(|(_, member)| member.clone())
+        .collect::<Vec<_>>();
+    let users = state
+        .members
+        .keys()
+        .filter(|(server, _)| server == id)
+        .filter_map(|(_, user)| state.users.get(user).cloned())
+        .collect::<Vec<_>>();
+
+    Ok(json!({ "members": members, "users": users }))
+}
+
+fn fetch_member(state: &State, request: &Request, id: &str, user: &str) -> Outcome {
+    joined_server(state, request.user, id)?;
+
+    state
+        .members
+        .get(&(id.to_string(), user.to_string()))
+        .cloned()
+        .ok_or_else(Rejection::not_found)
+}
+
+fn edit_member(state: &mut State, request: &Request, id: &str, user: &str) -> Outcome {
+    joined_server(state, request.user, id)?;
+    if !state.is_member(id, user) {
+        return Err(Rejection::not_found());
+    }
+
+    let body = &request.body;
+    let removed = |field: &str| {
+        body["remove"]
+            .as_array()
+            .map_or(false, |fields| fields.iter().any(|name| name == field))
+    };
+
+    if !body["nickname"].is_null() || removed("Nickname") {
+        let permission = if user == request.user {
+            Permission::ChangeNickname
+        } else {
+            Permission::ManageNicknames
+        };
+        require(state, id, request.user, permission)?;
+    }
+    if !body["roles"].is_null() || removed("Roles") {
+        require(state, id, request.user, Permission::AssignRoles)?;
+    }
+    if !body["timeout"].is_null() || removed("Timeout") {
+        require(state, id, request.user, Permission::TimeoutMembers)?;
+    }
+
+    string_field(body, "nickname", 1..=32)?;
+    if let Some(roles) = body["roles"].as_array() {
+        let server_roles = &state.servers[id]["roles"];
+        if roles.iter().any(|role| {
+            role.as_str()
+                .map_or(true, |role| server_roles.get(role).is_none())
+        }) {
+            return Err(Rejection::new(StatusCode::BAD_REQUEST, "InvalidRole"));
+        }
+    }
+
+    let member = state
+        .members
+        .get_mut(&(id.to_string(), user.to_string()))
+        .ok_or_else(Rejection::not_found)?;
+    merge(member, body, &["nickname", "roles", "timeout"]);
+    remove(
+        member,
+        body,
+        &[
+            ("Nickname", "nickname"),
+            ("Avatar", "avatar"),
+            ("Roles", "roles"),
+            ("Timeout", "timeout"),
+        ],
+    );
+
+    Ok(member.clone())
+}
+
+fn kick_member(state: &mut State, request: &Request, id: &str, user: &str) -> Outcome {
+    joined_server(state, request.user, id)?;
+    require(state, id, request.user, Permission::KickMembers)?;
+
+    if user == request.user {
+        return Err(Rejection::new(
+            StatusCode::BAD_REQUEST,
+            "CannotRemoveYourself",
+        ));
+    }
+    if state.servers[id]["owner"] == user {
+        return Err(Rejection::new(StatusCode::FORBIDDEN, "InvalidOperation"));
+    }
+
+    state
+        .members
+        .remove(&(id.to_string(), user.to_string()))
+        .map(|_| Value::Null)
+        .ok_or_else(Rejection::not_found)
+}
+
+fn create_role(state: &mut State, request: &Request, id: &str) -> Outcome {
+    joined_server(state, request.user, id)?;
+    require(state, id, request.user, Permission::ManageRole)?;
+
+    let name = string_field(&request.body, "name", 1..=32)?.ok_or_else(Rejection::validation)?;
+    let role_id = state.next_id();
+
+    let server = state.servers.get_mut(id).ok_or_else(Rejection::not_found)?;
+    let roles = server["roles"]
+        .as_object_mut()
+        .ok_or_else(Rejection::not_found)?;
+    let role = json!({
+        "name": name,
+        "permissions": { "a": 0, "d": 0 },
+        "rank": request.body["rank"].as_i64().unwrap_or(roles.len() as i64),
+    });
+    roles.insert(role_id.clone(), role.clone());
+
+    Ok(json!({ "id": role_id, "role": role }))
+}
+
+/// Role of a server the user may manage.
+fn managed_role<'a>(
+    state: &'a mut State,
+    request: &Request,
+    id: &str,
+    role: &str,
+    permission: Permission,
+) -> Result<&'a mut Value, Rejection> {
+    joined_server(state, request.user, id)?;
+    require(state, id, request.user, permission)?;
+
+    state
+        .servers
+        .get_mut(id)
+        .and_then(|server| server["roles"].get_mut(role))
+        .ok_or_else(Rejection::not_found)
+}
+
+fn edit_role(state: &mut State, request: &Request, id: &str, role: &str) -> Outcome {
+    string_field(&request.body, "name", 1..=32)?;
+
+    let role = managed_role(state, request, id, role, Permission::ManageRole)?;
+    merge(role, &request.body, &["name", "colour", "hoist", "rank"]);
+    remove(role, &request.body, &[("Colour", "colour")]);
+
+    Ok(role.clone())
+}
+
+fn delete_role(state: &mut State, request: &Request, id: &str, role: &str) -> Outcome {
+    managed_role(state, request, id, role, Permission::ManageRole)?;
+
+    if let Some(roles) = state
+        .servers
+        .get_mut(id)
+        .and_then(|server| server["roles"].as_object_mut())
+    {
+        roles.remove(role);
+    }
+    for ((server, _), member) in &mut state.members {
+        if server == id {
+            if let Some(roles) = member["roles"].as_array_mut() {
+                roles.retain(|member_role| member_role != role);
+            }
+        }
+    }
+
+    Ok(Value::Null)
+}
+
+fn set_role_permission(state: &mut State, request: &Request, id: &str, role: &str) -> Outcome {
+    let permissions = &request.body["permissions"];
+    let (Some(allow), Some(deny)) = (permissions["allow"].as_u64(), permissions["deny"].as_u64())
+    else {
+        return Err(Rejection::validation());
+    };
+
+    managed_role(state, request, id, role, Permission::ManagePermissions)?["permissions"] =
+        json!({ "a": allow, "d": deny });
+
+    Ok(state.servers[id].clone())
+}
+
+fn fetch_channel(state: &State, request: &Request, id: &str) -> Outcome {
+    visible_channel(state, request.user, id)?;
+
+    Ok(state.channels[id].clone())
+}
+
+fn edit_channel(state: &mut State, request: &Request, id: &str) -> Outcome {
+    let server_id = visible_channel(state, request.user, id)?;
+    require(state, &server_id, request.user, Permission::ManageChannel)?;
+    string_field(&request.body, "name", 1..=32)?;
+    string_field(&request.body, "description", 0..=1024)?;
+
+    let channel = state
+        .channels
+        .get_mut(id)
+        .ok_or_else(Rejection::not_found)?;
+    merge(channel, &request.body, &["name", "description", "nsfw"]);
+    remove(
+        channel,
+        &request.body,
+        &[
+            ("Description", "description"),
+            ("Icon", "icon"),
+            ("DefaultPermissions", "default_permissions"),
+        ],
+    );
+
+    Ok(channel.clone())
+}
+
+fn delete_channel(state: &mut State, request: &Request, id: &str) -> Outcome {
+    let server_id = visible_channel(state, request.user, id)?;
+    require(state, &server_id, request.user, Permission::ManageChannel)?;
+
+    state.channels.remove(id);
+    state.messages.retain(|_, message| message["channel"] != id);
+    state.invites.retain(|_, invite| invite["channel"] != id);
+    if let Some(channels) = state
+        .servers
+        .get_mut(&server_id)
+        .and_then(|server| server["channels"].as_array_mut())
+    {
+        channels.retain(|channel| channel != id);
+    }
+
+    Ok(Value::Null)
+}
+
+fn fetch_messages(state: &State, request: &Request, id: &str) -> Outcome {
+    let server_id = visible_channel(state, request.user, id)?;
+    require(
+        state,
+        &server_id,
+        request.user,
+        Permission::ReadMessageHistory,
+    )?;
+
+    let query = &request.query;
+    let limit = match query.get("limit").map(|limit| limit.parse::<usize>()) {
+        None => 50,
+        Some(Ok(limit @ 1..=100)) => limit,
+        Some(_) => return Err(Rejection::validation()),
+    };
+    let before = query.get("before").copied();
+    let after = query.get("after").copied();
+
+    let in_range = |message_id: &&String| {
+        before.map_or(true, |before| message_id.as_str() < before)
+            && after.map_or(true, |after| message_id.as_str() > after)
+    };
+    let in_channel = |(_, message): &(&String, &Value)| message["channel"] == id;
+
+    let messages = state.messages.iter().filter(|entry| in_range(&entry.0));
+    let messages = match query.get("sort").copied() {
+        None | Some("Latest") | Some("Relevance") => messages
+            .rev()
+            .filter(in_channel)
+            .take(limit)
+            .map(|(_, message)| message.clone())
+            .collect::<Vec<_>>(),
+        Some("Oldest") => messages
+            .filter(in_channel)
+            .take(limit)
+            .map(|(_, message)| message.clone())
+            .collect(),
+        Some(_) => return Err(Rejection::validation()),
+    };
+
+    if query.get("include_users").copied() != Some("true") {
+        return Ok(Value::Array(messages));
+    }
+
+    let mut authors = messages
+        .iter()
+        .filter_map(|message| message["author"].as_str())
+        .collect::<Vec<_>>();
+    authors.sort_unstable();
+    authors.dedup();
+
+    let users = authors
+        .iter()
+        .filter_map(|author| state.users.get(*author).cloned())
+        .collect::<Vec<_>>();
+    let members = authors
+        .iter()
+        .filter_map(|author| {
+            state
+                .members
+                .get(&(server_id.clone(), author.to_string()))
+                .cloned()
+        })
+        .collect::<Vec<_>>();
+
+    Ok(json!({ "messages": messages, "users": users, "members": members }))
+}
+
+fn send_message(state: &mut State, request: &Request, id: &str) -> Outcome {
+    let server_id = visible_channel(state, request.user, id)?;
+    require(state, &server_id, request.user, Permission::SendMessage)?;
+
+    let body = &request.body;
+    if !body["masquerade"].is_null() {
+        require(state, &server_id, request.user, Permission::Masquerade)?;
+    }
+
+    let content = body["content"].as_str().unwrap_or_default();
+    let has_embeds = body["embeds"]
+        .as_array()
+        .map_or(false, |embeds| !embeds.is_empty());
+    let has_attachments = body["attachments"]
+        .as_array()
+        .map_or(false, |attachments| !attachments.is_empty());
+    if content.is_empty() && !has_embeds && !has_attachments {
+        return Err(Rejection::new(StatusCode::BAD_REQUEST, "EmptyMessage"));
+    }
+    if content.chars().count() > MAX_MESSAGE_LENGTH {
+        return Err(Rejection::new(StatusCode::BAD_REQUEST, "PayloadTooLarge"));
+    }
+
+    let replies = body["replies"].as_array().cloned().unwrap_or_default();
+    if replies.len() > MAX_REPLIES {
+        return Err(Rejection::new(StatusCode::BAD_REQUEST, "TooManyReplies"));
+    }
+    let replies = replies
+        .iter()
+        .map(|reply| {
+            reply["id"]
+                .as_str()
+                .filter(|reply| {
+                    state
+                        .messages
+                        .get(*reply)
+                        .map_or(false, |message| message["channel"] == id)
+                })
+                .map(|reply| json!(reply))
+                .ok_or_else(|| Rejection::new(StatusCode::NOT_FOUND, "UnknownMessage"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if let Some(nonce) = request.idempotency_key {
+        if state.messages.values().any(|message| {
+            message["channel"] == id
+                && message["author"] == request.user
+                && message["nonce"] == nonce
+        }) {
+            return Err(Rejection::new(StatusCode::BAD_REQUEST, "DuplicateNonce"));
+        }
+    }
+
+    let message_id = state.next_id();
+    let mut message = Map::new();
+    message.insert("_id".to_string(), json!(message_id));
+    if let Some(nonce) = request.idempotency_key {
+        message.insert("nonce".to_string(), json!(nonce));
+    }
+    message.insert("channel".to_string(), json!(id));
+    message.insert("author".to_string(), json!(request.user));
+    if !content.is_empty() {
+        message.insert("content".to_string(), json!(content));
+    }
+    if !replies.is_empty() {
+        message.insert("replies".to_string(), Value::Array(replies));
+    }
+    for field in ["masquerade", "interactions"] {
+        if !body[field].is_null() {
+            message.insert(field.to_string(), body[field].clone());
+        }
+    }
+    let message = Value::Object(message);
+
+    state.messages.insert(message_id.clone(), message.clone());
+    if let Some(channel) = state.channels.get_mut(id) {
+        channel["last_message_id"] = json!(message_id);
+    }
+
+    Ok(message)
+}
+
+/// Message of a channel the user can see.
+fn visible_message(
+    state: &State,
+    request: &Request,
+    id: &str,
+    message: &str,
+) -> Result<String, Rejection> {
+    let server_id = visible_channel(state, request.user, id)?;
+
+    match state.messages.get(message) {
+        Some(message) if message["channel"] == id => Ok(server_id),
+        _ => Err(Rejection::new(StatusCode::NOT_FOUND, "UnknownMessage")),
+    }
+}
+
+fn fetch_message(state: &State, request: &Request, id: &str, message: &str) -> Outcome {
+    let server_id = visible_message(state, request, id, message)?;
+    require(
+        state,
+        &server_id,
+        request.user,
+        Permission::ReadMessageHistory,
+    )?;
+
+    Ok(state.messages[message].clone())
+}
+
+fn edit_message(state: &mut State, request: &Request, id: &str, message: &str) -> Outcome {
+    visible_message(state, request, id, message)?;
+
+    let content = string_field(&request.body, "content", 1..=MAX_MESSAGE_LENGTH)?;
+    let message = state
+        .messages
+        .get_mut(message)
+        .ok_or_else(Rejection::not_found)?;
+    if message["author"] != request.user {
+        return Err(Rejection::new(StatusCode::FORBIDDEN, "CannotEditMessage"));
+    }
+
+    if let Some(content) = content {
+        message["content"] = json!(content);
+    }
+    message["edited"] = json!(now());
+
+    Ok(message.clone())
+}
+
+fn delete_message(state: &mut State, request: &Request, id: &str, message: &str) -> Outcome {
+    let server_id = visible_message(state, request, id, message)?;
+    if state.messages[message]["author"] != request.user {
+        require(state, &server_id, request.user, Permission::ManageMessages)?;
+    }
+
+    state.messages.remove(message);
+
+    Ok(Value::Null)
+}
+
+fn create_invite(state: &mut State, request: &Request, id: &str) -> Outcome {
+    let server_id = visible_channel(state, request.user, id)?;
+    require(state, &server_id, request.user, Permission::InviteOthers)?;
+
+    let code = state.next_invite_code();
+    let invite = json!({
+        "type": "Server",
+        "_id": code,
+        "server": server_id,
+        "creator": request.user,
+        "channel": id,
+    });
+    state.invites.insert(code, invite.clone());
+
+    Ok(invite)
+}
+
+fn fetch_invite(state: &State, code: &str) -> Outcome {
+    let invite = state.invites.get(code).ok_or_else(Rejection::not_found)?;
+    let server_id = invite["server"].as_str().unwrap_or_default();
+    let server = state
+        .servers
+        .get(server_id)
+        .ok_or_else(Rejection::not_found)?;
+    let channel = state
+        .channels
+        .get(invite["channel"].as_str().unwrap_or_default())
+        .ok_or_else(Rejection::not_found)?;
+    let creator = state
+        .users
+        .get(invite["creator"].as_str().unwrap_or_default())
+        .ok_or_else(Rejection::not_found)?;
+    let member_count = state
+        .members
+        .keys()
+        .filter(|(server, _)| server == server_id)
+        .count();
+
+    Ok(json!({
+        "type": "Server",
+        "code": code,
+        "server_id": server_id,
+        "server_name": server["name"],
+        "channel_id": channel["_id"],
+        "channel_name": channel["name"],
+        "channel_description": channel["description"],
+        "user_name": creator["username"],
+        "member_count": member_count,
+    }))
+}
+
+fn join_invite(state: &mut State, request: &Request, code: &str) -> Outcome {
+    let server_id = state
+        .invites
+        .get(code)
+        .and_then(|invite| invite["server"].as_str())
+        .map(ToString::to_string)
+        .ok_or_else(Rejection::not_found)?;
+    let server = state
+        .servers
+        .get(&server_id)
+        .cloned()
+        .ok_or_else(Rejection::not_found)?;
+
+    if !state.is_member(&server_id, request.user) {
+        state.add_member(&server_id, request.user);
+    }
+
+    let channels = server["channels"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|channel| state.channels.get(channel.as_str()?).cloned())
+        .collect::<Vec<_>>();
+
+    Ok(json!({ "type": "Server", "channels": channels, "server": server }))
+}
+
+fn delete_invite(state: &mut State, request: &Request, code: &str) -> Outcome {
+    let invite = state.invites.get(code).ok_or_else(Rejection::not_found)?;
+
+    if invite["creator"] != request.user {
+        let server_id = invite["server"].as_str().unwrap_or_default().to_string();
+        require(state, &server_id, request.user, Permission::ManageServer)?;
+    }
+
+    state.invites.remove(code);
+
+    Ok(Value::Null)
+}
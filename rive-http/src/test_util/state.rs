@@ -0,0 +1,150 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use iso8601_timestamp::Timestamp;
+use rive_models::{
+    id::Id,
+    member::Member,
+    permission::{Permission, PermissionCalculator},
+    server::Server,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::ratelimit::Ratelimits;
+
+/// Credentials of an account that can log in.
+#[derive(Debug)]
+pub(super) struct Login {
+    pub user_id: String,
+    pub password: String,
+    /// Recovery code completing MFA, if the account requires it.
+    pub recovery_code: Option<String>,
+}
+
+/// In-memory state of the fake API.
+///
+/// Entities are stored in their JSON wire format.
+#[derive(Debug, Default)]
+pub(super) struct State {
+    /// Counter making generated IDs unique within the same millisecond.
+    counter: u64,
+    /// User IDs by session or bot token.
+    pub tokens: HashMap<String, String>,
+    /// Credentials by email.
+    pub logins: HashMap<String, Login>,
+    /// User IDs by pending MFA ticket.
+    pub mfa_tickets: HashMap<String, String>,
+    pub users: HashMap<String, Value>,
+    pub servers: HashMap<String, Value>,
+    pub channels: HashMap<String, Value>,
+    /// Messages ordered by ID, and thus by creation time.
+    pub messages: BTreeMap<String, Value>,
+    /// Members by server and user ID.
+    pub members: HashMap<(String, String), Value>,
+    /// Invites by code, with server, channel and creator IDs.
+    pub invites: HashMap<String, Value>,
+    pub ratelimits: Ratelimits,
+    /// Amount of requests received.
+    pub requests: usize,
+    /// Whether the file server is advertised as disabled.
+    pub autumn_disabled: bool,
+    /// Amount of upcoming responses to delay by `stall`.
+    pub stalled_responses: usize,
+    pub stall: Duration,
+}
+
+impl State {
+    /// Generate a new ULID.
+    pub fn next_id(&mut self) -> String {
+        self.counter += 1;
+
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system clock may have gone backwards")
+            .as_millis();
+
+        Id::<()>::from_ulid((millis << 80) | u128::from(self.counter)).value()
+    }
+
+    /// Generate a new invite code.
+    pub fn next_invite_code(&mut self) -> String {
+        let id = self.next_id();
+        id[id.len() - 8..].to_string()
+    }
+
+    pub fn add_user(&mut self, username: String, bot: bool) -> (String, String) {
+        let id = self.next_id();
+        let token = format!("{}.{}", id, self.next_id());
+
+        let mut user = json!({
+            "_id": id,
+            "username": username,
+            "discriminator": format!("{:04}", self.counter % 10000),
+            "online": true,
+        });
+        if bot {
+            user["bot"] = json!({ "owner": id });
+        }
+
+        self.users.insert(id.clone(), user);
+        self.tokens.insert(token.clone(), id.clone());
+
+        (id, token)
+    }
+
+    pub fn add_member(&mut self, server_id: &str, user_id: &str) {
+        let member = json!({
+            "_id": { "server": server_id, "user": user_id },
+            "joined_at": now(),
+            "roles": [],
+        });
+
+        self.members
+            .insert((server_id.to_string(), user_id.to_string()), member);
+    }
+
+    pub fn is_member(&self, server_id: &str, user_id: &str) -> bool {
+        self.members
+            .contains_key(&(server_id.to_string(), user_id.to_string()))
+    }
+
+    /// ID of the server a channel belongs to, if any.
+    pub fn channel_server(&self, channel_id: &str) -> Option<String> {
+        self.channels
+            .get(channel_id)?
+            .get("server")?
+            .as_str()
+            .map(ToString::to_string)
+    }
+
+    /// Server-wide permissions of a user.
+    pub fn server_permissions(&self, server_id: &str, user_id: &str) -> Permission {
+        let Some(server) = self
+            .servers
+            .get(server_id)
+            .and_then(|server| Server::deserialize(server).ok())
+        else {
+            return Permission::empty();
+        };
+        let member = self
+            .members
+            .get(&(server_id.to_string(), user_id.to_string()))
+            .and_then(|member| Member::deserialize(member).ok());
+
+        let user_id = Id::new(user_id.to_string());
+        let calculator = PermissionCalculator::new(&user_id).server(&server);
+
+        match &member {
+            Some(member) => calculator.member(member).in_server(),
+            None => calculator.in_server(),
+        }
+    }
+}
+
+/// Current time in the API timestamp format.
+pub(super) fn now() -> String {
+    Timestamp::now_utc().format().to_string()
+}
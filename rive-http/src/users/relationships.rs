@@ -8,16 +8,11 @@ use rive_models::{
 impl Client {
     /// This fetches your direct messages, including any DM and group DM conversations.
     pub async fn fetch_mutual_friends_and_servers(&self, id: &Id<UserMarker>) -> Result<Mutuals> {
-        Ok(self
-            .client
-            .get(ep!(self, "/users/{}/mutual", id.value_ref()))
-            .auth(&self.authentication)
-            .send()
-            .await?
-            .process_error()
-            .await?
-            .json()
-            .await?)
+        self.get_coalesced(
+            self.client
+                .get(ep!(self, "/users/{}/mutual", id.value_ref())),
+        )
+        .await
     }
 
     /// Accept another user's friend request
@@ -25,7 +20,7 @@ impl Client {
         Ok(self
             .client
             .put(ep!(self, "/users/{}/friend", id.value_ref()))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -39,7 +34,7 @@ impl Client {
         Ok(self
             .client
             .delete(ep!(self, "/users/{}/friend", id.value_ref()))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -53,7 +48,7 @@ impl Client {
         Ok(self
             .client
             .put(ep!(self, "/users/{}/block", id.value_ref()))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -67,7 +62,7 @@ impl Client {
         Ok(self
             .client
             .delete(ep!(self, "/users/{}/block", id.value_ref()))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -81,7 +76,7 @@ impl Client {
         Ok(self
             .client
             .post(ep!(self, "/users/friend"))
-            .auth(&self.authentication)
+            .auth(self)
             .json(&data)
             .send()
             .await?
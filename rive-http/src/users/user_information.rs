@@ -8,24 +8,18 @@ use rive_models::{
 impl Client {
     /// Retrieve your user information.
     pub async fn fetch_self(&self) -> Result<User> {
-        Ok(self
-            .client
-            .get(ep!(self, "/users/@me"))
-            .auth(&self.authentication)
-            .send()
-            .await?
-            .process_error()
-            .await?
-            .json()
-            .await?)
+        self.get_coalesced(self.client.get(ep!(self, "/users/@me")))
+            .await
     }
 
     /// Edit currently authenticated user.
     pub async fn edit_user(&self, data: &EditUserData) -> Result<User> {
+        self.validate(data)?;
+
         Ok(self
             .client
             .patch(ep!(self, "/users/@me"))
-            .auth(&self.authentication)
+            .auth(self)
             .json(&data)
             .send()
             .await?
@@ -37,24 +31,18 @@ impl Client {
 
     /// Fetch a user's information.
     pub async fn fetch_user(&self, id: &Id<UserMarker>) -> Result<User> {
-        Ok(self
-            .client
-            .get(ep!(self, "/users/{}", id.value_ref()))
-            .auth(&self.authentication)
-            .send()
-            .await?
-            .process_error()
-            .await?
-            .json()
-            .await?)
+        self.get_coalesced(self.client.get(ep!(self, "/users/{}", id.value_ref())))
+            .await
     }
 
     /// Change your username.
     pub async fn change_username(&self, data: &ChangeUsernameData) -> Result<User> {
+        self.validate(data)?;
+
         Ok(self
             .client
             .patch(ep!(self, "/users/@me/username"))
-            .auth(&self.authentication)
+            .auth(self)
             .json(&data)
             .send()
             .await?
@@ -69,7 +57,7 @@ impl Client {
         Ok(self
             .client
             .get(ep!(self, "/users/{}/default_avatar", id.value_ref()))
-            .auth(&self.authentication)
+            .auth(self)
             .send()
             .await?
             .process_error()
@@ -83,15 +71,10 @@ impl Client {
     ///
     ///Will fail if you do not have permission to access the other user's profile.
     pub async fn fetch_user_profile(&self, id: &Id<UserMarker>) -> Result<UserProfile> {
-        Ok(self
-            .client
-            .get(ep!(self, "/users/{}/profile", id.value_ref()))
-            .auth(&self.authentication)
-            .send()
-            .await?
-            .process_error()
-            .await?
-            .json()
-            .await?)
+        self.get_coalesced(
+            self.client
+                .get(ep!(self, "/users/{}/profile", id.value_ref())),
+        )
+        .await
     }
 }
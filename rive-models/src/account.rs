@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     id::{marker::AccountMarker, Id},
@@ -6,7 +6,7 @@ use crate::{
 };
 
 /// Account information
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AccountInfo {
     /// Account ID
     #[serde(rename = "_id")]
@@ -16,7 +16,7 @@ pub struct AccountInfo {
 }
 
 /// Email verification response
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(untagged)]
 pub enum EmailVerification {
     NoTicket,
@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::id::{
     marker::{AttachmentMarker, MessageMarker, ObjectMarker, ServerMarker, UserMarker},
@@ -6,7 +6,7 @@ use crate::id::{
 };
 
 /// Metadata associated with attachment
-#[derive(Deserialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 #[serde(tag = "type")]
 pub enum AttachmentMetadata {
     /// Attachment is just a generic uncategorised file
@@ -27,7 +27,7 @@ pub enum AttachmentMetadata {
 }
 
 /// Representation of an attachment on Revolt
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Attachment {
     /// Unique Id
     #[serde(rename = "_id")]
@@ -49,16 +49,22 @@ pub struct Attachment {
     pub size: isize,
 
     /// Whether this attachment was deleted
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub deleted: Option<bool>,
 
     /// Whether this attachment was reported
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub reported: Option<bool>,
 
     // NOTE: These 3 fields will be deprecated in the next update
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub message_id: Option<Id<MessageMarker>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub user_id: Option<Id<UserMarker>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub server_id: Option<Id<ServerMarker>>,
 
     /// ID of the object this attachment is associated with
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub object_id: Option<Id<ObjectMarker>>,
 }
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::id::{marker::AttachmentMarker, Id};
 
@@ -9,14 +9,14 @@ const fn default_as_true() -> bool {
 }
 
 /// Uploaded attachment data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct UploadData {
     /// Attachment ID
     pub id: Id<AttachmentMarker>,
 }
 
 /// Restricted content type
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum ContentType {
     Image,
     Video,
@@ -24,7 +24,7 @@ pub enum ContentType {
 }
 
 /// Tag information
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Tag {
     /// Max file size
     pub max_size: usize,
@@ -42,10 +42,11 @@ pub struct Tag {
     pub serve_if_field_present: Vec<String>,
 
     /// Restricted tag's content type
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub restrict_content_type: Option<ContentType>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Config {
     /// Autumn version
     pub autumn: String,
@@ -28,7 +28,7 @@ impl<'de> Deserialize<'de> for BotFlags {
 }
 
 /// Public bot
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PublicBot {
     /// Bot Id
     #[serde(rename = "_id")]
@@ -36,13 +36,15 @@ pub struct PublicBot {
     /// Bot Username
     pub username: String,
     /// Profile Avatar
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub avatar: Option<Attachment>,
     /// Profile Description
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 }
 
 /// Representation of a bot on Revolt
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Bot {
     /// Bot Id
     ///
@@ -64,13 +66,17 @@ pub struct Bot {
     #[serde(default)]
     pub discoverable: bool,
     /// Reserved; URL for handling interactions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub interactions_url: Option<String>,
     /// URL for terms of service
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub terms_of_service_url: Option<String>,
     /// URL for privacy policy
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub privacy_policy_url: Option<String>,
 
     /// Enum of bot flags
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub flags: Option<BotFlags>,
 }
 
@@ -108,7 +114,7 @@ pub struct PartialBot {
 /// Owned bot.
 ///
 /// Contains bot and user information.
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct OwnedBot {
     /// Bot object
     pub bot: Bot,
@@ -126,7 +132,7 @@ pub enum FieldsBot {
 /// Owned bots.
 ///
 /// Both lists are sorted by their IDs.
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct OwnedBots {
     /// Bot objects
     pub bots: Vec<Bot>,
@@ -11,11 +11,12 @@ use crate::{
         Id,
     },
     permission::{OverrideField, Permission},
+    unknown::{impl_unknown_variant, UnknownVariant},
 };
 
 /// Representation of a channel on Revolt
-#[derive(Deserialize, Debug, Clone)]
-#[serde(tag = "channel_type")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "channel_type", remote = "Self")]
 pub enum Channel {
     /// Personal "Saved Notes" channel which allows users to save messages
     SavedMessages {
@@ -37,6 +38,7 @@ pub enum Channel {
         /// 2-tuple of user ids participating in direct message
         recipients: Vec<Id<UserMarker>>,
         /// Id of the last message sent in this channel
+        #[serde(skip_serializing_if = "Option::is_none")]
         last_message_id: Option<Id<MessageMarker>>,
     },
 
@@ -51,17 +53,21 @@ pub enum Channel {
         /// User id of the owner of the group
         owner: Id<UserMarker>,
         /// Channel description
+        #[serde(skip_serializing_if = "Option::is_none")]
         description: Option<String>,
         /// Array of user ids participating in channel
         recipients: Vec<Id<UserMarker>>,
 
         /// Custom icon attachment
+        #[serde(skip_serializing_if = "Option::is_none")]
         icon: Option<Attachment>,
         /// Id of the last message sent in this channel
+        #[serde(skip_serializing_if = "Option::is_none")]
         last_message_id: Option<Id<MessageMarker>>,
 
         /// Permissions assigned to members of this group
         /// (does not apply to the owner of the group)
+        #[serde(skip_serializing_if = "Option::is_none")]
         permissions: Option<Permission>,
 
         /// Whether this group is marked as not safe for work
@@ -80,14 +86,18 @@ pub enum Channel {
         /// Display name of the channel
         name: String,
         /// Channel description
+        #[serde(skip_serializing_if = "Option::is_none")]
         description: Option<String>,
 
         /// Custom icon attachment
+        #[serde(skip_serializing_if = "Option::is_none")]
         icon: Option<Attachment>,
         /// Id of the last message sent in this channel
+        #[serde(skip_serializing_if = "Option::is_none")]
         last_message_id: Option<Id<MessageMarker>>,
 
         /// Default permissions assigned to users in this channel
+        #[serde(skip_serializing_if = "Option::is_none")]
         default_permissions: Option<OverrideField>,
         /// Permissions assigned based on role to this channel
         #[serde(default = "HashMap::<Id<RoleMarker>, OverrideField>::new")]
@@ -109,11 +119,14 @@ pub enum Channel {
         /// Display name of the channel
         name: String,
         /// Channel description
+        #[serde(skip_serializing_if = "Option::is_none")]
         description: Option<String>,
         /// Custom icon attachment
+        #[serde(skip_serializing_if = "Option::is_none")]
         icon: Option<Attachment>,
 
         /// Default permissions assigned to users in this channel
+        #[serde(skip_serializing_if = "Option::is_none")]
         default_permissions: Option<OverrideField>,
         /// Permissions assigned based on role to this channel
         #[serde(default = "HashMap::<Id<RoleMarker>, OverrideField>::new")]
@@ -123,30 +136,55 @@ pub enum Channel {
         #[serde(default)]
         nsfw: bool,
     },
+
+    /// Channel unknown to this crate
+    #[serde(skip)]
+    Unknown(UnknownVariant),
 }
 
+/// Types of the channels known to this crate.
+const CHANNEL_TYPES: &[&str] = &[
+    "SavedMessages",
+    "DirectMessage",
+    "Group",
+    "TextChannel",
+    "VoiceChannel",
+];
+
+impl_unknown_variant!(Channel, "channel_type", CHANNEL_TYPES);
+
 /// Partial values of [Channel]
-#[derive(Deserialize, Debug, Default, Clone)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct PartialChannel {
     /// Display name of the channel
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     /// User id of the owner of the group
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub owner: Option<Id<UserMarker>>,
     /// Channel description
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     /// Custom icon attachment
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub icon: Option<Attachment>,
     /// Whether this channel is marked as not safe for work
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub nsfw: Option<bool>,
     /// Whether this direct message channel is currently open on both sides
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub active: Option<bool>,
     /// Permissions assigned to members of this channel
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub permissions: Option<Permission>,
     /// Permissions assigned based on role to this channel
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub role_permissions: Option<HashMap<Id<RoleMarker>, OverrideField>>,
     /// Default permissions assigned to users in this channel
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub default_permissions: Option<OverrideField>,
     /// Id of the last message sent in this channel
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub last_message_id: Option<Id<MessageMarker>>,
 }
 
@@ -167,8 +205,8 @@ pub enum FieldsChannel {
 }
 
 /// Representation of an invite to a channel on Revolt
-#[derive(Deserialize, Debug, Clone)]
-#[serde(tag = "type")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", remote = "Self")]
 pub enum PartialInvite {
     /// Invite to a specific server channel
     Server {
@@ -192,10 +230,19 @@ pub enum PartialInvite {
         /// Id of the group channel this invite points to
         channel: Id<ChannelMarker>,
     },
+
+    /// Invite unknown to this crate
+    #[serde(skip)]
+    Unknown(UnknownVariant),
 }
 
+/// Types of the partial invites known to this crate.
+const PARTIAL_INVITE_TYPES: &[&str] = &["Server", "Group"];
+
+impl_unknown_variant!(PartialInvite, "type", PARTIAL_INVITE_TYPES);
+
 /// Composite primary key consisting of channel and user ID
-#[derive(Deserialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 pub struct ChannelCompositeKey {
     /// Channel ID
     pub channel: Id<ChannelMarker>,
@@ -204,14 +251,63 @@ pub struct ChannelCompositeKey {
 }
 
 /// Representation of the state of a channel from the perspective of a user
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ChannelUnread {
     /// Composite key pointing to a user's view of a channel
     #[serde(rename = "_id")]
     pub id: ChannelCompositeKey,
 
     /// ID of the last message read in this channel by a user
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub last_id: Option<Id<MessageMarker>>,
     /// Array of message ids that mention the user
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub mentions: Option<Vec<Id<MessageMarker>>>,
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use serde_test::{assert_ser_tokens, Token};
+
+    use super::Channel;
+
+    #[test]
+    fn serialize() {
+        let channel = Channel::SavedMessages {
+            id: "01FFD06NDVZ14W5T1WKKB4KKZX".to_string().into(),
+            user: "01EX2NCWQ0CHS3QJF0FEQS1GR4".to_string().into(),
+        };
+
+        assert_ser_tokens(
+            &channel,
+            &[
+                Token::Struct {
+                    name: "Channel",
+                    len: 3,
+                },
+                Token::Str("channel_type"),
+                Token::Str("SavedMessages"),
+                Token::Str("_id"),
+                Token::NewtypeStruct { name: "Id" },
+                Token::Str("01FFD06NDVZ14W5T1WKKB4KKZX"),
+                Token::Str("user"),
+                Token::NewtypeStruct { name: "Id" },
+                Token::Str("01EX2NCWQ0CHS3QJF0FEQS1GR4"),
+                Token::StructEnd,
+            ],
+        );
+
+        let value = json!({
+            "channel_type": "TextChannel",
+            "_id": "01FFD06NDVZ14W5T1WKKB4KKZX",
+            "server": "01EX2NCWQ0CHS3QJF0FEQS1GR4",
+            "name": "general",
+            "default_permissions": { "a": 0, "d": 4 },
+            "role_permissions": {},
+            "nsfw": false,
+        });
+        let channel: Channel = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(serde_json::to_value(channel).unwrap(), value);
+    }
+}
@@ -1,7 +1,7 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Revolt instance configuration
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct InstanceConfiguration {
     /// Revolt API version
     pub revolt: String,
@@ -23,7 +23,7 @@ pub struct InstanceConfiguration {
 }
 
 /// Features enabled on this Revolt node
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct InstanceFeatures {
     /// hCaptcha configuration
     pub captcha: CaptchaConfiguration,
@@ -45,7 +45,7 @@ pub struct InstanceFeatures {
 }
 
 /// hCaptcha configuration
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CaptchaConfiguration {
     /// Whether captcha is enabled
     pub enabled: bool,
@@ -55,7 +55,7 @@ pub struct CaptchaConfiguration {
 }
 
 /// File server service configuration
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AutumnConfiguration {
     /// Whether the service is enabled
     pub enabled: bool,
@@ -65,7 +65,7 @@ pub struct AutumnConfiguration {
 }
 
 /// Proxy server configuration
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct JanuaryConfiguration {
     /// Whether the service is enabled
     pub enabled: bool,
@@ -75,7 +75,7 @@ pub struct JanuaryConfiguration {
 }
 
 /// Voice server configuration
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct VosoConfiguration {
     /// Whether the service is enabled
     pub enabled: bool,
@@ -88,7 +88,7 @@ pub struct VosoConfiguration {
 }
 
 /// Build information
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct BuildInformation {
     /// Commit hash
     pub commit_sha: String,
@@ -1,17 +1,29 @@
-use crate::attachment::Attachment;
+use crate::{
+    attachment::Attachment,
+    unknown::{impl_unknown_variant, UnknownVariant},
+};
 use serde::{Deserialize, Serialize};
 
 /// Embed
-#[derive(Deserialize, Debug, Clone)]
-#[serde(tag = "type")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", remote = "Self")]
 pub enum Embed {
     Website(WebsiteMetadata),
     Image(Image),
     Video(Video),
     Text(Text),
     None,
+
+    /// Embed unknown to this crate
+    #[serde(skip)]
+    Unknown(UnknownVariant),
 }
 
+/// Types of the embeds known to this crate.
+const EMBED_TYPES: &[&str] = &["Website", "Image", "Video", "Text", "None"];
+
+impl_unknown_variant!(Embed, "type", EMBED_TYPES);
+
 /// Representation of a text embed before it is sent.
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct SendableEmbed {
@@ -60,7 +72,7 @@ pub struct SendableEmbedBorrowed<'a> {
 }
 
 /// Image
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Image {
     /// URL to the original image
     pub url: String,
@@ -76,7 +88,7 @@ pub struct Image {
 }
 
 /// Image positioning and size
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum ImageSize {
     /// Show large preview at the bottom of the embed
     Large,
@@ -86,7 +98,7 @@ pub enum ImageSize {
 }
 
 /// Video
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Video {
     /// URL to the original video
     pub url: String,
@@ -99,30 +111,36 @@ pub struct Video {
 }
 
 /// Text Embed
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Text {
     /// URL to icon
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub icon_url: Option<String>,
 
     /// URL for title
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
 
     /// Title of text embed
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
 
     /// Description of text embed
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 
     /// ID of uploaded attachment
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub media: Option<Attachment>,
 
     /// CSS colour
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub colour: Option<String>,
 }
 
 /// Information about special remote content
-#[derive(Deserialize, Debug, Clone)]
-#[serde(tag = "type")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", remote = "Self")]
 pub enum Special {
     /// No remote content
     None,
@@ -135,6 +153,7 @@ pub enum Special {
     /// YouTube video
     YouTube {
         id: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
         timestamp: Option<String>,
     },
 
@@ -160,10 +179,28 @@ pub enum Special {
         content_type: BandcampType,
         id: String,
     },
+
+    /// Remote content unknown to this crate
+    #[serde(skip)]
+    Unknown(UnknownVariant),
 }
 
+/// Types of the remote content known to this crate.
+const SPECIAL_TYPES: &[&str] = &[
+    "None",
+    "GIF",
+    "YouTube",
+    "Lightspeed",
+    "Twitch",
+    "Spotify",
+    "Soundcloud",
+    "Bandcamp",
+];
+
+impl_unknown_variant!(Special, "type", SPECIAL_TYPES);
+
 /// Type of remote Twitch content
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum TwitchType {
     Channel,
     Video,
@@ -171,48 +208,58 @@ pub enum TwitchType {
 }
 
 /// Type of remote Lightspeed.tv content
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum LightspeedType {
     Channel,
 }
 
 /// Type of remote Bandcamp content
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum BandcampType {
     Album,
     Track,
 }
 
 /// Website metadata
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct WebsiteMetadata {
     /// Direct URL to web page
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
 
     /// Original direct URL
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub original_url: Option<String>,
 
     /// Remote content
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub special: Option<Special>,
 
     /// Title of website
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
 
     /// Description of website
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 
     /// Embedded image
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub image: Option<Image>,
 
     /// Embedded video
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub video: Option<Video>,
 
     /// Site name
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub site_name: Option<String>,
 
     /// URL to site icon
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub icon_url: Option<String>,
 
     /// CSS colour
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub colour: Option<String>,
 }
@@ -1,20 +1,34 @@
 use serde::{Deserialize, Serialize};
 
-use crate::id::{
-    marker::{EmojiMarker, ServerMarker, UserMarker},
-    Id,
+use crate::{
+    id::{
+        marker::{EmojiMarker, ServerMarker, UserMarker},
+        Id,
+    },
+    unknown::{impl_unknown_variant, UnknownVariant},
 };
 
 /// Information about what owns this emoji
-#[derive(Serialize, Deserialize, Debug, Clone)]
-#[serde(tag = "type")]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type", remote = "Self")]
 pub enum EmojiParent {
-    Server { id: Id<ServerMarker> },
+    Server {
+        id: Id<ServerMarker>,
+    },
     Detached,
+
+    /// Emoji parent unknown to this crate
+    #[serde(skip)]
+    Unknown(UnknownVariant),
 }
 
+/// Types of the emoji parents known to this crate.
+const EMOJI_PARENT_TYPES: &[&str] = &["Server", "Detached"];
+
+impl_unknown_variant!(EmojiParent, "type", EMOJI_PARENT_TYPES);
+
 /// Representation of an Emoji on Revolt
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Emoji {
     /// Unique Id
     #[serde(rename = "_id")]
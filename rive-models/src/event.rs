@@ -1,4 +1,5 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
 
 use crate::{
     channel::{Channel, FieldsChannel, PartialChannel},
@@ -14,6 +15,7 @@ use crate::{
     message::{AppendMessage, Message, PartialMessage},
     report::Report,
     server::{FieldsRole, FieldsServer, PartialRole, PartialServer, Server},
+    unknown::{deserialize_or_unknown, impl_unknown_variant, UnknownVariant},
     user::{FieldsUser, PartialUser, RelationshipStatus, User, UserSettings},
     webhook::{FieldsWebhook, PartialWebhook, Webhook},
 };
@@ -26,8 +28,8 @@ pub enum Ping {
     Number(usize),
 }
 
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq)]
-#[serde(tag = "type")]
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(tag = "type", remote = "Self")]
 pub enum ErrorId {
     LabelMe,
     InternalError {
@@ -39,13 +41,14 @@ pub enum ErrorId {
     MalformedData {
         msg: String,
     },
-    #[serde(other)]
-    Unknown,
+    /// Error unknown to this crate
+    #[serde(skip)]
+    Unknown(UnknownVariant),
 }
 
 /// Event sent by server
-#[derive(Deserialize, Debug, Clone)]
-#[serde(tag = "type")]
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type", remote = "Self")]
 pub enum ServerEvent {
     /// Multiple events
     Bulk(BulkEvent),
@@ -175,11 +178,94 @@ pub enum ServerEvent {
     /// Auth event
     Auth(AuthifierEvent),
 
-    /// Unknown event
+    /// Event unknown to this crate
     ///
     /// If you received this event, please open an issue!
-    #[serde(other)]
-    Unknown,
+    #[serde(skip)]
+    Unknown(UnknownVariant),
+}
+
+/// Tags of the errors known to this crate.
+const ERROR_IDS: &[&str] = &[
+    "LabelMe",
+    "InternalError",
+    "InvalidSession",
+    "OnboardingNotFinished",
+    "AlreadyAuthenticated",
+    "MalformedData",
+];
+
+impl_unknown_variant!(ErrorId, "type", ERROR_IDS);
+
+impl ServerEvent {
+    /// Parse an event from its payload, if its tag is known.
+    ///
+    /// Payloads are parsed directly, rather than through the derived
+    /// implementation which would buffer them again.
+    fn parse_known(tag: &str, payload: &Value) -> Result<Option<Self>, serde_json::Error> {
+        let event = match tag {
+            "Bulk" => Self::Bulk(Deserialize::deserialize(payload)?),
+            "Error" => Self::Error(Deserialize::deserialize(payload)?),
+            "Authenticated" => Self::Authenticated,
+            "Ready" => Self::Ready(Deserialize::deserialize(payload)?),
+            "Pong" => Self::Pong(Deserialize::deserialize(payload)?),
+            "Message" => Self::Message(Deserialize::deserialize(payload)?),
+            "MessageUpdate" => Self::MessageUpdate(Deserialize::deserialize(payload)?),
+            "MessageAppend" => Self::MessageAppend(Deserialize::deserialize(payload)?),
+            "MessageDelete" => Self::MessageDelete(Deserialize::deserialize(payload)?),
+            "MessageReact" => Self::MessageReact(Deserialize::deserialize(payload)?),
+            "MessageUnreact" => Self::MessageUnreact(Deserialize::deserialize(payload)?),
+            "MessageRemoveReaction" => {
+                Self::MessageRemoveReaction(Deserialize::deserialize(payload)?)
+            }
+            "BulkMessageDelete" => Self::BulkMessageDelete(Deserialize::deserialize(payload)?),
+            "ChannelCreate" => Self::ChannelCreate(Deserialize::deserialize(payload)?),
+            "ChannelUpdate" => Self::ChannelUpdate(Deserialize::deserialize(payload)?),
+            "ChannelDelete" => Self::ChannelDelete(Deserialize::deserialize(payload)?),
+            "ChannelGroupJoin" => Self::ChannelGroupJoin(Deserialize::deserialize(payload)?),
+            "ChannelGroupLeave" => Self::ChannelGroupLeave(Deserialize::deserialize(payload)?),
+            "ChannelStartTyping" => Self::ChannelStartTyping(Deserialize::deserialize(payload)?),
+            "ChannelStopTyping" => Self::ChannelStopTyping(Deserialize::deserialize(payload)?),
+            "ChannelAck" => Self::ChannelAck(Deserialize::deserialize(payload)?),
+            "ServerCreate" => Self::ServerCreate(Deserialize::deserialize(payload)?),
+            "ServerUpdate" => Self::ServerUpdate(Deserialize::deserialize(payload)?),
+            "ServerDelete" => Self::ServerDelete(Deserialize::deserialize(payload)?),
+            "ServerMemberUpdate" => Self::ServerMemberUpdate(Deserialize::deserialize(payload)?),
+            "ServerMemberJoin" => Self::ServerMemberJoin(Deserialize::deserialize(payload)?),
+            "ServerMemberLeave" => Self::ServerMemberLeave(Deserialize::deserialize(payload)?),
+            "ServerRoleUpdate" => Self::ServerRoleUpdate(Deserialize::deserialize(payload)?),
+            "ServerRoleDelete" => Self::ServerRoleDelete(Deserialize::deserialize(payload)?),
+            "UserUpdate" => Self::UserUpdate(Deserialize::deserialize(payload)?),
+            "UserRelationship" => Self::UserRelationship(Deserialize::deserialize(payload)?),
+            "UserSettingsUpdate" => Self::UserSettingsUpdate(Deserialize::deserialize(payload)?),
+            "UserPlatformWipe" => Self::UserPlatformWipe(Deserialize::deserialize(payload)?),
+            "EmojiCreate" => Self::EmojiCreate(Deserialize::deserialize(payload)?),
+            "EmojiDelete" => Self::EmojiDelete(Deserialize::deserialize(payload)?),
+            "WebhookCreate" => Self::WebhookCreate(Deserialize::deserialize(payload)?),
+            "WebhookUpdate" => Self::WebhookUpdate(Deserialize::deserialize(payload)?),
+            "WebhookDelete" => Self::WebhookDelete(Deserialize::deserialize(payload)?),
+            "ReportCreate" => Self::ReportCreate(Deserialize::deserialize(payload)?),
+            "Auth" => Self::Auth(Deserialize::deserialize(payload)?),
+            _ => return Ok(None),
+        };
+
+        Ok(Some(event))
+    }
+}
+
+impl Serialize for ServerEvent {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Unknown(unknown) => unknown.payload.serialize(serializer),
+            _ => ServerEvent::serialize(self, serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ServerEvent {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_or_unknown(deserializer, "type", Self::parse_known, Self::Unknown)
+    }
 }
 
 /// Event sent by client
@@ -207,21 +293,21 @@ pub enum AuthifierEvent {
 }
 
 /// Bulk event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct BulkEvent {
     /// List of events
     pub v: Vec<ServerEvent>,
 }
 
 /// Error event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ErrorEvent {
     /// Error ID
     pub error: ErrorId,
 }
 
 /// Ready event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ReadyEvent {
     /// List of users
     pub users: Vec<User>,
@@ -232,18 +318,19 @@ pub struct ReadyEvent {
     /// List of server members
     pub members: Vec<Member>,
     /// List of emojis
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub emojis: Option<Vec<Emoji>>,
 }
 
 /// Server pong event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PongEvent {
     /// Client echo data
     pub data: Ping,
 }
 
 /// Message update event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct MessageUpdateEvent {
     /// Message ID
     pub id: Id<MessageMarker>,
@@ -254,7 +341,7 @@ pub struct MessageUpdateEvent {
 }
 
 /// Message append event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct MessageAppendEvent {
     /// Message ID
     pub id: Id<MessageMarker>,
@@ -265,7 +352,7 @@ pub struct MessageAppendEvent {
 }
 
 /// Message delete event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct MessageDeleteEvent {
     /// Message ID
     pub id: Id<MessageMarker>,
@@ -274,7 +361,7 @@ pub struct MessageDeleteEvent {
 }
 
 /// Message react event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct MessageReactEvent {
     /// Message ID
     pub id: Id<MessageMarker>,
@@ -287,7 +374,7 @@ pub struct MessageReactEvent {
 }
 
 /// Message reaction remove event
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct MessageUnreactEvent {
     /// Message ID
     pub id: Id<MessageMarker>,
@@ -300,7 +387,7 @@ pub struct MessageUnreactEvent {
 }
 
 /// Message remove reaction event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct MessageRemoveReactionEvent {
     /// Message ID
     pub id: Id<MessageMarker>,
@@ -311,7 +398,7 @@ pub struct MessageRemoveReactionEvent {
 }
 
 /// Bulk message delete event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct BulkMessageDeleteEvent {
     /// Channel ID
     pub channel: Id<ChannelMarker>,
@@ -320,7 +407,7 @@ pub struct BulkMessageDeleteEvent {
 }
 
 /// Channel update event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ChannelUpdateEvent {
     pub id: Id<ChannelMarker>,
     pub data: PartialChannel,
@@ -328,14 +415,14 @@ pub struct ChannelUpdateEvent {
 }
 
 /// Channel delete event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ChannelDeleteEvent {
     /// Deleted channel ID
     pub id: Id<ChannelMarker>,
 }
 
 /// Group join event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ChannelGroupJoinEvent {
     /// Group ID
     pub id: Id<ChannelMarker>,
@@ -344,7 +431,7 @@ pub struct ChannelGroupJoinEvent {
 }
 
 /// Group leave event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ChannelGroupLeaveEvent {
     /// Group ID
     pub id: Id<ChannelMarker>,
@@ -353,7 +440,7 @@ pub struct ChannelGroupLeaveEvent {
 }
 
 /// Channel start typing event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ChannelStartTypingEvent {
     /// Channel ID
     pub id: Id<ChannelMarker>,
@@ -362,7 +449,7 @@ pub struct ChannelStartTypingEvent {
 }
 
 /// Channel stop typing event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ChannelStopTypingEvent {
     /// Channel ID
     pub id: Id<ChannelMarker>,
@@ -371,7 +458,7 @@ pub struct ChannelStopTypingEvent {
 }
 
 /// Channel acknowledge event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ChannelAckEvent {
     /// Channel ID
     pub id: Id<ChannelMarker>,
@@ -382,7 +469,7 @@ pub struct ChannelAckEvent {
 }
 
 /// New server data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ServerCreateEvent {
     /// Server ID
     pub id: Id<ServerMarker>,
@@ -393,7 +480,7 @@ pub struct ServerCreateEvent {
 }
 
 /// Server update data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ServerUpdateEvent {
     /// Server ID
     pub id: Id<ServerMarker>,
@@ -404,14 +491,14 @@ pub struct ServerUpdateEvent {
 }
 
 /// Server delete event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ServerDeleteEvent {
     /// Deleted server ID
     pub id: Id<ServerMarker>,
 }
 
 /// Server member update event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ServerMemberUpdateEvent {
     /// Member ID
     pub id: MemberCompositeKey,
@@ -422,7 +509,7 @@ pub struct ServerMemberUpdateEvent {
 }
 
 /// Member join event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ServerMemberJoinEvent {
     /// Server ID
     pub id: Id<ServerMarker>,
@@ -431,7 +518,7 @@ pub struct ServerMemberJoinEvent {
 }
 
 /// Server member leave event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ServerMemberLeaveEvent {
     /// Server ID
     pub id: Id<ServerMarker>,
@@ -440,7 +527,7 @@ pub struct ServerMemberLeaveEvent {
 }
 
 /// Server role update event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ServerRoleUpdateEvent {
     /// Server ID
     pub id: Id<ServerMarker>,
@@ -453,7 +540,7 @@ pub struct ServerRoleUpdateEvent {
 }
 
 /// Server role delete event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ServerRoleDeleteEvent {
     /// Server ID
     pub id: Id<ServerMarker>,
@@ -462,7 +549,7 @@ pub struct ServerRoleDeleteEvent {
 }
 
 /// User update event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct UserUpdateEvent {
     /// User ID
     pub id: Id<UserMarker>,
@@ -473,7 +560,7 @@ pub struct UserUpdateEvent {
 }
 
 /// User relationship update event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct UserRelationshipEvent {
     pub id: Id<ServerMarker>,
     /// User with whom relationship changed
@@ -483,7 +570,7 @@ pub struct UserRelationshipEvent {
 }
 
 /// Settings update event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct UserSettingsUpdateEvent {
     pub id: String,
     /// Updated settings
@@ -491,7 +578,7 @@ pub struct UserSettingsUpdateEvent {
 }
 
 /// User wipe event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct UserPlatformWipeEvent {
     /// Deleted user ID
     pub user_id: Id<UserMarker>,
@@ -500,14 +587,14 @@ pub struct UserPlatformWipeEvent {
 }
 
 /// Emoji delete event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct EmojiDeleteEvent {
     /// Deleted emoji ID
     pub id: Id<EmojiMarker>,
 }
 
 /// Webhook update event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct WebhookUpdateEvent {
     /// Webhook ID
     pub id: Id<WebhookMarker>,
@@ -518,7 +605,124 @@ pub struct WebhookUpdateEvent {
 }
 
 /// Webhook delete event data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct WebhookDeleteEvent {
     pub id: Id<WebhookMarker>,
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{ErrorEvent, ErrorId, ServerEvent, ERROR_IDS};
+    use crate::channel::Channel;
+
+    #[test]
+    fn round_trip() {
+        let value = json!({
+            "type": "Bulk",
+            "v": [
+                {
+                    "type": "Ready",
+                    "users": [{
+                        "_id": "01EX2NCWQ0CHS3QJF0FEQS1GR4",
+                        "username": "user",
+                        "discriminator": "0001",
+                        "privileged": false,
+                        "online": true,
+                    }],
+                    "servers": [{
+                        "_id": "01FSRTTGJC1XJ6ZEQJMSX8Q96C",
+                        "owner": "01EX2NCWQ0CHS3QJF0FEQS1GR4",
+                        "name": "Server",
+                        "channels": ["01FFD06NDVZ14W5T1WKKB4KKZX"],
+                        "roles": {},
+                        "default_permissions": 0,
+                        "nsfw": false,
+                        "analytics": false,
+                        "discoverable": false,
+                    }],
+                    "channels": [{
+                        "channel_type": "SavedMessages",
+                        "_id": "01FFD06NDVZ14W5T1WKKB4KKZX",
+                        "user": "01EX2NCWQ0CHS3QJF0FEQS1GR4",
+                    }],
+                    "members": [{
+                        "_id": {
+                            "server": "01FSRTTGJC1XJ6ZEQJMSX8Q96C",
+                            "user": "01EX2NCWQ0CHS3QJF0FEQS1GR4",
+                        },
+                        "joined_at": "2023-01-01T00:00:00.000Z",
+                        "roles": [],
+                    }],
+                },
+                {
+                    "type": "Message",
+                    "_id": "01H9F3XJ2C2K6G8B4Y7Q9M0S1T",
+                    "channel": "01FFD06NDVZ14W5T1WKKB4KKZX",
+                    "author": "01EX2NCWQ0CHS3QJF0FEQS1GR4",
+                    "content": "hello",
+                    "reactions": {},
+                    "interactions": { "restrict_reactions": false },
+                },
+            ],
+        });
+
+        let event: ServerEvent = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(serde_json::to_value(event).unwrap(), value);
+
+        let event: ServerEvent = serde_json::from_str(&value.to_string()).unwrap();
+        assert_eq!(serde_json::to_value(event).unwrap(), value);
+    }
+
+    #[test]
+    fn unknown() {
+        let value = json!({ "type": "NewEvent", "id": "01FFD06NDVZ14W5T1WKKB4KKZX" });
+        let event: ServerEvent = serde_json::from_value(value.clone()).unwrap();
+        let ServerEvent::Unknown(unknown) = &event else {
+            panic!("expected an unknown event, got {event:?}");
+        };
+        assert_eq!(unknown.tag, "NewEvent");
+        assert_eq!(unknown.payload, value);
+        assert_eq!(serde_json::to_value(event).unwrap(), value);
+
+        let value = json!({ "type": "Error", "error": { "type": "NewError", "at": 0 } });
+        let event: ServerEvent = serde_json::from_value(value.clone()).unwrap();
+        assert!(matches!(
+            &event,
+            ServerEvent::Error(ErrorEvent { error: ErrorId::Unknown(unknown) }) if unknown.tag == "NewError"
+        ));
+        assert_eq!(serde_json::to_value(event).unwrap(), value);
+
+        assert!(serde_json::from_value::<ServerEvent>(json!({ "type": "ChannelDelete" })).is_err());
+
+        let value = json!({
+            "type": "ChannelCreate",
+            "channel_type": "Forum",
+            "_id": "01FFD06NDVZ14W5T1WKKB4KKZX",
+        });
+        let event: ServerEvent = serde_json::from_value(value.clone()).unwrap();
+        assert!(matches!(
+            &event,
+            ServerEvent::ChannelCreate(Channel::Unknown(unknown)) if unknown.tag == "Forum"
+        ));
+        assert_eq!(serde_json::to_value(event).unwrap(), value);
+
+        // a malformed nested variant is an error of the known event
+        let value = json!({
+            "type": "ChannelCreate",
+            "channel_type": "SavedMessages",
+            "_id": "01FFD06NDVZ14W5T1WKKB4KKZX",
+        });
+        assert!(serde_json::from_value::<ServerEvent>(value).is_err());
+    }
+
+    #[test]
+    fn known_errors() {
+        for tag in ERROR_IDS {
+            let value = json!({ "type": tag, "at": "here", "msg": "message" });
+            let error: ErrorId = serde_json::from_value(value).unwrap();
+            assert!(!matches!(error, ErrorId::Unknown(_)), "{tag} is unknown");
+        }
+    }
+}
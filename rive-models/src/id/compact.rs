@@ -0,0 +1,412 @@
+use std::{
+    any,
+    cmp::Ordering,
+    fmt::{self, Debug, Display, Formatter},
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    sync::Arc,
+};
+
+use iso8601_timestamp::Timestamp;
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{ulid, Id, InvalidUlid};
+
+/// Compact representation of an [`Id`] that is a ULID.
+///
+/// The ULID is stored as its 128-bit value, which makes this ID [`Copy`],
+/// 16 bytes large and free of allocations. IDs that are not ULIDs, such as
+/// invite codes, cannot be represented: see [`MaybeCompactId`] for IDs that
+/// may not be ULIDs.
+///
+/// Only uppercase ULIDs are accepted so that every ID keeps its exact string
+/// form. Comparing, hashing and serializing works like on [`Id`].
+///
+/// The in-memory cache keys its resources on [`Id`] regardless of this
+/// type, which is meant for storing IDs in your own data structures.
+pub struct CompactId<T> {
+    /// Big-endian bytes of the ULID.
+    ulid: [u8; 16],
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> CompactId<T> {
+    /// Create a compact ID from a ULID.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidUlid`] if the value is not an uppercase ULID.
+    pub fn parse(value: &str) -> Result<Self, InvalidUlid> {
+        if value.bytes().any(|byte| byte.is_ascii_lowercase()) {
+            return Err(InvalidUlid);
+        }
+
+        ulid::decode(value).map(Self::from_ulid)
+    }
+
+    /// Create a compact ID from the 128-bit value of a ULID.
+    pub const fn from_ulid(value: u128) -> Self {
+        Self {
+            ulid: value.to_be_bytes(),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Cast an ID from one type to another.
+    pub const fn cast<New>(self) -> CompactId<New> {
+        CompactId {
+            ulid: self.ulid,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Return the 128-bit value of the ULID.
+    pub const fn ulid(self) -> u128 {
+        u128::from_be_bytes(self.ulid)
+    }
+
+    /// Creation time of this ID, in milliseconds since the UNIX epoch.
+    pub const fn timestamp(self) -> u64 {
+        (self.ulid() >> ulid::RANDOM_BITS) as u64
+    }
+
+    /// Creation time of this ID.
+    ///
+    /// Returns [`None`] if the time cannot be represented.
+    pub fn created_at(self) -> Option<Timestamp> {
+        ulid::timestamp_from_millis(self.timestamp())
+    }
+
+    /// Convert this ID back to an [`Id`].
+    pub fn to_id(self) -> Id<T> {
+        Id::from_ulid(self.ulid())
+    }
+}
+
+impl<T> Clone for CompactId<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for CompactId<T> {}
+
+impl<T> Debug for CompactId<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("CompactId")?;
+        write_type_name::<T>(f)?;
+        f.write_str("(\"")?;
+        Display::fmt(self, f)?;
+        f.write_str("\")")
+    }
+}
+
+impl<T> Display for CompactId<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&ulid::encode(self.ulid()))
+    }
+}
+
+impl<T> Ord for CompactId<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // uppercase ULIDs sort like their value
+        self.ulid.cmp(&other.ulid)
+    }
+}
+
+impl<T> PartialOrd for CompactId<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Eq for CompactId<T> {}
+
+impl<T> PartialEq for CompactId<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ulid == other.ulid
+    }
+}
+
+impl<T> Hash for CompactId<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.ulid.hash(state)
+    }
+}
+
+impl<T> TryFrom<&Id<T>> for CompactId<T> {
+    type Error = InvalidUlid;
+
+    fn try_from(value: &Id<T>) -> Result<Self, Self::Error> {
+        Self::parse(value.value_ref())
+    }
+}
+
+impl<T> TryFrom<Id<T>> for CompactId<T> {
+    type Error = InvalidUlid;
+
+    fn try_from(value: Id<T>) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+impl<T> From<CompactId<T>> for Id<T> {
+    fn from(value: CompactId<T>) -> Self {
+        value.to_id()
+    }
+}
+
+impl<T> Serialize for CompactId<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct("Id", &self.to_string())
+    }
+}
+
+impl<'de, T> Deserialize<'de> for CompactId<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let id = Id::deserialize(deserializer)?;
+
+        Self::try_from(&id).map_err(D::Error::custom)
+    }
+}
+
+/// Representation of an [`Id`] that is compact if it is a ULID.
+///
+/// Other IDs, such as invite codes, are stored in a shared [`Arc<str>`],
+/// which is cloned without allocating.
+pub enum MaybeCompactId<T> {
+    /// The ID is an uppercase ULID.
+    Compact(CompactId<T>),
+    /// The ID is not an uppercase ULID.
+    Other(Arc<str>),
+}
+
+impl<T> MaybeCompactId<T> {
+    /// Create a new ID, compact if it is an uppercase ULID.
+    pub fn new(value: &str) -> Self {
+        match CompactId::parse(value) {
+            Ok(id) => Self::Compact(id),
+            Err(_) => Self::Other(value.into()),
+        }
+    }
+
+    /// Return the compact ID, if the ID is a ULID.
+    pub const fn compact(&self) -> Option<CompactId<T>> {
+        match self {
+            Self::Compact(id) => Some(*id),
+            Self::Other(_) => None,
+        }
+    }
+
+    /// Convert this ID back to an [`Id`].
+    pub fn to_id(&self) -> Id<T> {
+        match self {
+            Self::Compact(id) => id.to_id(),
+            Self::Other(value) => Id::new(value.to_string()),
+        }
+    }
+}
+
+impl<T> Clone for MaybeCompactId<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Compact(id) => Self::Compact(*id),
+            Self::Other(value) => Self::Other(Arc::clone(value)),
+        }
+    }
+}
+
+impl<T> Debug for MaybeCompactId<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("MaybeCompactId")?;
+        write_type_name::<T>(f)?;
+        f.write_str("(\"")?;
+        Display::fmt(self, f)?;
+        f.write_str("\")")
+    }
+}
+
+impl<T> Display for MaybeCompactId<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Compact(id) => Display::fmt(id, f),
+            Self::Other(value) => f.write_str(value),
+        }
+    }
+}
+
+impl<T> Ord for MaybeCompactId<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Compact(left), Self::Compact(right)) => left.cmp(right),
+            (Self::Other(left), Self::Other(right)) => left.cmp(right),
+            _ => self.to_string().cmp(&other.to_string()),
+        }
+    }
+}
+
+impl<T> PartialOrd for MaybeCompactId<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Eq for MaybeCompactId<T> {}
+
+impl<T> PartialEq for MaybeCompactId<T> {
+    fn eq(&self, other: &Self) -> bool {
+        // a value is always stored in the same variant
+        match (self, other) {
+            (Self::Compact(left), Self::Compact(right)) => left == right,
+            (Self::Other(left), Self::Other(right)) => left == right,
+            _ => false,
+        }
+    }
+}
+
+impl<T> Hash for MaybeCompactId<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Self::Compact(id) => id.hash(state),
+            Self::Other(value) => value.hash(state),
+        }
+    }
+}
+
+impl<T> From<CompactId<T>> for MaybeCompactId<T> {
+    fn from(value: CompactId<T>) -> Self {
+        Self::Compact(value)
+    }
+}
+
+impl<T> From<&Id<T>> for MaybeCompactId<T> {
+    fn from(value: &Id<T>) -> Self {
+        Self::new(value.value_ref())
+    }
+}
+
+impl<T> From<Id<T>> for MaybeCompactId<T> {
+    fn from(value: Id<T>) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl<T> From<MaybeCompactId<T>> for Id<T> {
+    fn from(value: MaybeCompactId<T>) -> Self {
+        value.to_id()
+    }
+}
+
+impl<T> Serialize for MaybeCompactId<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct("Id", &self.to_string())
+    }
+}
+
+impl<'de, T> Deserialize<'de> for MaybeCompactId<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Id::deserialize(deserializer).map(Self::from)
+    }
+}
+
+/// Write the name of a marker type in angle brackets.
+fn write_type_name<T>(f: &mut Formatter<'_>) -> fmt::Result {
+    let type_name = any::type_name::<T>();
+
+    if let Some(position) = type_name.rfind("::") {
+        if let Some(slice) = type_name.get(position + 2..) {
+            f.write_str("<")?;
+            f.write_str(slice)?;
+            f.write_str(">")?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, mem};
+
+    use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+    use static_assertions::assert_impl_all;
+
+    use super::{CompactId, MaybeCompactId};
+    use crate::id::{marker::UserMarker, Id, InvalidUlid};
+
+    assert_impl_all!(CompactId<()>: Copy, Send, Sync);
+    assert_impl_all!(MaybeCompactId<()>: Clone, Send, Sync);
+
+    #[test]
+    fn compact() {
+        assert_eq!(mem::size_of::<CompactId<()>>(), 16);
+
+        let id = CompactId::<UserMarker>::parse("01FFD06NDVZ14W5T1WKKB4KKZX").unwrap();
+        assert_eq!(id.timestamp(), 1_631_450_256_827);
+        assert_eq!(
+            id.to_id(),
+            Id::new("01FFD06NDVZ14W5T1WKKB4KKZX".to_string())
+        );
+        assert_eq!(CompactId::from_ulid(id.ulid()), id);
+        assert_tokens(
+            &id,
+            &[
+                Token::NewtypeStruct { name: "Id" },
+                Token::Str("01FFD06NDVZ14W5T1WKKB4KKZX"),
+            ],
+        );
+
+        for value in ["01ffd06ndvz14w5t1wkkb4kkzx", "Testers"] {
+            assert_eq!(CompactId::<UserMarker>::parse(value), Err(InvalidUlid));
+            assert_de_tokens_error::<CompactId<UserMarker>>(
+                &[Token::NewtypeStruct { name: "Id" }, Token::Str(value)],
+                "ID is not a valid ULID",
+            );
+        }
+
+        let earlier = CompactId::<UserMarker>::parse("01EX2NCWQ0CHS3QJF0FEQS1GR4").unwrap();
+        assert!(earlier < id);
+    }
+
+    #[test]
+    fn maybe_compact() {
+        let ulid = MaybeCompactId::<UserMarker>::new("01FFD06NDVZ14W5T1WKKB4KKZX");
+        let lowercase = MaybeCompactId::<UserMarker>::new("01ffd06ndvz14w5t1wkkb4kkzx");
+        let other = MaybeCompactId::<UserMarker>::new("Testers");
+        assert!(ulid.compact().is_some());
+        assert_eq!(lowercase.compact(), None);
+        assert_eq!(other.compact(), None);
+
+        for (id, value) in [
+            (&ulid, "01FFD06NDVZ14W5T1WKKB4KKZX"),
+            (&lowercase, "01ffd06ndvz14w5t1wkkb4kkzx"),
+            (&other, "Testers"),
+        ] {
+            assert_eq!(id.to_id(), Id::new(value.to_string()));
+            assert_eq!(&MaybeCompactId::from(Id::new(value.to_string())), id);
+            assert_tokens(
+                id,
+                &[Token::NewtypeStruct { name: "Id" }, Token::Str(value)],
+            );
+        }
+
+        let ids: HashSet<_> = [ulid.clone(), other.clone(), MaybeCompactId::new("Testers")].into();
+        assert_eq!(ids.len(), 2);
+
+        let mut sorted = [other.clone(), lowercase.clone(), ulid.clone()];
+        sorted.sort();
+        assert_eq!(sorted, [ulid, lowercase, other]);
+    }
+}
@@ -9,6 +9,24 @@
 //! - `serde` deserialization
 //! - [`Id::new`]
 //! - [`std::convert::From`]<[`String`]>
+//! - [`Id::parse`], which validates that the ID is a ULID
+//!
+//! # Creation time
+//!
+//! IDs of most resources are [ULIDs], which embed their creation time. It
+//! can be read with [`Id::created_at`], and [`Id::lower_bound`] and
+//! [`Id::upper_bound`] create IDs to filter resources by creation time:
+//!
+//! ```
+//! use iso8601_timestamp::Timestamp;
+//! use rive_models::id::{marker::MessageMarker, Id};
+//!
+//! let id: Id<MessageMarker> = Id::new("01FFD06NDVZ14W5T1WKKB4KKZX".to_string());
+//! let created_at = id.created_at().unwrap();
+//!
+//! assert!(Id::<MessageMarker>::lower_bound(created_at) <= id);
+//! assert!(Id::<MessageMarker>::upper_bound(created_at) >= id);
+//! ```
 //!
 //! # Casting between resource types
 //!
@@ -28,6 +46,8 @@
 //!
 //! assert_eq!(user_id.value(), channel_id.value());
 //! ```
+//!
+//! [ULIDs]: https://github.com/ulid/spec
 
 use std::{
     any,
@@ -42,7 +62,14 @@ use serde::{
     Deserialize, Deserializer, Serialize, Serializer,
 };
 
+#[cfg(feature = "compact-id")]
+mod compact;
 pub mod marker;
+mod ulid;
+
+#[cfg(feature = "compact-id")]
+pub use compact::{CompactId, MaybeCompactId};
+pub use ulid::InvalidUlid;
 
 /// ID of a resource, such as the ID of a message or user.
 ///
@@ -0,0 +1,184 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+use iso8601_timestamp::{Duration, Timestamp};
+
+use super::Id;
+
+/// Crockford's base32 alphabet used by ULIDs.
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Length of an encoded ULID.
+const LENGTH: usize = 26;
+
+/// Amount of bits of the random part of a ULID.
+pub(super) const RANDOM_BITS: u32 = 80;
+
+/// Largest timestamp a ULID can hold, in milliseconds.
+const MAX_TIMESTAMP: u64 = (1 << 48) - 1;
+
+/// ID is not a valid ULID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidUlid;
+
+impl Display for InvalidUlid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("ID is not a valid ULID")
+    }
+}
+
+impl Error for InvalidUlid {}
+
+/// Decode a ULID into its 128-bit value.
+pub(super) fn decode(value: &str) -> Result<u128, InvalidUlid> {
+    if value.len() != LENGTH {
+        return Err(InvalidUlid);
+    }
+
+    let value = value.bytes().try_fold(0u128, |value, character| {
+        let digit = ALPHABET
+            .iter()
+            .position(|digit| *digit == character.to_ascii_uppercase())
+            .ok_or(InvalidUlid)?;
+
+        // overflows if the first character holds more than 3 bits
+        value
+            .checked_mul(32)
+            .map(|value| value | digit as u128)
+            .ok_or(InvalidUlid)
+    })?;
+
+    Ok(value)
+}
+
+/// Encode a 128-bit value as a ULID.
+pub(super) fn encode(value: u128) -> String {
+    (0..LENGTH)
+        .rev()
+        .map(|i| ALPHABET[((value >> (i * 5)) & 0x1f) as usize] as char)
+        .collect()
+}
+
+/// Time of a ULID timestamp.
+pub(super) fn timestamp_from_millis(millis: u64) -> Option<Timestamp> {
+    Timestamp::UNIX_EPOCH.checked_add(Duration::milliseconds(millis as i64))
+}
+
+/// Milliseconds since the UNIX epoch, clamped to what a ULID can hold.
+fn millis(time: Timestamp) -> u128 {
+    let millis = time
+        .duration_since(Timestamp::UNIX_EPOCH)
+        .whole_milliseconds();
+
+    millis.clamp(0, MAX_TIMESTAMP as i128) as u128
+}
+
+impl<T> Id<T> {
+    /// Create an ID from a ULID, validating it.
+    ///
+    /// IDs of most resources are ULIDs, but some are not, such as invite
+    /// codes, so [`Id::new`] does not validate its value.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidUlid`] if the value is not a ULID.
+    pub fn parse(value: impl Into<String>) -> Result<Self, InvalidUlid> {
+        let value = value.into();
+        decode(&value)?;
+
+        Ok(Self::new(value))
+    }
+
+    /// Create an ID from the 128-bit value of a ULID.
+    pub fn from_ulid(value: u128) -> Self {
+        Self::new(encode(value))
+    }
+
+    /// Whether this ID is a valid ULID.
+    pub fn is_ulid(&self) -> bool {
+        decode(self.value_ref()).is_ok()
+    }
+
+    /// Creation time of this ID, in milliseconds since the UNIX epoch.
+    ///
+    /// Returns [`None`] if the ID is not a ULID.
+    pub fn timestamp(&self) -> Option<u64> {
+        decode(self.value_ref())
+            .ok()
+            .map(|value| (value >> RANDOM_BITS) as u64)
+    }
+
+    /// Creation time of this ID.
+    ///
+    /// Returns [`None`] if the ID is not a ULID.
+    pub fn created_at(&self) -> Option<Timestamp> {
+        self.timestamp().and_then(timestamp_from_millis)
+    }
+
+    /// Create the lowest ID of the given millisecond.
+    ///
+    /// Every ID created at or after this time is greater than or equal to
+    /// it, which makes it suitable as the `after` bound of a time range.
+    /// Times outside of what a ULID can hold are clamped.
+    pub fn lower_bound(time: Timestamp) -> Self {
+        Self::new(encode(millis(time) << RANDOM_BITS))
+    }
+
+    /// Create the greatest ID of the given millisecond.
+    ///
+    /// Every ID created at or before this time is lower than or equal to it,
+    /// which makes it suitable as the `before` bound of a time range. Times
+    /// outside of what a ULID can hold are clamped.
+    pub fn upper_bound(time: Timestamp) -> Self {
+        Self::new(encode(
+            (millis(time) << RANDOM_BITS) | ((1 << RANDOM_BITS) - 1),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iso8601_timestamp::{Duration, Timestamp};
+
+    use super::{decode, encode, InvalidUlid};
+    use crate::id::{marker::MessageMarker, Id};
+
+    #[test]
+    fn decoding() {
+        let id = Id::<MessageMarker>::parse("01FFD06NDVZ14W5T1WKKB4KKZX").unwrap();
+        assert_eq!(id.timestamp(), Some(1_631_450_256_827));
+        assert_eq!(Id::from_ulid(decode(id.value_ref()).unwrap()), id);
+        assert_eq!(
+            id.created_at(),
+            Timestamp::parse("2021-09-12T12:37:36.827Z")
+        );
+        assert!(Id::<MessageMarker>::new("01ffd06ndvz14w5t1wkkb4kkzx".to_string()).is_ulid());
+
+        assert_eq!(decode(&encode(u128::MAX)), Ok(u128::MAX));
+        assert_eq!(decode("81FFD06NDVZ14W5T1WKKB4KKZX"), Err(InvalidUlid));
+        assert_eq!(decode("01FFD06NDVZ14W5T1WKKB4KKZU"), Err(InvalidUlid));
+        assert_eq!(decode("01FFD06NDVZ14W5T1WKKB4KKZ"), Err(InvalidUlid));
+        assert_eq!(
+            Id::<MessageMarker>::new("Testers".to_string()).timestamp(),
+            None
+        );
+    }
+
+    #[test]
+    fn bounds() {
+        let id = Id::<MessageMarker>::new("01FFD06NDVZ14W5T1WKKB4KKZX".to_string());
+        let created_at = id.created_at().unwrap();
+
+        let lower = Id::<MessageMarker>::lower_bound(created_at);
+        let upper = Id::<MessageMarker>::upper_bound(created_at);
+        assert_eq!(lower.value_ref(), "01FFD06NDV0000000000000000");
+        assert_eq!(upper.value_ref(), "01FFD06NDVZZZZZZZZZZZZZZZZ");
+        assert!(lower < id && id < upper);
+        assert_eq!(lower.created_at(), Some(created_at));
+
+        let lower = Id::<MessageMarker>::lower_bound(Timestamp::UNIX_EPOCH - Duration::SECOND);
+        assert_eq!(lower.value_ref(), "00000000000000000000000000");
+    }
+}
@@ -6,13 +6,14 @@ use crate::{
         Id,
     },
     server::Server,
+    unknown::{impl_unknown_variant, UnknownVariant},
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Invite
 #[allow(clippy::large_enum_variant)]
-#[derive(Deserialize, Debug, Clone)]
-#[serde(tag = "type")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", remote = "Self")]
 pub enum Invite {
     /// Server channel invite
     Server {
@@ -23,20 +24,25 @@ pub enum Invite {
         /// Name of the server
         server_name: String,
         /// Attachment for server icon
+        #[serde(skip_serializing_if = "Option::is_none")]
         server_icon: Option<Attachment>,
         /// Attachment for server banner
+        #[serde(skip_serializing_if = "Option::is_none")]
         server_banner: Option<Attachment>,
         /// Enum of server flags
+        #[serde(skip_serializing_if = "Option::is_none")]
         server_flags: Option<i32>,
         /// Id of server channel
         channel_id: Id<ChannelMarker>,
         /// Name of server channel
         channel_name: String,
         /// Description of server channel
+        #[serde(skip_serializing_if = "Option::is_none")]
         channel_description: Option<String>,
         /// Name of user who created the invite
         user_name: String,
         /// Avatar of the user who created the invite
+        #[serde(skip_serializing_if = "Option::is_none")]
         user_avatar: Option<Attachment>,
         /// Number of members in this server
         member_count: i64,
@@ -50,17 +56,29 @@ pub enum Invite {
         /// Name of group channel
         channel_name: String,
         /// Description of group channel
+        #[serde(skip_serializing_if = "Option::is_none")]
         channel_description: Option<String>,
         /// Name of user who created the invite
         user_name: String,
         /// Avatar of the user who created the invite
+        #[serde(skip_serializing_if = "Option::is_none")]
         user_avatar: Option<Attachment>,
     },
+
+    /// Invite unknown to this crate
+    #[serde(skip)]
+    Unknown(UnknownVariant),
 }
 
+/// Types of the invites known to this crate.
+const INVITE_TYPES: &[&str] = &["Server", "Group"];
+
+impl_unknown_variant!(Invite, "type", INVITE_TYPES);
+
 /// Invite join response
-#[derive(Deserialize, Debug, Clone)]
-#[serde(tag = "type")]
+#[allow(clippy::large_enum_variant)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", remote = "Self")]
 pub enum InviteJoin {
     Server {
         /// Channels in the server
@@ -68,4 +86,13 @@ pub enum InviteJoin {
         /// Server we are joining
         server: Server,
     },
+
+    /// Invite join response unknown to this crate
+    #[serde(skip)]
+    Unknown(UnknownVariant),
 }
+
+/// Types of the invite join responses known to this crate.
+const INVITE_JOIN_TYPES: &[&str] = &["Server"];
+
+impl_unknown_variant!(InviteJoin, "type", INVITE_JOIN_TYPES);
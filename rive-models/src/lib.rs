@@ -17,6 +17,7 @@ pub mod invite;
 pub mod member;
 pub mod message;
 pub mod mfa;
+pub mod moderation;
 pub mod onboarding;
 pub mod permission;
 pub mod report;
@@ -25,6 +26,12 @@ pub mod session;
 pub mod snapshot;
 pub mod stats;
 pub mod strike;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
+#[cfg(feature = "totp")]
+pub mod totp;
+pub mod unknown;
 pub mod user;
+pub mod validate;
 pub mod voice;
 pub mod webhook;
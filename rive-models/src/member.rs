@@ -11,7 +11,7 @@ use crate::{
 };
 
 /// Composite primary key consisting of server and user id
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq, Hash)]
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Hash)]
 pub struct MemberCompositeKey {
     /// Server Id
     pub server: Id<ServerMarker>,
@@ -20,7 +20,7 @@ pub struct MemberCompositeKey {
 }
 
 /// Representation of a member of a server on Revolt
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Member {
     /// Unique member id
     #[serde(rename = "_id")]
@@ -30,42 +30,50 @@ pub struct Member {
     pub joined_at: Timestamp,
 
     /// Member's nickname
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub nickname: Option<String>,
     /// Avatar attachment
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub avatar: Option<Attachment>,
 
     /// Member's roles
     #[serde(default)]
     pub roles: Vec<Id<RoleMarker>>,
     /// Timestamp this member is timed out until
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub timeout: Option<Timestamp>,
 }
 
 /// Partial representation of a member of a server on Revolt
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PartialMember {
     /// Unique member id
-    #[serde(rename = "_id")]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "_id")]
     pub id: Option<MemberCompositeKey>,
 
     /// Time at which this user joined the server
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub joined_at: Option<Timestamp>,
 
     /// Member's nickname
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub nickname: Option<String>,
     /// Avatar attachment
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub avatar: Option<Attachment>,
 
     /// Member's roles
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub roles: Option<Vec<Id<RoleMarker>>>,
     /// Timestamp this member is timed out until
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub timeout: Option<Timestamp>,
 }
 
 /// Member List
 ///
 /// Both lists are sorted by ID.
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct MemberList {
     /// List of members
     pub members: Vec<Member>,
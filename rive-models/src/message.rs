@@ -10,17 +10,19 @@ use crate::{
         Id,
     },
     member::Member,
+    unknown::{impl_unknown_variant, UnknownVariant},
     user::User,
 };
 
 /// Channel message
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Message {
     /// Unique message ID
     #[serde(rename = "_id")]
     pub id: Id<MessageMarker>,
 
     /// Unique value generated by client sending this message
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub nonce: Option<String>,
 
     /// ID of the channel this message was sent in
@@ -30,24 +32,31 @@ pub struct Message {
     pub author: Id<UserMarker>,
 
     /// Message content
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub content: Option<String>,
 
     /// System message
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub system: Option<SystemMessage>,
 
     /// Array of attachments
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub attachments: Option<Vec<Attachment>>,
 
     /// Time at which this message was last edited
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub edited: Option<Timestamp>,
 
     /// Attached embeds to this message
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub embeds: Option<Vec<Embed>>,
 
     /// Array of user ids mentioned in this message
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub mentions: Option<Vec<Id<UserMarker>>>,
 
     /// Array of message ids this message is replying to
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub replies: Option<Vec<Id<MessageMarker>>>,
 
     /// Hashmap of emoji IDs to array of user IDs
@@ -59,53 +68,67 @@ pub struct Message {
     pub interactions: Interactions,
 
     /// Name and / or avatar overrides for this message
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub masquerade: Option<Masquerade>,
 }
 
 ///Partial channel message
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PartialMessage {
     /// Unique message ID
-    #[serde(rename = "_id")]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "_id")]
     pub id: Option<Id<MessageMarker>>,
 
     /// Unique value generated by client sending this message
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub nonce: Option<String>,
 
     /// ID of the channel this message was sent in
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub channel: Option<Id<ChannelMarker>>,
 
     /// ID of the user that sent this message
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub author: Option<Id<UserMarker>>,
 
     /// Message content
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub content: Option<String>,
 
     /// System message
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub system: Option<SystemMessage>,
 
     /// Array of attachments
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub attachments: Option<Vec<Attachment>>,
 
     /// Time at which this message was last edited
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub edited: Option<Timestamp>,
 
     /// Attached embeds to this message
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub embeds: Option<Vec<Embed>>,
 
     /// Array of user ids mentioned in this message
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub mentions: Option<Vec<Id<UserMarker>>>,
 
     /// Array of message ids this message is replying to
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub replies: Option<Vec<Id<MessageMarker>>>,
 
     /// Hashmap of emoji IDs to array of user IDs
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub reactions: Option<HashMap<Id<EmojiMarker>, HashSet<Id<UserMarker>>>>,
 
     /// Information about how this message should be interacted with
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub interactions: Option<Interactions>,
 
     /// Name and / or avatar overrides for this message
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub masquerade: Option<Masquerade>,
 }
 
@@ -189,8 +212,8 @@ pub struct MasqueradeBorrowed<'a> {
 }
 
 /// System message type
-#[derive(Deserialize, Debug, Clone)]
-#[serde(tag = "type", rename_all = "snake_case")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case", remote = "Self")]
 pub enum SystemMessage {
     Text {
         content: String,
@@ -229,8 +252,29 @@ pub enum SystemMessage {
         from: Id<UserMarker>,
         to: Id<UserMarker>,
     },
+
+    /// System message unknown to this crate
+    #[serde(skip)]
+    Unknown(UnknownVariant),
 }
 
+/// Types of the system messages known to this crate.
+const SYSTEM_MESSAGE_TYPES: &[&str] = &[
+    "text",
+    "user_added",
+    "user_remove",
+    "user_joined",
+    "user_left",
+    "user_kicked",
+    "user_banned",
+    "channel_renamed",
+    "channel_description_changed",
+    "channel_icon_changed",
+    "channel_ownership_changed",
+];
+
+impl_unknown_variant!(SystemMessage, "type", SYSTEM_MESSAGE_TYPES);
+
 /// Sort used for retrieving messages
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum MessageSort {
@@ -243,14 +287,15 @@ pub enum MessageSort {
 }
 
 /// Appended Information
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AppendMessage {
     /// Additional embeds to include in this message
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub embeds: Option<Vec<Embed>>,
 }
 
 /// Response used when multiple messages are fetched
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(untagged)]
 pub enum BulkMessageResponse {
     JustMessages(
@@ -263,12 +308,13 @@ pub enum BulkMessageResponse {
         /// List of users
         users: Vec<User>,
         /// List of members
+        #[serde(skip_serializing_if = "Option::is_none")]
         members: Option<Vec<Member>>,
     },
 }
 
 /// Representation of a message reply before it is sent
-#[derive(Serialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Reply {
     /// Message ID
     pub id: Id<MessageMarker>,
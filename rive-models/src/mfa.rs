@@ -15,7 +15,7 @@ pub enum MFAData {
 }
 
 /// MFA method
-#[derive(Deserialize, Debug, Clone, Eq, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
 pub enum MFAMethod {
     Password,
     Recovery,
@@ -26,7 +26,7 @@ pub enum MFAMethod {
 pub type MFARecoveryCode = String;
 
 /// MFA status
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct MFAStatus {
     pub email_otp: bool,
     pub trusted_handover: bool,
@@ -37,13 +37,13 @@ pub struct MFAStatus {
 }
 
 /// TOTP secret response
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct TOTPSecret {
     pub secret: String,
 }
 
 /// Multi-factor auth ticket
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct MFATicket {
     /// Unique Id
     #[serde(rename = "_id")]
@@ -64,5 +64,6 @@ pub struct MFATicket {
     pub authorised: bool,
 
     /// TOTP code at time of ticket creation
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub last_totp_code: Option<String>,
 }
@@ -0,0 +1,189 @@
+//! Role hierarchy and moderation eligibility checks.
+//!
+//! Members may only act on members ranked below them. A member's ranking is
+//! the rank of their top role, the lowest rank value being the highest
+//! ranking. The server owner ranks above everyone.
+
+use crate::{
+    id::{marker::RoleMarker, Id},
+    member::Member,
+    permission::{Permission, PermissionCalculator},
+    server::{Role, Server},
+};
+
+/// Action a member takes on another one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ModerationAction {
+    /// Kick the member from the server
+    Kick,
+    /// Ban the member from the server
+    Ban,
+    /// Time the member out
+    Timeout,
+    /// Assign roles to the member, or remove them
+    AssignRoles,
+    /// Change or remove the member's nickname
+    ManageNickname,
+    /// Remove the member's avatar
+    RemoveAvatar,
+}
+
+impl ModerationAction {
+    /// Permission required to take this action.
+    pub const fn permission(self) -> Permission {
+        match self {
+            Self::Kick => Permission::KickMembers,
+            Self::Ban => Permission::BanMembers,
+            Self::Timeout => Permission::TimeoutMembers,
+            Self::AssignRoles => Permission::AssignRoles,
+            Self::ManageNickname => Permission::ManageNicknames,
+            Self::RemoveAvatar => Permission::RemoveAvatars,
+        }
+    }
+}
+
+impl Server {
+    /// Ranking of a member of this server.
+    ///
+    /// Lower values rank higher. The owner ranks [`i64::MIN`] and members
+    /// without roles rank [`i64::MAX`].
+    pub fn ranking(&self, member: &Member) -> i64 {
+        if member.id.user == self.owner {
+            return i64::MIN;
+        }
+
+        member
+            .top_role(self)
+            .map_or(i64::MAX, |(_, role)| role.rank)
+    }
+
+    /// Whether a member ranks strictly above another one.
+    pub fn outranks(&self, member: &Member, other: &Member) -> bool {
+        self.ranking(member) < self.ranking(other)
+    }
+
+    /// Whether a member may take an action on another one.
+    ///
+    /// The actor needs the permission of the action and must rank above the
+    /// target. Nobody may act on the owner or on themselves.
+    pub fn can_moderate(&self, actor: &Member, target: &Member, action: ModerationAction) -> bool {
+        if actor.id.user == target.id.user || target.id.user == self.owner {
+            return false;
+        }
+
+        let permissions = PermissionCalculator::new(&actor.id.user)
+            .server(self)
+            .member(actor)
+            .in_server();
+
+        permissions.contains(action.permission()) && self.outranks(actor, target)
+    }
+
+    /// Whether a member may assign a role to others.
+    ///
+    /// The member needs [`Permission::AssignRoles`] and must rank above the
+    /// role.
+    pub fn can_assign_role(&self, member: &Member, role_id: &Id<RoleMarker>) -> bool {
+        let Some(role) = self.roles.get(role_id) else {
+            return false;
+        };
+
+        let permissions = PermissionCalculator::new(&member.id.user)
+            .server(self)
+            .member(member)
+            .in_server();
+
+        permissions.contains(Permission::AssignRoles) && self.ranking(member) < role.rank
+    }
+}
+
+impl Member {
+    /// Highest ranked role of this member in the server.
+    ///
+    /// Roles missing from the server are ignored.
+    pub fn top_role<'a>(&self, server: &'a Server) -> Option<(&'a Id<RoleMarker>, &'a Role)> {
+        self.roles
+            .iter()
+            .filter_map(|id| server.roles.get_key_value(id))
+            .min_by_key(|(_, role)| role.rank)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::ModerationAction;
+    use crate::{id::Id, member::Member, permission::Permission, server::Server};
+
+    fn member(user: &str, roles: &[&str]) -> Member {
+        serde_json::from_value(json!({
+            "_id": { "server": "server", "user": user },
+            "joined_at": "2023-01-01T00:00:00Z",
+            "roles": roles,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn hierarchy() {
+        let server: Server = serde_json::from_value(json!({
+            "_id": "server",
+            "owner": "owner",
+            "name": "Server",
+            "channels": [],
+            "roles": {
+                "admin": {
+                    "name": "Admin",
+                    "permissions": {
+                        "a": (Permission::KickMembers | Permission::AssignRoles).bits(),
+                        "d": 0,
+                    },
+                    "rank": 0,
+                },
+                "moderator": {
+                    "name": "Moderator",
+                    "permissions": { "a": Permission::KickMembers.bits(), "d": 0 },
+                    "rank": 1,
+                },
+                "member": {
+                    "name": "Member",
+                    "permissions": { "a": 0, "d": 0 },
+                    "rank": 2,
+                },
+            },
+            "default_permissions": Permission::DEFAULT_SERVER.bits(),
+        }))
+        .unwrap();
+
+        let owner = member("owner", &[]);
+        let admin = member("admin", &["moderator", "admin"]);
+        let moderator = member("moderator", &["moderator"]);
+        let other = member("other", &["moderator"]);
+        let user = member("user", &[]);
+
+        let (id, _) = admin.top_role(&server).unwrap();
+        assert_eq!(id.value_ref(), "admin");
+        assert_eq!(server.ranking(&owner), i64::MIN);
+        assert_eq!(server.ranking(&user), i64::MAX);
+        assert!(server.outranks(&admin, &moderator));
+        assert!(!server.outranks(&moderator, &other));
+
+        assert!(server.can_moderate(&admin, &moderator, ModerationAction::Kick));
+        assert!(server.can_moderate(&moderator, &user, ModerationAction::Kick));
+        assert!(!server.can_moderate(&moderator, &user, ModerationAction::Ban));
+        assert!(!server.can_moderate(&moderator, &other, ModerationAction::Kick));
+        assert!(!server.can_moderate(&moderator, &admin, ModerationAction::Kick));
+        assert!(!server.can_moderate(&admin, &owner, ModerationAction::Kick));
+        assert!(server.can_moderate(&owner, &admin, ModerationAction::Ban));
+
+        let admin_role = Id::new("admin".to_string());
+        let moderator_role = Id::new("moderator".to_string());
+        let member_role = Id::new("member".to_string());
+        assert!(server.can_assign_role(&admin, &moderator_role));
+        assert!(!server.can_assign_role(&admin, &admin_role));
+        assert!(!server.can_assign_role(&moderator, &member_role));
+        assert!(server.can_assign_role(&owner, &admin_role));
+    }
+}
@@ -1,5 +1,15 @@
+use std::cmp::Reverse;
+
+use iso8601_timestamp::Timestamp;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+use crate::{
+    channel::Channel,
+    id::{marker::UserMarker, Id},
+    member::Member,
+    server::Server,
+};
+
 bitflags::bitflags! {
     #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
     /// Permission
@@ -79,6 +89,41 @@ bitflags::bitflags! {
         // % Bits 53 to 64: do not use
     }
 }
+
+impl Permission {
+    /// Permissions to view a channel and read its history
+    pub const DEFAULT_VIEW_ONLY: Self = Self::ViewChannel.union(Self::ReadMessageHistory);
+
+    /// Base permissions of a channel participant
+    pub const DEFAULT: Self = Self::DEFAULT_VIEW_ONLY
+        .union(Self::SendMessage)
+        .union(Self::InviteOthers)
+        .union(Self::SendEmbeds)
+        .union(Self::UploadFiles)
+        .union(Self::Connect)
+        .union(Self::Speak);
+
+    /// Permissions of the recipients of a direct message channel, and of the
+    /// members of a group without configured permissions
+    pub const DEFAULT_DIRECT_MESSAGE: Self =
+        Self::DEFAULT.union(Self::ManageChannel).union(Self::React);
+
+    /// Default permissions of a new server
+    pub const DEFAULT_SERVER: Self = Self::DEFAULT
+        .union(Self::React)
+        .union(Self::ChangeNickname)
+        .union(Self::ChangeAvatar);
+
+    /// Permissions kept by a member in timeout
+    pub const ALLOW_IN_TIMEOUT: Self = Self::DEFAULT_VIEW_ONLY;
+
+    /// Apply an override to these permissions.
+    ///
+    /// Denied permissions take precedence over allowed ones.
+    pub const fn apply(self, field: &OverrideField) -> Self {
+        self.union(field.a).difference(field.d)
+    }
+}
 impl Serialize for Permission {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         serializer.serialize_u64(self.bits())
@@ -102,6 +147,12 @@ bitflags::bitflags! {
     }
 }
 
+impl Serialize for UserPermission {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.bits())
+    }
+}
+
 impl<'de> Deserialize<'de> for UserPermission {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         Ok(Self::from_bits_truncate(u64::deserialize(deserializer)?))
@@ -118,6 +169,23 @@ pub struct OverrideField {
     d: Permission,
 }
 
+impl OverrideField {
+    /// Create an override from allowed and denied permissions.
+    pub const fn new(allow: Permission, deny: Permission) -> Self {
+        Self { a: allow, d: deny }
+    }
+
+    /// Allowed permissions
+    pub const fn allow(&self) -> Permission {
+        self.a
+    }
+
+    /// Denied permissions
+    pub const fn deny(&self) -> Permission {
+        self.d
+    }
+}
+
 /// Representation of a single permission override
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct Override {
@@ -126,3 +194,345 @@ pub struct Override {
     /// Disallow bit flags
     deny: Permission,
 }
+
+/// Calculator of the effective permissions of a user, following the
+/// algorithm of the Revolt API.
+///
+/// In a server, the owner has every permission. Other members start from the
+/// server default permissions, then the overrides of their roles are applied
+/// from the lowest to the highest ranked one (the lowest rank value). In a
+/// server channel, the channel default override and the channel overrides
+/// of the member's roles are applied in turn. A member in timeout only keeps
+/// [`Permission::ALLOW_IN_TIMEOUT`].
+///
+/// Saved messages grant every permission to their owner, direct messages
+/// grant [`Permission::DEFAULT_DIRECT_MESSAGE`] to their recipients, and
+/// groups grant every permission to their owner and the group permissions to
+/// the other recipients.
+///
+/// ```
+/// use rive_models::{
+///     member::Member,
+///     permission::{Permission, PermissionCalculator},
+///     server::Server,
+/// };
+///
+/// fn can_kick(server: &Server, member: &Member) -> bool {
+///     PermissionCalculator::new(&member.id.user)
+///         .server(server)
+///         .member(member)
+///         .in_server()
+///         .contains(Permission::KickMembers)
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PermissionCalculator<'a> {
+    user: &'a Id<UserMarker>,
+    server: Option<&'a Server>,
+    member: Option<&'a Member>,
+    recipient_permissions: Option<UserPermission>,
+    now: Option<Timestamp>,
+}
+
+impl<'a> PermissionCalculator<'a> {
+    /// Create a calculator for the given user.
+    pub const fn new(user: &'a Id<UserMarker>) -> Self {
+        Self {
+            user,
+            server: None,
+            member: None,
+            recipient_permissions: None,
+            now: None,
+        }
+    }
+
+    /// Set the server to calculate permissions in.
+    ///
+    /// It is required for server channels.
+    pub const fn server(mut self, server: &'a Server) -> Self {
+        self.server = Some(server);
+        self
+    }
+
+    /// Set the server member of the user.
+    ///
+    /// Without it, the user has no permission in a server they do not own.
+    pub const fn member(mut self, member: &'a Member) -> Self {
+        self.member = Some(member);
+        self
+    }
+
+    /// Set the permissions the other recipient of a direct message channel
+    /// grants the user.
+    ///
+    /// If the other recipient does not allow the user to message them, the
+    /// user may only view the channel. By default, messaging is allowed.
+    pub const fn recipient_permissions(mut self, permissions: UserPermission) -> Self {
+        self.recipient_permissions = Some(permissions);
+        self
+    }
+
+    /// Set the time to check timeouts against.
+    ///
+    /// Defaults to the current time.
+    pub const fn at(mut self, now: Timestamp) -> Self {
+        self.now = Some(now);
+        self
+    }
+
+    /// Calculate the permissions of the user in the server.
+    pub fn in_server(&self) -> Permission {
+        let Some(server) = self.server else {
+            return Permission::empty();
+        };
+        if server.owner == *self.user {
+            return Permission::all();
+        }
+        let Some(member) = self.member else {
+            return Permission::empty();
+        };
+
+        let mut roles = member
+            .roles
+            .iter()
+            .filter_map(|id| server.roles.get(id))
+            .collect::<Vec<_>>();
+        roles.sort_by_key(|role| Reverse(role.rank));
+
+        let permissions = roles
+            .into_iter()
+            .fold(server.default_permissions, |permissions, role| {
+                permissions.apply(&role.permissions)
+            });
+
+        self.restrict_timeout(member, permissions)
+    }
+
+    /// Calculate the permissions of the user in the channel.
+    ///
+    /// Server channels require the [`server`] the channel belongs to. Nothing
+    /// is allowed in unknown channels.
+    ///
+    /// [`server`]: Self::server
+    pub fn in_channel(&self, channel: &Channel) -> Permission {
+        match channel {
+            Channel::SavedMessages { user, .. } => {
+                if user == self.user {
+                    Permission::all()
+                } else {
+                    Permission::empty()
+                }
+            }
+            Channel::DirectMessage { recipients, .. } => {
+                if !recipients.contains(self.user) {
+                    Permission::empty()
+                } else if self.recipient_permissions.map_or(true, |permissions| {
+                    permissions.contains(UserPermission::SendMessage)
+                }) {
+                    Permission::DEFAULT_DIRECT_MESSAGE
+                } else {
+                    Permission::DEFAULT_VIEW_ONLY
+                }
+            }
+            Channel::Group {
+                owner,
+                recipients,
+                permissions,
+                ..
+            } => {
+                if owner == self.user {
+                    Permission::all()
+                } else if recipients.contains(self.user) {
+                    permissions.unwrap_or(Permission::DEFAULT_DIRECT_MESSAGE)
+                        | Permission::ViewChannel
+                } else {
+                    Permission::empty()
+                }
+            }
+            Channel::TextChannel {
+                default_permissions,
+                role_permissions,
+                ..
+            }
+            | Channel::VoiceChannel {
+                default_permissions,
+                role_permissions,
+                ..
+            } => {
+                let Some(server) = self.server else {
+                    return Permission::empty();
+                };
+                if server.owner == *self.user {
+                    return Permission::all();
+                }
+                let Some(member) = self.member else {
+                    return Permission::empty();
+                };
+
+                let mut permissions = self.in_server();
+                if let Some(default_permissions) = default_permissions {
+                    permissions = permissions.apply(default_permissions);
+                }
+
+                let mut overrides = member
+                    .roles
+                    .iter()
+                    .filter_map(|id| Some((server.roles.get(id)?.rank, role_permissions.get(id)?)))
+                    .collect::<Vec<_>>();
+                overrides.sort_by_key(|(rank, _)| Reverse(*rank));
+
+                let permissions = overrides
+                    .into_iter()
+                    .fold(permissions, |permissions, (_, field)| {
+                        permissions.apply(field)
+                    });
+
+                self.restrict_timeout(member, permissions)
+            }
+            Channel::Unknown(_) => Permission::empty(),
+        }
+    }
+
+    fn restrict_timeout(&self, member: &Member, permissions: Permission) -> Permission {
+        let now = self.now.unwrap_or_else(Timestamp::now_utc);
+
+        if member.timeout.map_or(false, |timeout| timeout > now) {
+            permissions & Permission::ALLOW_IN_TIMEOUT
+        } else {
+            permissions
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{OverrideField, Permission, PermissionCalculator, UserPermission};
+    use crate::{channel::Channel, id::Id, member::Member, server::Server};
+
+    fn server() -> Server {
+        serde_json::from_value(json!({
+            "_id": "server",
+            "owner": "owner",
+            "name": "Server",
+            "channels": ["channel"],
+            "roles": {
+                "moderator": {
+                    "name": "Moderator",
+                    "permissions": { "a": Permission::KickMembers.bits(), "d": 0 },
+                    "rank": 0,
+                },
+                "muted": {
+                    "name": "Muted",
+                    "permissions": {
+                        "a": 0,
+                        "d": (Permission::SendMessage | Permission::KickMembers).bits(),
+                    },
+                    "rank": 1,
+                },
+            },
+            "default_permissions": Permission::DEFAULT_SERVER.bits(),
+        }))
+        .unwrap()
+    }
+
+    fn member(roles: &[&str], timeout: Option<&str>) -> Member {
+        serde_json::from_value(json!({
+            "_id": { "server": "server", "user": "member" },
+            "joined_at": "2023-01-01T00:00:00Z",
+            "roles": roles,
+            "timeout": timeout,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn server_permissions() {
+        let server = server();
+        let owner = Id::new("owner".to_string());
+        let user = Id::new("member".to_string());
+
+        let calculator = PermissionCalculator::new(&owner).server(&server);
+        assert_eq!(calculator.in_server(), Permission::all());
+
+        let calculator = PermissionCalculator::new(&user).server(&server);
+        assert_eq!(calculator.in_server(), Permission::empty());
+
+        let member = member(&[], None);
+        let permissions = calculator.clone().member(&member).in_server();
+        assert_eq!(permissions, Permission::DEFAULT_SERVER);
+
+        // the moderator role ranks higher, so it overrides the muted role
+        let member = self::member(&["muted", "moderator"], None);
+        let permissions = calculator.clone().member(&member).in_server();
+        assert!(permissions.contains(Permission::KickMembers));
+        assert!(!permissions.contains(Permission::SendMessage));
+
+        let member = self::member(&["moderator"], Some("2100-01-01T00:00:00Z"));
+        let permissions = calculator.member(&member).in_server();
+        assert_eq!(permissions, Permission::ALLOW_IN_TIMEOUT);
+    }
+
+    #[test]
+    fn channel_permissions() {
+        let server = server();
+        let user = Id::new("member".to_string());
+        let member = member(&["moderator"], None);
+        let calculator = PermissionCalculator::new(&user)
+            .server(&server)
+            .member(&member);
+
+        let channel: Channel = serde_json::from_value(json!({
+            "channel_type": "TextChannel",
+            "_id": "channel",
+            "server": "server",
+            "name": "general",
+            "default_permissions": {
+                "a": 0,
+                "d": (Permission::SendMessage | Permission::KickMembers).bits(),
+            },
+            "role_permissions": {
+                "moderator": { "a": Permission::SendMessage.bits(), "d": 0 },
+            },
+        }))
+        .unwrap();
+        let permissions = calculator.in_channel(&channel);
+        assert!(permissions.contains(Permission::SendMessage));
+        assert!(!permissions.contains(Permission::KickMembers));
+
+        let channel: Channel = serde_json::from_value(json!({
+            "channel_type": "DirectMessage",
+            "_id": "dm",
+            "active": true,
+            "recipients": ["member", "other"],
+        }))
+        .unwrap();
+        assert_eq!(
+            calculator.in_channel(&channel),
+            Permission::DEFAULT_DIRECT_MESSAGE
+        );
+        let calculator = calculator.recipient_permissions(UserPermission::Access);
+        assert_eq!(
+            calculator.in_channel(&channel),
+            Permission::DEFAULT_VIEW_ONLY
+        );
+
+        let channel: Channel = serde_json::from_value(json!({
+            "channel_type": "Group",
+            "_id": "group",
+            "name": "Group",
+            "owner": "other",
+            "recipients": ["member", "other"],
+            "permissions": Permission::SendMessage.bits(),
+        }))
+        .unwrap();
+        assert_eq!(
+            calculator.in_channel(&channel),
+            Permission::SendMessage | Permission::ViewChannel
+        );
+
+        let field = OverrideField::new(Permission::React, Permission::React);
+        assert_eq!(Permission::empty().apply(&field), Permission::empty());
+    }
+}
@@ -91,7 +91,7 @@ pub enum ReportStatus {
 }
 
 /// User-generated platform moderation report.
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Report {
     /// Unique Id
     #[serde(rename = "_id")]
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use serde::{Deserialize, Deserializer, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::{
     attachment::Attachment,
@@ -13,7 +13,7 @@ use crate::{
 };
 
 /// Representation of a server role
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Role {
     /// Role name
     pub name: String,
@@ -22,6 +22,7 @@ pub struct Role {
     /// Colour used for this role
     ///
     /// This can be any valid CSS colour
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub colour: Option<String>,
     /// Whether this role should be shown separately on the member sidebar
     #[serde(default)]
@@ -32,7 +33,7 @@ pub struct Role {
 }
 
 /// New role response
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct NewRole {
     /// ID of the role
     pub id: Id<RoleMarker>,
@@ -41,19 +42,24 @@ pub struct NewRole {
 }
 
 /// Partial representation of a server role
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PartialRole {
     /// Role name
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     /// Permissions available to this role
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub permissions: Option<OverrideField>,
     /// Colour used for this role
     ///
     /// This can be any valid CSS colour
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub colour: Option<String>,
     /// Whether this role should be shown separately on the member sidebar
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub hoist: Option<bool>,
     /// Ranking of this role
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub rank: Option<i64>,
 }
 
@@ -90,6 +96,12 @@ bitflags::bitflags! {
     }
 }
 
+impl Serialize for ServerFlags {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.bits())
+    }
+}
+
 impl<'de> Deserialize<'de> for ServerFlags {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         Ok(Self::from_bits_truncate(u64::deserialize(deserializer)?))
@@ -97,7 +109,7 @@ impl<'de> Deserialize<'de> for ServerFlags {
 }
 
 /// Representation of a server on Revolt
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Server {
     /// Unique Id
     #[serde(rename = "_id")]
@@ -108,14 +120,17 @@ pub struct Server {
     /// Name of the server
     pub name: String,
     /// Description for the server
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 
     /// Channels within this server
     // ! FIXME: this may be redundant
     pub channels: Vec<Id<ChannelMarker>>,
     /// Categories for this server
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub categories: Option<Vec<Category>>,
     /// Configuration for sending system event messages
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub system_messages: Option<SystemMessageChannels>,
 
     /// Roles for this server
@@ -125,11 +140,14 @@ pub struct Server {
     pub default_permissions: Permission,
 
     /// Icon attachment
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub icon: Option<Attachment>,
     /// Banner attachment
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub banner: Option<Attachment>,
 
     /// Enum of server flags
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub flags: Option<ServerFlags>,
 
     /// Whether this server is flagged as not safe for work
@@ -144,59 +162,74 @@ pub struct Server {
 }
 
 /// Partial representation of a server on Revolt
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PartialServer {
     /// User id of the owner
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub owner: Option<Id<UserMarker>>,
 
     /// Name of the server
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     /// Description for the server
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 
     /// Channels within this server
     // ! FIXME: this may be redundant
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub channels: Option<Vec<Id<ChannelMarker>>>,
     /// Categories for this server
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub categories: Option<Vec<Category>>,
     /// Configuration for sending system event messages
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub system_messages: Option<SystemMessageChannels>,
 
     /// Roles for this server
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub roles: Option<HashMap<Id<RoleMarker>, Role>>,
     /// Default set of server and channel permissions
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub default_permissions: Option<Permission>,
 
     /// Icon attachment
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub icon: Option<Attachment>,
     /// Banner attachment
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub banner: Option<Attachment>,
 
     /// Enum of server flags
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub flags: Option<ServerFlags>,
 
     /// Whether this server is flagged as not safe for work
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub nsfw: Option<bool>,
     /// Whether to enable analytics
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub analytics: Option<bool>,
     /// Whether this server should be publicly discoverable
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub discoverable: Option<bool>,
 }
 
 /// Representation of a server ban
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ServerBan {
     /// Unique member id
     #[serde(rename = "_id")]
     pub id: MemberCompositeKey,
     /// Reason for ban creation
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub reason: Option<String>,
 }
 
 /// Banned user
 ///
 /// Just enough user information to list bans.
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct BannedUser {
     /// Id of the banned user
     #[serde(rename = "_id")]
@@ -204,11 +237,12 @@ pub struct BannedUser {
     /// Username of the banned user
     pub username: String,
     /// Avatar of the banned user
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub avatar: Option<Attachment>,
 }
 
 /// Ban list
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct BanList {
     /// Users objects
     pub users: Vec<BannedUser>,
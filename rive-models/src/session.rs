@@ -5,10 +5,10 @@ use crate::{
     },
     mfa::MFAMethod,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Web Push subscription
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct WebPushSubscription {
     pub endpoint: String,
     pub p256dh: String,
@@ -16,7 +16,7 @@ pub struct WebPushSubscription {
 }
 
 /// Session information
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Session {
     /// Unique Id
     #[serde(rename = "_id")]
@@ -28,11 +28,12 @@ pub struct Session {
     /// Display name
     pub name: String,
     /// Web Push subscription
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub subscription: Option<WebPushSubscription>,
 }
 
 /// Partial session information
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SessionInfo {
     #[serde(rename = "_id")]
     pub id: Id<SessionMarker>,
@@ -40,7 +41,7 @@ pub struct SessionInfo {
 }
 
 /// Login response
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(tag = "result")]
 pub enum LoginResponse {
     Success(Session),
@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     channel::Channel,
@@ -8,13 +8,14 @@ use crate::{
     },
     message::Message,
     server::Server,
+    unknown::{impl_unknown_variant, UnknownVariant},
     user::User,
 };
 
 /// Enum to map into different models
 /// that can be saved in a snapshot
-#[derive(Deserialize, Debug, Clone)]
-#[serde(tag = "_type")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "_type", remote = "Self")]
 pub enum SnapshotContent {
     Message {
         /// Context before the message
@@ -31,10 +32,19 @@ pub enum SnapshotContent {
     },
     Server(Server),
     User(User),
+
+    /// Content unknown to this crate
+    #[serde(skip)]
+    Unknown(UnknownVariant),
 }
 
+/// Types of the snapshot content known to this crate.
+const SNAPSHOT_CONTENT_TYPES: &[&str] = &["Message", "Server", "User"];
+
+impl_unknown_variant!(SnapshotContent, "_type", SNAPSHOT_CONTENT_TYPES);
+
 /// Snapshot of some content
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Snapshot {
     /// Unique Id
     #[serde(rename = "_id")]
@@ -46,7 +56,7 @@ pub struct Snapshot {
 }
 
 /// Snapshot of some content with required data to render
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SnapshotWithContext {
     /// Snapshot itself
     #[serde(flatten)]
@@ -58,6 +68,6 @@ pub struct SnapshotWithContext {
     #[serde(rename = "_channels")]
     pub channels: Vec<Channel>,
     /// Server involved in snapshot
-    #[serde(rename = "_server")]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "_server")]
     pub server: Option<Server>,
 }
@@ -1,9 +1,9 @@
 use iso8601_timestamp::Timestamp;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Index access information
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct IndexAccess {
     /// Operations since timestamp
     pub ops: i32,
@@ -12,7 +12,7 @@ pub struct IndexAccess {
 }
 
 /// Collection index
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Index {
     /// Index name
     pub name: String,
@@ -21,7 +21,7 @@ pub struct Index {
 }
 
 /// Histogram entry
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct LatencyHistogramEntry {
     /// Time
     pub micros: i64,
@@ -30,7 +30,7 @@ pub struct LatencyHistogramEntry {
 }
 
 /// Collection latency stats
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct LatencyStats {
     /// Total operations
     pub ops: i64,
@@ -41,7 +41,7 @@ pub struct LatencyStats {
 }
 
 /// Collection storage stats
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct StorageStats {
     /// Uncompressed data size
@@ -61,7 +61,7 @@ pub struct StorageStats {
 }
 
 /// Query collection scan stats
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct CollectionScans {
     /// Number of total collection scans
@@ -71,7 +71,7 @@ pub struct CollectionScans {
 }
 
 /// Collection query execution stats
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct QueryExecStats {
     /// Stats regarding collection scans
@@ -79,7 +79,7 @@ pub struct QueryExecStats {
 }
 
 /// Collection stats
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct CollectionStats {
     /// Namespace
@@ -95,7 +95,7 @@ pub struct CollectionStats {
 }
 
 /// Server Stats
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Stats {
     /// Index usage information
     pub indices: HashMap<String, Vec<Index>>,
@@ -1,11 +1,11 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::id::{
     marker::{StrikeMarker, UserMarker},
     Id,
 };
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 /// Account Strike
 pub struct AccountStrike {
     /// Strike Id
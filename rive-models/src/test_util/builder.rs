@@ -0,0 +1,462 @@
+use std::collections::HashMap;
+
+use iso8601_timestamp::Timestamp;
+
+use crate::{
+    channel::Channel,
+    id::{
+        marker::{ChannelMarker, MessageMarker, RoleMarker, ServerMarker, UserMarker},
+        Id,
+    },
+    member::{Member, MemberCompositeKey},
+    message::{Masquerade, Message},
+    permission::{OverrideField, Permission},
+    server::{Role, Server},
+    user::{BotInformation, RelationshipStatus, User},
+};
+
+use super::{next_id, timestamp};
+
+/// Builder of a [`Message`].
+#[derive(Debug, Clone)]
+#[must_use = "builders have no effect if unused"]
+pub struct MessageBuilder(Message);
+
+impl MessageBuilder {
+    /// Create a builder of an empty message.
+    pub fn new(channel: Id<ChannelMarker>, author: Id<UserMarker>) -> Self {
+        Self(Message {
+            id: next_id(),
+            nonce: None,
+            channel,
+            author,
+            content: None,
+            system: None,
+            attachments: None,
+            edited: None,
+            embeds: None,
+            mentions: None,
+            replies: None,
+            reactions: HashMap::new(),
+            interactions: Default::default(),
+            masquerade: None,
+        })
+    }
+
+    /// Set the ID of the message.
+    pub fn id(mut self, id: Id<MessageMarker>) -> Self {
+        self.0.id = id;
+        self
+    }
+
+    /// Set the content of the message.
+    pub fn content(mut self, content: impl Into<String>) -> Self {
+        self.0.content = Some(content.into());
+        self
+    }
+
+    /// Set the nonce of the message.
+    pub fn nonce(mut self, nonce: impl Into<String>) -> Self {
+        self.0.nonce = Some(nonce.into());
+        self
+    }
+
+    /// Mention a user.
+    pub fn mention(mut self, user: Id<UserMarker>) -> Self {
+        self.0.mentions.get_or_insert_with(Vec::new).push(user);
+        self
+    }
+
+    /// Reply to a message.
+    pub fn reply(mut self, message: Id<MessageMarker>) -> Self {
+        self.0.replies.get_or_insert_with(Vec::new).push(message);
+        self
+    }
+
+    /// Set the time the message was last edited at.
+    pub fn edited(mut self, edited: Timestamp) -> Self {
+        self.0.edited = Some(edited);
+        self
+    }
+
+    /// Set the masquerade of the message.
+    pub fn masquerade(mut self, masquerade: Masquerade) -> Self {
+        self.0.masquerade = Some(masquerade);
+        self
+    }
+
+    /// Build the message.
+    pub fn build(self) -> Message {
+        self.0
+    }
+}
+
+/// Builder of a [`Server`].
+#[derive(Debug, Clone)]
+#[must_use = "builders have no effect if unused"]
+pub struct ServerBuilder(Server);
+
+impl ServerBuilder {
+    /// Create a builder of a server without channels or roles and with
+    /// [`Permission::DEFAULT_SERVER`] as default permissions.
+    pub fn new(owner: Id<UserMarker>) -> Self {
+        Self(Server {
+            id: next_id(),
+            owner,
+            name: "Server".to_string(),
+            description: None,
+            channels: Vec::new(),
+            categories: None,
+            system_messages: None,
+            roles: HashMap::new(),
+            default_permissions: Permission::DEFAULT_SERVER,
+            icon: None,
+            banner: None,
+            flags: None,
+            nsfw: false,
+            analytics: false,
+            discoverable: false,
+        })
+    }
+
+    /// Set the ID of the server.
+    pub fn id(mut self, id: Id<ServerMarker>) -> Self {
+        self.0.id = id;
+        self
+    }
+
+    /// Set the name of the server.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.0.name = name.into();
+        self
+    }
+
+    /// Set the description of the server.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.0.description = Some(description.into());
+        self
+    }
+
+    /// Add a channel to the server.
+    pub fn channel(mut self, channel: Id<ChannelMarker>) -> Self {
+        self.0.channels.push(channel);
+        self
+    }
+
+    /// Add a role to the server.
+    pub fn role(
+        mut self,
+        id: Id<RoleMarker>,
+        name: impl Into<String>,
+        permissions: OverrideField,
+        rank: i64,
+    ) -> Self {
+        self.0.roles.insert(
+            id,
+            Role {
+                name: name.into(),
+                permissions,
+                colour: None,
+                hoist: false,
+                rank,
+            },
+        );
+        self
+    }
+
+    /// Set the default permissions of the server.
+    pub fn default_permissions(mut self, permissions: Permission) -> Self {
+        self.0.default_permissions = permissions;
+        self
+    }
+
+    /// Set whether the server is marked as not safe for work.
+    pub fn nsfw(mut self, nsfw: bool) -> Self {
+        self.0.nsfw = nsfw;
+        self
+    }
+
+    /// Build the server.
+    pub fn build(self) -> Server {
+        self.0
+    }
+}
+
+/// Builder of a [`Member`].
+#[derive(Debug, Clone)]
+#[must_use = "builders have no effect if unused"]
+pub struct MemberBuilder(Member);
+
+impl MemberBuilder {
+    /// Create a builder of a member without roles, who joined at
+    /// [`timestamp`](super::timestamp).
+    pub fn new(server: Id<ServerMarker>, user: Id<UserMarker>) -> Self {
+        Self(Member {
+            id: MemberCompositeKey { server, user },
+            joined_at: timestamp(),
+            nickname: None,
+            avatar: None,
+            roles: Vec::new(),
+            timeout: None,
+        })
+    }
+
+    /// Set the time the member joined at.
+    pub fn joined_at(mut self, joined_at: Timestamp) -> Self {
+        self.0.joined_at = joined_at;
+        self
+    }
+
+    /// Set the nickname of the member.
+    pub fn nickname(mut self, nickname: impl Into<String>) -> Self {
+        self.0.nickname = Some(nickname.into());
+        self
+    }
+
+    /// Give a role to the member.
+    pub fn role(mut self, role: Id<RoleMarker>) -> Self {
+        self.0.roles.push(role);
+        self
+    }
+
+    /// Time the member out until the given time.
+    pub fn timeout(mut self, until: Timestamp) -> Self {
+        self.0.timeout = Some(until);
+        self
+    }
+
+    /// Build the member.
+    pub fn build(self) -> Member {
+        self.0
+    }
+}
+
+/// Builder of a [`User`].
+#[derive(Debug, Clone)]
+#[must_use = "builders have no effect if unused"]
+pub struct UserBuilder(User);
+
+impl UserBuilder {
+    /// Create a builder of a user with the `0001` discriminator.
+    pub fn new(username: impl Into<String>) -> Self {
+        Self(User {
+            id: next_id(),
+            username: username.into(),
+            discriminator: "0001".to_string(),
+            display_name: None,
+            avatar: None,
+            relations: None,
+            badges: None,
+            status: None,
+            profile: None,
+            flags: None,
+            privileged: false,
+            bot: None,
+            relationship: None,
+            online: None,
+        })
+    }
+
+    /// Set the ID of the user.
+    pub fn id(mut self, id: Id<UserMarker>) -> Self {
+        self.0.id = id;
+        self
+    }
+
+    /// Set the display name of the user.
+    pub fn display_name(mut self, display_name: impl Into<String>) -> Self {
+        self.0.display_name = Some(display_name.into());
+        self
+    }
+
+    /// Make the user a bot owned by another user.
+    pub fn bot(mut self, owner: Id<UserMarker>) -> Self {
+        self.0.bot = Some(BotInformation { owner });
+        self
+    }
+
+    /// Set the relationship of the current user with the user.
+    pub fn relationship(mut self, relationship: RelationshipStatus) -> Self {
+        self.0.relationship = Some(relationship);
+        self
+    }
+
+    /// Set whether the user is online.
+    pub fn online(mut self, online: bool) -> Self {
+        self.0.online = Some(online);
+        self
+    }
+
+    /// Build the user.
+    pub fn build(self) -> User {
+        self.0
+    }
+}
+
+#[derive(Debug, Clone)]
+enum ChannelKind {
+    SavedMessages { user: Id<UserMarker> },
+    DirectMessage,
+    Group { owner: Id<UserMarker> },
+    Text { server: Id<ServerMarker> },
+    Voice { server: Id<ServerMarker> },
+}
+
+/// Builder of a [`Channel`].
+///
+/// Fields a kind of channel does not have are ignored.
+#[derive(Debug, Clone)]
+#[must_use = "builders have no effect if unused"]
+pub struct ChannelBuilder {
+    kind: ChannelKind,
+    id: Id<ChannelMarker>,
+    name: String,
+    description: Option<String>,
+    recipients: Vec<Id<UserMarker>>,
+    last_message_id: Option<Id<MessageMarker>>,
+    default_permissions: Option<OverrideField>,
+    role_permissions: HashMap<Id<RoleMarker>, OverrideField>,
+    nsfw: bool,
+}
+
+impl ChannelBuilder {
+    fn new(kind: ChannelKind, name: &str) -> Self {
+        Self {
+            kind,
+            id: next_id(),
+            name: name.to_string(),
+            description: None,
+            recipients: Vec::new(),
+            last_message_id: None,
+            default_permissions: None,
+            role_permissions: HashMap::new(),
+            nsfw: false,
+        }
+    }
+
+    /// Create a builder of the saved messages channel of a user.
+    pub fn saved_messages(user: Id<UserMarker>) -> Self {
+        Self::new(ChannelKind::SavedMessages { user }, "Saved Notes")
+    }
+
+    /// Create a builder of a direct message channel between two users.
+    pub fn direct_message(user: Id<UserMarker>, other: Id<UserMarker>) -> Self {
+        Self::new(ChannelKind::DirectMessage, "")
+            .recipient(user)
+            .recipient(other)
+    }
+
+    /// Create a builder of a group with its owner as only recipient.
+    pub fn group(owner: Id<UserMarker>) -> Self {
+        Self::new(
+            ChannelKind::Group {
+                owner: owner.clone(),
+            },
+            "Group",
+        )
+        .recipient(owner)
+    }
+
+    /// Create a builder of a text channel of a server.
+    pub fn text(server: Id<ServerMarker>) -> Self {
+        Self::new(ChannelKind::Text { server }, "general")
+    }
+
+    /// Create a builder of a voice channel of a server.
+    pub fn voice(server: Id<ServerMarker>) -> Self {
+        Self::new(ChannelKind::Voice { server }, "Voice")
+    }
+
+    /// Set the ID of the channel.
+    pub fn id(mut self, id: Id<ChannelMarker>) -> Self {
+        self.id = id;
+        self
+    }
+
+    /// Set the name of the channel.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Set the description of the channel.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add a recipient to the channel.
+    pub fn recipient(mut self, user: Id<UserMarker>) -> Self {
+        self.recipients.push(user);
+        self
+    }
+
+    /// Set the ID of the last message sent in the channel.
+    pub fn last_message_id(mut self, message: Id<MessageMarker>) -> Self {
+        self.last_message_id = Some(message);
+        self
+    }
+
+    /// Set the default permissions of the channel.
+    pub fn default_permissions(mut self, permissions: OverrideField) -> Self {
+        self.default_permissions = Some(permissions);
+        self
+    }
+
+    /// Override the permissions of a role in the channel.
+    pub fn role_permissions(mut self, role: Id<RoleMarker>, permissions: OverrideField) -> Self {
+        self.role_permissions.insert(role, permissions);
+        self
+    }
+
+    /// Set whether the channel is marked as not safe for work.
+    pub fn nsfw(mut self, nsfw: bool) -> Self {
+        self.nsfw = nsfw;
+        self
+    }
+
+    /// Build the channel.
+    pub fn build(self) -> Channel {
+        match self.kind {
+            ChannelKind::SavedMessages { user } => Channel::SavedMessages { id: self.id, user },
+            ChannelKind::DirectMessage => Channel::DirectMessage {
+                id: self.id,
+                active: true,
+                recipients: self.recipients,
+                last_message_id: self.last_message_id,
+            },
+            ChannelKind::Group { owner } => Channel::Group {
+                id: self.id,
+                name: self.name,
+                owner,
+                description: self.description,
+                recipients: self.recipients,
+                icon: None,
+                last_message_id: self.last_message_id,
+                permissions: None,
+                nsfw: self.nsfw,
+            },
+            ChannelKind::Text { server } => Channel::TextChannel {
+                id: self.id,
+                server: server.value(),
+                name: self.name,
+                description: self.description,
+                icon: None,
+                last_message_id: self.last_message_id,
+                default_permissions: self.default_permissions,
+                role_permissions: self.role_permissions,
+                nsfw: self.nsfw,
+            },
+            ChannelKind::Voice { server } => Channel::VoiceChannel {
+                id: self.id,
+                server,
+                name: self.name,
+                description: self.description,
+                icon: None,
+                default_permissions: self.default_permissions,
+                role_permissions: self.role_permissions,
+                nsfw: self.nsfw,
+            },
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use crate::{
+    channel::Channel,
+    event::{
+        BulkEvent, ChannelDeleteEvent, ChannelStartTypingEvent, ChannelStopTypingEvent,
+        MessageDeleteEvent, ReadyEvent, ServerCreateEvent, ServerDeleteEvent, ServerEvent,
+        ServerMemberJoinEvent, ServerMemberLeaveEvent,
+    },
+    id::{
+        marker::{ChannelMarker, ServerMarker, UserMarker},
+        Id,
+    },
+    member::Member,
+    message::Message,
+    server::Server,
+    user::User,
+};
+
+use super::{next_id, ChannelBuilder, MemberBuilder, MessageBuilder, ServerBuilder, UserBuilder};
+
+/// Builder of a sequence of [`ServerEvent`]s.
+#[derive(Debug, Clone, Default)]
+#[must_use = "builders have no effect if unused"]
+pub struct EventSequence(Vec<ServerEvent>);
+
+impl EventSequence {
+    /// Create an empty sequence.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an event.
+    pub fn event(mut self, event: ServerEvent) -> Self {
+        self.0.push(event);
+        self
+    }
+
+    /// Add events.
+    pub fn extend(mut self, events: impl IntoIterator<Item = ServerEvent>) -> Self {
+        self.0.extend(events);
+        self
+    }
+
+    /// Add a ready event.
+    pub fn ready(
+        self,
+        users: Vec<User>,
+        servers: Vec<Server>,
+        channels: Vec<Channel>,
+        members: Vec<Member>,
+    ) -> Self {
+        self.event(ServerEvent::Ready(ReadyEvent {
+            users,
+            servers,
+            channels,
+            members,
+            emojis: None,
+        }))
+    }
+
+    /// Add the creation of a server and its channels.
+    pub fn server_create(self, server: Server, channels: Vec<Channel>) -> Self {
+        self.event(ServerEvent::ServerCreate(ServerCreateEvent {
+            id: server.id.clone(),
+            server,
+            channels,
+        }))
+    }
+
+    /// Add the deletion of a server.
+    pub fn server_delete(self, id: Id<ServerMarker>) -> Self {
+        self.event(ServerEvent::ServerDelete(ServerDeleteEvent { id }))
+    }
+
+    /// Add the creation of a channel.
+    pub fn channel_create(self, channel: Channel) -> Self {
+        self.event(ServerEvent::ChannelCreate(channel))
+    }
+
+    /// Add the deletion of a channel.
+    pub fn channel_delete(self, id: Id<ChannelMarker>) -> Self {
+        self.event(ServerEvent::ChannelDelete(ChannelDeleteEvent { id }))
+    }
+
+    /// Add a user joining a server.
+    ///
+    /// The gateway only sends the IDs of the member, so only those of the
+    /// given member are used.
+    pub fn member_join(self, member: Member) -> Self {
+        self.event(ServerEvent::ServerMemberJoin(ServerMemberJoinEvent {
+            id: member.id.server,
+            user: member.id.user,
+        }))
+    }
+
+    /// Add a user leaving a server.
+    pub fn member_leave(self, server: Id<ServerMarker>, user: Id<UserMarker>) -> Self {
+        self.event(ServerEvent::ServerMemberLeave(ServerMemberLeaveEvent {
+            id: server,
+            user,
+        }))
+    }
+
+    /// Add a new message.
+    pub fn message(self, message: Message) -> Self {
+        self.event(ServerEvent::Message(message))
+    }
+
+    /// Add a conversation of `count` messages in a channel, whose authors
+    /// take turns in the given order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `authors` is empty and `count` is not zero.
+    pub fn conversation(
+        self,
+        channel: &Id<ChannelMarker>,
+        authors: &[Id<UserMarker>],
+        count: usize,
+    ) -> Self {
+        let messages = (0..count).map(|i| {
+            let author = authors[i % authors.len()].clone();
+            let message = MessageBuilder::new(channel.clone(), author)
+                .content(format!("Message {i}"))
+                .build();
+
+            ServerEvent::Message(message)
+        });
+
+        self.extend(messages)
+    }
+
+    /// Add the deletion of a message.
+    pub fn message_delete(self, message: &Message) -> Self {
+        self.event(ServerEvent::MessageDelete(MessageDeleteEvent {
+            id: message.id.clone(),
+            channel: message.channel.clone(),
+        }))
+    }
+
+    /// Add a user starting and stopping typing in a channel.
+    pub fn typing(self, channel: Id<ChannelMarker>, user: Id<UserMarker>) -> Self {
+        self.event(ServerEvent::ChannelStartTyping(ChannelStartTypingEvent {
+            id: channel.clone(),
+            user: user.clone(),
+        }))
+        .event(ServerEvent::ChannelStopTyping(ChannelStopTypingEvent {
+            id: channel,
+            user,
+        }))
+    }
+
+    /// Build the sequence.
+    pub fn build(self) -> Vec<ServerEvent> {
+        self.0
+    }
+
+    /// Build the sequence as a single bulk event.
+    pub fn bulk(self) -> ServerEvent {
+        ServerEvent::Bulk(BulkEvent { v: self.0 })
+    }
+}
+
+impl IntoIterator for EventSequence {
+    type Item = ServerEvent;
+    type IntoIter = std::vec::IntoIter<ServerEvent>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// Server populated with users, members and channels.
+#[derive(Debug, Clone)]
+pub struct ServerFixture {
+    /// The server
+    pub server: Server,
+    /// A text and a voice channel of the server
+    pub channels: Vec<Channel>,
+    /// Users of the members, the owner being the first one
+    pub users: Vec<User>,
+    /// Members of the server, in the order of their users
+    pub members: Vec<Member>,
+}
+
+impl ServerFixture {
+    /// Create a server with an owner and `members` other members.
+    pub fn new(members: usize) -> Self {
+        let users: Vec<_> = (0..=members)
+            .map(|i| UserBuilder::new(format!("user{i}")).build())
+            .collect();
+
+        let text_id = next_id();
+        let voice_id = next_id();
+        let server = ServerBuilder::new(users[0].id.clone())
+            .channel(text_id.clone())
+            .channel(voice_id.clone())
+            .build();
+
+        let channels = vec![
+            ChannelBuilder::text(server.id.clone()).id(text_id).build(),
+            ChannelBuilder::voice(server.id.clone())
+                .id(voice_id)
+                .build(),
+        ];
+        let members = users
+            .iter()
+            .map(|user| MemberBuilder::new(server.id.clone(), user.id.clone()).build())
+            .collect();
+
+        Self {
+            server,
+            channels,
+            users,
+            members,
+        }
+    }
+
+    /// Events of the current user receiving the server on connection.
+    pub fn ready(&self) -> EventSequence {
+        EventSequence::new().ready(
+            self.users.clone(),
+            vec![self.server.clone()],
+            self.channels.clone(),
+            self.members.clone(),
+        )
+    }
+
+    /// Events of the server being created and its members joining it.
+    pub fn create(&self) -> EventSequence {
+        let sequence =
+            EventSequence::new().server_create(self.server.clone(), self.channels.clone());
+
+        self.members
+            .iter()
+            .skip(1)
+            .cloned()
+            .fold(sequence, EventSequence::member_join)
+    }
+}
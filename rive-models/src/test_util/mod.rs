@@ -0,0 +1,108 @@
+//! Builders and fixtures for testing code built on the models.
+//!
+//! Models have many fields and no constructors, which makes building them
+//! by hand verbose. The builders in this module fill every field with a
+//! valid default, so tests only mention what they care about. IDs are
+//! generated with [`next_id`] unless set explicitly.
+//!
+//! [`EventSequence`] and [`ServerFixture`] produce the events a gateway
+//! would send, for feeding caches, standbys and event handlers.
+//!
+//! ```
+//! use rive_models::{
+//!     event::ServerEvent,
+//!     test_util::{next_id, ChannelBuilder, EventSequence, MessageBuilder, ServerBuilder},
+//! };
+//!
+//! let owner = next_id();
+//! let server = ServerBuilder::new(owner.clone()).name("Rustaceans").build();
+//! let channel_id = next_id();
+//! let channel = ChannelBuilder::text(server.id.clone())
+//!     .id(channel_id.clone())
+//!     .build();
+//! let message = MessageBuilder::new(channel_id, owner)
+//!     .content("hi")
+//!     .build();
+//!
+//! let events = EventSequence::new()
+//!     .server_create(server, vec![channel])
+//!     .message(message)
+//!     .build();
+//! assert!(matches!(events[1], ServerEvent::Message(_)));
+//! ```
+
+mod builder;
+mod events;
+
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use iso8601_timestamp::{Duration, Timestamp};
+
+use crate::id::Id;
+
+pub use self::{
+    builder::{ChannelBuilder, MemberBuilder, MessageBuilder, ServerBuilder, UserBuilder},
+    events::{EventSequence, ServerFixture},
+};
+
+/// Time of the first generated ID, 2023-01-01T00:00:00Z.
+const EPOCH: i64 = 1_672_531_200_000;
+
+/// Offset of the next generated ID from [`EPOCH`], in milliseconds.
+static NEXT: AtomicI64 = AtomicI64::new(0);
+
+/// Time used for fields such as the join time of members, 2023-01-01T00:00:00Z.
+pub fn timestamp() -> Timestamp {
+    Timestamp::UNIX_EPOCH + Duration::milliseconds(EPOCH)
+}
+
+/// Generate a new ULID.
+///
+/// Each generated ID is created one millisecond after the previous one,
+/// starting at 2023-01-01T00:00:00Z, so IDs are unique and sort in the
+/// order they were generated.
+pub fn next_id<T>() -> Id<T> {
+    let offset = NEXT.fetch_add(1, Ordering::Relaxed);
+
+    Id::lower_bound(timestamp() + Duration::milliseconds(offset))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{next_id, EventSequence, MemberBuilder, ServerFixture};
+    use crate::{
+        event::ServerEvent,
+        id::{marker::UserMarker, Id},
+        member::Member,
+    };
+
+    #[test]
+    fn fixture() {
+        let first = next_id::<UserMarker>();
+        let second = next_id::<UserMarker>();
+        assert!(first.is_ulid() && first < second);
+
+        let fixture = ServerFixture::new(3);
+        assert_eq!(fixture.users.len(), 4);
+        assert_eq!(fixture.members.len(), 4);
+        assert_eq!(fixture.server.owner, fixture.users[0].id);
+
+        let member = MemberBuilder::new(fixture.server.id.clone(), Id::new("user".to_string()))
+            .nickname("ferris")
+            .build();
+        let value = serde_json::to_value(&member).unwrap();
+        assert_eq!(value["nickname"], json!("ferris"));
+        assert!(serde_json::from_value::<Member>(value).is_ok());
+
+        let events = EventSequence::new()
+            .extend(fixture.ready())
+            .member_join(member)
+            .build();
+        assert!(matches!(
+            events.as_slice(),
+            [ServerEvent::Ready(ready), ServerEvent::ServerMemberJoin(_)] if ready.members.len() == 4
+        ));
+    }
+}
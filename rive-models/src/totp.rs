@@ -0,0 +1,204 @@
+//! [RFC 6238](https://datatracker.ietf.org/doc/html/rfc6238) time-based
+//! one-time password generator.
+//!
+//! It allows accounts with TOTP multi-factor authentication to log in
+//! unattended, given the secret returned by the API when enabling it.
+
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use ring::hmac;
+
+use crate::mfa::{MFAData, TOTPSecret};
+
+/// Default time step in seconds.
+pub const DEFAULT_STEP: u64 = 30;
+
+/// Default amount of digits of a code.
+pub const DEFAULT_DIGITS: u32 = 6;
+
+/// Secret could not be decoded from base32.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidSecret;
+
+impl Display for InvalidSecret {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("TOTP secret is not valid base32")
+    }
+}
+
+impl Error for InvalidSecret {}
+
+/// TOTP code generator using HMAC-SHA1.
+#[derive(Clone)]
+pub struct Totp {
+    key: hmac::Key,
+    step: u64,
+    digits: u32,
+}
+
+impl fmt::Debug for Totp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Totp")
+            .field("step", &self.step)
+            .field("digits", &self.digits)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Totp {
+    /// Create a generator from a base32 encoded secret, with
+    /// [`DEFAULT_STEP`] and [`DEFAULT_DIGITS`].
+    ///
+    /// Padding, whitespace and letter case are ignored.
+    pub fn new(secret: &str) -> Result<Self, InvalidSecret> {
+        decode_base32(secret).map(|secret| Self::from_bytes(&secret))
+    }
+
+    /// Create a generator from a raw secret, with [`DEFAULT_STEP`] and
+    /// [`DEFAULT_DIGITS`].
+    pub fn from_bytes(secret: &[u8]) -> Self {
+        Self {
+            key: hmac::Key::new(hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY, secret),
+            step: DEFAULT_STEP,
+            digits: DEFAULT_DIGITS,
+        }
+    }
+
+    /// Set the time step in seconds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the step is zero.
+    pub const fn step(mut self, step: u64) -> Self {
+        assert!(step > 0, "TOTP step must not be zero");
+        self.step = step;
+        self
+    }
+
+    /// Set the amount of digits of a code.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the amount of digits is not between 1 and 9.
+    pub const fn digits(mut self, digits: u32) -> Self {
+        assert!(
+            digits >= 1 && digits <= 9,
+            "TOTP digits must be between 1 and 9"
+        );
+        self.digits = digits;
+        self
+    }
+
+    /// Generate the code for the given UNIX time in seconds.
+    pub fn generate(&self, time: u64) -> String {
+        let counter = time / self.step;
+        let tag = hmac::sign(&self.key, &counter.to_be_bytes());
+        let hash = tag.as_ref();
+
+        let offset = usize::from(hash[hash.len() - 1] & 0x0f);
+        let binary = u32::from_be_bytes([
+            hash[offset] & 0x7f,
+            hash[offset + 1],
+            hash[offset + 2],
+            hash[offset + 3],
+        ]);
+
+        format!(
+            "{:0width$}",
+            binary % 10u32.pow(self.digits),
+            width = self.digits as usize
+        )
+    }
+
+    /// Generate the code for the current time.
+    pub fn now(&self) -> String {
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system clock may have gone backwards")
+            .as_secs();
+
+        self.generate(time)
+    }
+}
+
+impl TOTPSecret {
+    /// Create a [`Totp`] generator from this secret.
+    pub fn totp(&self) -> Result<Totp, InvalidSecret> {
+        Totp::new(&self.secret)
+    }
+}
+
+impl MFAData {
+    /// Create a TOTP MFA response with the current code of the generator.
+    pub fn totp(totp: &Totp) -> Self {
+        Self::Totp {
+            totp_code: totp.now(),
+        }
+    }
+}
+
+/// Decode RFC 4648 base32.
+fn decode_base32(value: &str) -> Result<Vec<u8>, InvalidSecret> {
+    let mut bytes = Vec::with_capacity(value.len() * 5 / 8);
+    let mut buffer = 0u64;
+    let mut bits = 0;
+
+    for character in value.chars() {
+        let digit = match character.to_ascii_uppercase() {
+            character @ 'A'..='Z' => character as u64 - 'A' as u64,
+            character @ '2'..='7' => character as u64 - '2' as u64 + 26,
+            '=' | ' ' | '-' => continue,
+            _ => return Err(InvalidSecret),
+        };
+
+        buffer = (buffer << 5) | digit;
+        bits += 5;
+
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_base32, InvalidSecret, Totp};
+
+    #[test]
+    fn rfc_6238_vectors() {
+        let totp = Totp::from_bytes(b"12345678901234567890").digits(8);
+
+        for (time, code) in [
+            (59, "94287082"),
+            (1_111_111_109, "07081804"),
+            (1_111_111_111, "14050471"),
+            (1_234_567_890, "89005924"),
+            (2_000_000_000, "69279037"),
+            (20_000_000_000, "65353130"),
+        ] {
+            assert_eq!(totp.generate(time), code);
+        }
+    }
+
+    #[test]
+    fn base32_secret() {
+        assert_eq!(
+            decode_base32("GEZDGNBVGY3TQOJQgezdgnbvgy3tqojq").unwrap(),
+            b"12345678901234567890"
+        );
+        assert_eq!(decode_base32("MZXW6==="), Ok(b"foo".to_vec()));
+        assert_eq!(decode_base32("MZXW1"), Err(InvalidSecret));
+
+        let totp = Totp::new("GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ").unwrap();
+        assert_eq!(totp.generate(59), "287082");
+        assert_eq!(totp.step(60).generate(59), "755224");
+    }
+}
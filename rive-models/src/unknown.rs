@@ -0,0 +1,131 @@
+//! Variants of tagged enums that are unknown to this crate.
+
+use serde::{de::Error as _, Deserialize, Deserializer};
+use serde_json::Value;
+
+/// Variant of a tagged enum that is unknown to this crate.
+///
+/// It keeps the whole payload as it was received, so new variants can be
+/// handled before the crate supports them and are serialized back unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownVariant {
+    /// Tag of the variant
+    pub tag: String,
+    /// Payload, including the tag
+    pub payload: Value,
+}
+
+/// Deserialize an enum tagged with `field`, falling back to an unknown
+/// variant if `known` does not know its tag.
+///
+/// The payload is buffered into a [`Value`] and its tag is read before it is
+/// parsed, so that malformed payloads of known variants are still errors.
+pub(crate) fn deserialize_or_unknown<'de, D, T>(
+    deserializer: D,
+    field: &'static str,
+    known: impl FnOnce(&str, &Value) -> Result<Option<T>, serde_json::Error>,
+    unknown: impl FnOnce(UnknownVariant) -> T,
+) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let payload = Value::deserialize(deserializer)?;
+    let tag = match payload.get(field) {
+        Some(Value::String(tag)) => tag.clone(),
+        Some(_) => return Err(D::Error::custom(format_args!("`{field}` is not a string"))),
+        None => return Err(D::Error::missing_field(field)),
+    };
+
+    if let Some(known) = known(&tag, &payload).map_err(D::Error::custom)? {
+        return Ok(known);
+    }
+
+    Ok(unknown(UnknownVariant { tag, payload }))
+}
+
+/// Implement `Serialize` and `Deserialize` for an enum tagged with `$field`,
+/// which derives them with `remote = "Self"` and has a skipped
+/// `Unknown(UnknownVariant)` variant for tags missing from `$tags`.
+macro_rules! impl_unknown_variant {
+    ($enum:ident, $field:literal, $tags:expr) => {
+        impl serde::Serialize for $enum {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                match self {
+                    Self::Unknown(unknown) => {
+                        serde::Serialize::serialize(&unknown.payload, serializer)
+                    }
+                    _ => $enum::serialize(self, serializer),
+                }
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $enum {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                $crate::unknown::deserialize_or_unknown(
+                    deserializer,
+                    $field,
+                    |tag, payload| {
+                        if !$tags.contains(&tag) {
+                            return Ok(None);
+                        }
+
+                        $enum::deserialize(payload).map(Some)
+                    },
+                    Self::Unknown,
+                )
+            }
+        }
+    };
+}
+
+pub(crate) use impl_unknown_variant;
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+    use super::UnknownVariant;
+    use crate::emoji::EmojiParent;
+
+    #[test]
+    fn tokens() {
+        assert_tokens(
+            &EmojiParent::Detached,
+            &[
+                Token::Struct {
+                    name: "EmojiParent",
+                    len: 1,
+                },
+                Token::Str("type"),
+                Token::Str("Detached"),
+                Token::StructEnd,
+            ],
+        );
+
+        assert_tokens(
+            &EmojiParent::Unknown(UnknownVariant {
+                tag: "Webhook".to_string(),
+                payload: json!({ "type": "Webhook", "id": "01FFD06NDVZ14W5T1WKKB4KKZX" }),
+            }),
+            &[
+                Token::Map { len: Some(2) },
+                Token::Str("id"),
+                Token::Str("01FFD06NDVZ14W5T1WKKB4KKZX"),
+                Token::Str("type"),
+                Token::Str("Webhook"),
+                Token::MapEnd,
+            ],
+        );
+
+        assert_de_tokens_error::<EmojiParent>(
+            &[
+                Token::Map { len: Some(1) },
+                Token::Str("id"),
+                Token::Str("01FFD06NDVZ14W5T1WKKB4KKZX"),
+                Token::MapEnd,
+            ],
+            "missing field `type`",
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use serde::{Deserialize, Deserializer, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::{
     attachment::Attachment,
@@ -11,7 +11,7 @@ use crate::{
 };
 
 /// User's relationship with another user (or themselves)
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub enum RelationshipStatus {
     None,
     User,
@@ -23,7 +23,7 @@ pub enum RelationshipStatus {
 }
 
 /// Relationship entry indicating current status with other user
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Relationship {
     #[serde(rename = "_id")]
     pub id: Id<UserMarker>,
@@ -31,7 +31,7 @@ pub struct Relationship {
 }
 
 /// Mutual servers and friends
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Mutuals {
     /// Array of mutual user IDs that both users are friends with
     pub users: Vec<Id<UserMarker>>,
@@ -66,11 +66,13 @@ pub struct UserStatus {
 }
 
 /// User's profile
-#[derive(Deserialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct UserProfile {
     /// Text content on user's profile
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub content: Option<String>,
     /// Background visible on user's profile
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub background: Option<Attachment>,
 }
 
@@ -81,7 +83,7 @@ pub struct UserProfile {
 pub struct PartialUserProfile {
     /// Text to set as user profile description
     #[serde(skip_serializing_if = "Option::is_none")]
-    content: Option<String>,
+    pub(crate) content: Option<String>,
     /// Attachment Id for background
     #[serde(skip_serializing_if = "Option::is_none")]
     background: Option<Id<AttachmentMarker>>,
@@ -116,6 +118,12 @@ bitflags::bitflags! {
     }
 }
 
+impl Serialize for Badges {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.bits())
+    }
+}
+
 impl<'de> Deserialize<'de> for Badges {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         Ok(Self::from_bits_truncate(u64::deserialize(deserializer)?))
@@ -137,6 +145,12 @@ bitflags::bitflags! {
     }
 }
 
+impl Serialize for UserFlags {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.bits())
+    }
+}
+
 impl<'de> Deserialize<'de> for UserFlags {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         Ok(Self::from_bits_truncate(u64::deserialize(deserializer)?))
@@ -144,14 +158,14 @@ impl<'de> Deserialize<'de> for UserFlags {
 }
 
 /// Bot information for if the user is a bot
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct BotInformation {
     /// Id of the owner of this bot
     pub owner: Id<UserMarker>,
 }
 
 /// Representiation of a User on Revolt.
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct User {
     /// Unique Id
     #[serde(rename = "_id")]
@@ -161,67 +175,90 @@ pub struct User {
     /// User discriminator (four numbers after the username)
     pub discriminator: String,
     /// User's display name
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub display_name: Option<String>,
     /// Avatar attachment
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub avatar: Option<Attachment>,
     /// Relationships with other users
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub relations: Option<Vec<Relationship>>,
 
     /// Bitfield of user badges
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub badges: Option<i32>,
     /// User's current status
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<UserStatus>,
     /// User's profile page
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub profile: Option<UserProfile>,
 
     /// Enum of user flags
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub flags: Option<UserFlags>,
     /// Whether this user is privileged
     #[serde(default)]
     pub privileged: bool,
     /// Bot information
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub bot: Option<BotInformation>,
 
     /// Current session user's relationship with this user
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub relationship: Option<RelationshipStatus>,
     /// Whether this user is currently online
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub online: Option<bool>,
 }
 
 /// Partial representiation of a User on Revolt.
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PartialUser {
     /// Unique Id
-    #[serde(rename = "_id")]
+    #[serde(skip_serializing_if = "Option::is_none", rename = "_id")]
     pub id: Option<Id<UserMarker>>,
     /// Username
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub username: Option<String>,
     /// User discriminator
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub discriminator: Option<String>,
     /// Display name
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub display_name: Option<String>,
     /// Avatar attachment
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub avatar: Option<Attachment>,
     /// Relationships with other users
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub relations: Option<Vec<Relationship>>,
 
     /// Bitfield of user badges
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub badges: Option<i32>,
     /// User's current status
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<UserStatus>,
     /// User's profile page
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub profile: Option<UserProfile>,
 
     /// Enum of user flags
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub flags: Option<UserFlags>,
     /// Whether this user is privileged
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub privileged: Option<bool>,
     /// Bot information
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub bot: Option<BotInformation>,
 
     /// Current session user's relationship with this user
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub relationship: Option<RelationshipStatus>,
     /// Whether this user is currently online
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub online: Option<bool>,
 }
 
@@ -0,0 +1,553 @@
+//! Validation of outgoing payloads against the limits of the API.
+//!
+//! Payloads exceeding a limit are rejected by the API once sent. Validating
+//! them beforehand reports the problem without a request and tells which
+//! field is at fault.
+//!
+//! The limits are those of the official instance. Self-hosted instances may
+//! be configured with other attachment limits.
+
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+use crate::{
+    data::{
+        ChangeUsernameData, CreateChannelData, CreateRoleData, CreateServerData, EditBotData,
+        EditChannelData, EditMemberData, EditMessageData, EditRoleData, EditServerData,
+        EditUserData, SendMessageData,
+    },
+    embed::{SendableEmbed, SendableEmbedBorrowed},
+    message::{Masquerade, MasqueradeBorrowed},
+};
+
+/// Maximum length of the content of a message.
+pub const MAX_MESSAGE_LENGTH: usize = 2000;
+
+/// Maximum amount of attachments of a message.
+pub const MAX_ATTACHMENTS: usize = 5;
+
+/// Maximum amount of embeds of a message.
+pub const MAX_EMBEDS: usize = 5;
+
+/// Maximum amount of replies of a message.
+pub const MAX_REPLIES: usize = 5;
+
+/// Maximum amount of reactions suggested by the interactions of a message.
+pub const MAX_INTERACTION_REACTIONS: usize = 20;
+
+/// Minimum length of a username.
+pub const MIN_USERNAME_LENGTH: usize = 2;
+
+/// Maximum length of a username.
+pub const MAX_USERNAME_LENGTH: usize = 32;
+
+/// Maximum length of the name of a channel, server, role or category, and
+/// of nicknames and masquerade names.
+pub const MAX_NAME_LENGTH: usize = 32;
+
+/// Maximum length of the description of a channel or server.
+pub const MAX_DESCRIPTION_LENGTH: usize = 1024;
+
+/// Maximum length of a colour.
+pub const MAX_COLOUR_LENGTH: usize = 128;
+
+/// Payload exceeds a limit of the API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ValidationError {
+    /// Message has no content, attachments or embeds
+    EmptyMessage,
+    /// Text is shorter or longer than allowed
+    Length {
+        /// Name of the field
+        field: &'static str,
+        /// Minimum length, in characters
+        min: usize,
+        /// Maximum length, in characters
+        max: usize,
+        /// Actual length, in characters
+        length: usize,
+    },
+    /// List has more items than allowed
+    TooMany {
+        /// Name of the field
+        field: &'static str,
+        /// Maximum amount of items
+        max: usize,
+        /// Actual amount of items
+        count: usize,
+    },
+    /// Colour is not a CSS colour accepted by the API
+    InvalidColour {
+        /// Name of the field
+        field: &'static str,
+    },
+    /// Username contains characters other than letters, digits, `_`, `.`
+    /// and `-`
+    InvalidUsername,
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyMessage => f.write_str("message has no content, attachments or embeds"),
+            Self::Length {
+                field,
+                min,
+                max,
+                length,
+            } => write!(
+                f,
+                "{field} is {length} characters long, expected {min} to {max}"
+            ),
+            Self::TooMany { field, max, count } => {
+                write!(f, "{field} has {count} items, expected at most {max}")
+            }
+            Self::InvalidColour { field } => write!(f, "{field} is not a valid colour"),
+            Self::InvalidUsername => f.write_str(
+                "username may only contain letters, digits, underscores, dots and dashes",
+            ),
+        }
+    }
+}
+
+impl Error for ValidationError {}
+
+/// Payload that can be checked against the limits of the API.
+pub trait Validate {
+    /// Check the payload against the limits of the API.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first limit the payload exceeds.
+    fn validate(&self) -> Result<(), ValidationError>;
+}
+
+fn length(field: &'static str, value: &str, min: usize, max: usize) -> Result<(), ValidationError> {
+    let length = value.chars().count();
+    if (min..=max).contains(&length) {
+        Ok(())
+    } else {
+        Err(ValidationError::Length {
+            field,
+            min,
+            max,
+            length,
+        })
+    }
+}
+
+fn optional_length(
+    field: &'static str,
+    value: Option<&str>,
+    min: usize,
+    max: usize,
+) -> Result<(), ValidationError> {
+    value.map_or(Ok(()), |value| length(field, value, min, max))
+}
+
+fn count(field: &'static str, count: usize, max: usize) -> Result<(), ValidationError> {
+    if count <= max {
+        Ok(())
+    } else {
+        Err(ValidationError::TooMany { field, max, count })
+    }
+}
+
+fn colour(field: &'static str, value: Option<&str>) -> Result<(), ValidationError> {
+    let Some(value) = value else {
+        return Ok(());
+    };
+
+    length(field, value, 1, MAX_COLOUR_LENGTH)?;
+    if is_colour(&value.to_ascii_lowercase()) {
+        Ok(())
+    } else {
+        Err(ValidationError::InvalidColour { field })
+    }
+}
+
+fn username(value: &str) -> Result<(), ValidationError> {
+    length("username", value, MIN_USERNAME_LENGTH, MAX_USERNAME_LENGTH)?;
+    if value
+        .chars()
+        .all(|c| c.is_alphabetic() || c.is_ascii_digit() || matches!(c, '_' | '.' | '-'))
+    {
+        Ok(())
+    } else {
+        Err(ValidationError::InvalidUsername)
+    }
+}
+
+/// Whether a lowercase value is a colour accepted by the API: a named
+/// colour, a variable, an RGB(A) function, a hex colour or a gradient of
+/// those.
+fn is_colour(value: &str) -> bool {
+    if is_plain_colour(value) {
+        return true;
+    }
+
+    let value = value.strip_prefix("repeating-").unwrap_or(value);
+    let Some(stops) = ["linear", "conic", "radial"].iter().find_map(|kind| {
+        value
+            .strip_prefix(kind)?
+            .strip_prefix("-gradient(")?
+            .strip_suffix(')')
+    }) else {
+        return false;
+    };
+
+    let stops = split_top_level(stops);
+    stops.len() >= 2
+        && stops.iter().enumerate().all(|(i, stop)| {
+            let stop = stop.trim_start();
+            let colour = match stop.rsplit_once(' ') {
+                Some((colour, position)) if is_stop_position(position) => colour.trim_end(),
+                _ => stop,
+            };
+
+            is_plain_colour(colour) || (i == 0 && is_angle(colour))
+        })
+}
+
+fn is_plain_colour(value: &str) -> bool {
+    let function = |name: &str| {
+        value
+            .strip_prefix(name)
+            .and_then(|value| value.strip_prefix('('))
+            .and_then(|value| value.strip_suffix(')'))
+    };
+
+    if let Some(name) = function("var").and_then(|value| value.strip_prefix("--")) {
+        return !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    }
+
+    if let Some(arguments) = function("rgb").or_else(|| function("rgba")) {
+        return !arguments.is_empty()
+            && arguments
+                .chars()
+                .all(|c| c.is_ascii_digit() || c == ',' || c == ' ');
+    }
+
+    if let Some(hex) = value.strip_prefix('#') {
+        return !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit());
+    }
+
+    !value.is_empty() && value.chars().all(|c| c.is_ascii_lowercase() || c == ' ')
+}
+
+fn is_stop_position(value: &str) -> bool {
+    value == "0"
+        || value.strip_suffix('%').map_or(false, |value| {
+            (1..=3).contains(&value.len()) && value.chars().all(|c| c.is_ascii_digit())
+        })
+}
+
+fn is_angle(value: &str) -> bool {
+    value.strip_suffix("deg").map_or(false, |value| {
+        !value.is_empty() && value.chars().all(|c| c.is_ascii_digit())
+    })
+}
+
+/// Split a value on the commas that are not within parentheses.
+fn split_top_level(value: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+
+    for (i, c) in value.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(&value[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&value[start..]);
+
+    parts
+}
+
+impl Validate for SendableEmbedBorrowed<'_> {
+    fn validate(&self) -> Result<(), ValidationError> {
+        optional_length("embed icon_url", self.icon_url, 1, 128)?;
+        optional_length("embed url", self.url, 1, 256)?;
+        optional_length("embed title", self.title, 1, 100)?;
+        optional_length("embed description", self.description, 1, 2000)?;
+        optional_length("embed media", self.media, 1, 128)?;
+        colour("embed colour", self.colour)
+    }
+}
+
+impl Validate for SendableEmbed {
+    fn validate(&self) -> Result<(), ValidationError> {
+        self.as_borrowed().validate()
+    }
+}
+
+impl Validate for MasqueradeBorrowed<'_> {
+    fn validate(&self) -> Result<(), ValidationError> {
+        optional_length("masquerade name", self.name, 1, MAX_NAME_LENGTH)?;
+        optional_length("masquerade avatar", self.avatar, 1, 256)?;
+        colour("masquerade colour", self.colour)
+    }
+}
+
+impl Validate for Masquerade {
+    fn validate(&self) -> Result<(), ValidationError> {
+        self.as_borrowed().validate()
+    }
+}
+
+impl Validate for SendMessageData<'_> {
+    fn validate(&self) -> Result<(), ValidationError> {
+        let attachments = self.attachments.map_or(0, <[_]>::len);
+        let embeds = self.embeds.unwrap_or_default();
+        if self.content.map_or(true, str::is_empty) && attachments == 0 && embeds.is_empty() {
+            return Err(ValidationError::EmptyMessage);
+        }
+
+        optional_length("content", self.content, 0, MAX_MESSAGE_LENGTH)?;
+        count("attachments", attachments, MAX_ATTACHMENTS)?;
+        count("replies", self.replies.map_or(0, <[_]>::len), MAX_REPLIES)?;
+        count("embeds", embeds.len(), MAX_EMBEDS)?;
+        embeds.iter().try_for_each(Validate::validate)?;
+
+        if let Some(masquerade) = self.masquerade {
+            masquerade.validate()?;
+        }
+
+        if let Some(reactions) = self
+            .interactions
+            .and_then(|interactions| interactions.reactions)
+        {
+            count(
+                "interactions reactions",
+                reactions.len(),
+                MAX_INTERACTION_REACTIONS,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Validate for EditMessageData {
+    fn validate(&self) -> Result<(), ValidationError> {
+        optional_length("content", self.content.as_deref(), 0, MAX_MESSAGE_LENGTH)?;
+
+        if let Some(embeds) = &self.embeds {
+            count("embeds", embeds.len(), MAX_EMBEDS)?;
+            embeds.iter().try_for_each(Validate::validate)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Validate for EditUserData {
+    fn validate(&self) -> Result<(), ValidationError> {
+        if let Some(status) = &self.status {
+            optional_length("status text", status.text.as_deref(), 0, 128)?;
+        }
+
+        if let Some(profile) = &self.profile {
+            optional_length("profile content", profile.content.as_deref(), 0, 2000)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Validate for ChangeUsernameData {
+    fn validate(&self) -> Result<(), ValidationError> {
+        username(&self.username)
+    }
+}
+
+impl Validate for EditBotData {
+    fn validate(&self) -> Result<(), ValidationError> {
+        self.name.as_deref().map_or(Ok(()), username)
+    }
+}
+
+impl Validate for CreateChannelData {
+    fn validate(&self) -> Result<(), ValidationError> {
+        length("name", &self.name, 1, MAX_NAME_LENGTH)?;
+        optional_length(
+            "description",
+            self.description.as_deref(),
+            0,
+            MAX_DESCRIPTION_LENGTH,
+        )
+    }
+}
+
+impl Validate for EditChannelData {
+    fn validate(&self) -> Result<(), ValidationError> {
+        optional_length("name", self.name.as_deref(), 1, MAX_NAME_LENGTH)?;
+        optional_length(
+            "description",
+            self.description.as_deref(),
+            0,
+            MAX_DESCRIPTION_LENGTH,
+        )
+    }
+}
+
+impl Validate for CreateServerData {
+    fn validate(&self) -> Result<(), ValidationError> {
+        length("name", &self.name, 1, MAX_NAME_LENGTH)?;
+        optional_length(
+            "description",
+            self.description.as_deref(),
+            0,
+            MAX_DESCRIPTION_LENGTH,
+        )
+    }
+}
+
+impl Validate for EditServerData {
+    fn validate(&self) -> Result<(), ValidationError> {
+        optional_length("name", self.name.as_deref(), 1, MAX_NAME_LENGTH)?;
+        optional_length(
+            "description",
+            self.description.as_deref(),
+            0,
+            MAX_DESCRIPTION_LENGTH,
+        )?;
+
+        self.categories
+            .iter()
+            .flatten()
+            .try_for_each(|category| length("category title", &category.title, 1, MAX_NAME_LENGTH))
+    }
+}
+
+impl Validate for CreateRoleData {
+    fn validate(&self) -> Result<(), ValidationError> {
+        length("name", &self.name, 1, MAX_NAME_LENGTH)
+    }
+}
+
+impl Validate for EditRoleData {
+    fn validate(&self) -> Result<(), ValidationError> {
+        optional_length("name", self.name.as_deref(), 1, MAX_NAME_LENGTH)?;
+        colour("colour", self.colour.as_deref())
+    }
+}
+
+impl Validate for EditMemberData {
+    fn validate(&self) -> Result<(), ValidationError> {
+        optional_length("nickname", self.nickname.as_deref(), 1, MAX_NAME_LENGTH)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_colour, Validate, ValidationError, MAX_MESSAGE_LENGTH};
+    use crate::{
+        data::{ChangeUsernameData, SendMessageData},
+        embed::SendableEmbedBorrowed,
+        message::MasqueradeBorrowed,
+    };
+
+    #[test]
+    fn colours() {
+        for colour in [
+            "red",
+            "light blue",
+            "#ff00AA",
+            "var(--accent)",
+            "rgba(0, 0, 0, 128)",
+            "linear-gradient(30deg, red, #fff 50%)",
+            "repeating-radial-gradient(rgb(1, 2, 3) 0, blue 100%)",
+        ] {
+            assert!(is_colour(&colour.to_ascii_lowercase()), "{colour}");
+        }
+
+        for colour in ["", "#", "#xyz", "url(evil)", "linear-gradient(red)", "red;"] {
+            assert!(!is_colour(colour), "{colour}");
+        }
+    }
+
+    #[test]
+    fn send_message() {
+        assert_eq!(
+            SendMessageData::default().validate(),
+            Err(ValidationError::EmptyMessage)
+        );
+
+        let content = "a".repeat(MAX_MESSAGE_LENGTH + 1);
+        assert_eq!(
+            SendMessageData {
+                content: Some(&content),
+                ..Default::default()
+            }
+            .validate(),
+            Err(ValidationError::Length {
+                field: "content",
+                min: 0,
+                max: MAX_MESSAGE_LENGTH,
+                length: MAX_MESSAGE_LENGTH + 1,
+            })
+        );
+
+        let embeds = vec![
+            SendableEmbedBorrowed {
+                title: Some("Title"),
+                ..Default::default()
+            };
+            6
+        ];
+        assert_eq!(
+            SendMessageData {
+                embeds: Some(&embeds),
+                ..Default::default()
+            }
+            .validate(),
+            Err(ValidationError::TooMany {
+                field: "embeds",
+                max: 5,
+                count: 6,
+            })
+        );
+
+        let masquerade = MasqueradeBorrowed {
+            name: None,
+            avatar: None,
+            colour: Some("url(https://example.com)"),
+        };
+        assert_eq!(
+            SendMessageData {
+                content: Some("hello"),
+                masquerade: Some(&masquerade),
+                ..Default::default()
+            }
+            .validate(),
+            Err(ValidationError::InvalidColour {
+                field: "masquerade colour"
+            })
+        );
+        assert!(SendMessageData {
+            embeds: Some(&embeds[..1]),
+            ..Default::default()
+        }
+        .validate()
+        .is_ok());
+
+        let data = ChangeUsernameData {
+            username: "ferris the crab".to_string(),
+            password: String::new(),
+        };
+        assert_eq!(data.validate(), Err(ValidationError::InvalidUsername));
+    }
+}
@@ -9,7 +9,7 @@ use crate::{
 };
 
 /// Webhook information
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Webhook {
     /// Webhook ID
     pub id: Id<WebhookMarker>,
@@ -18,28 +18,34 @@ pub struct Webhook {
     pub name: String,
 
     /// The avatar of the webhook
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub avatar: Option<Attachment>,
 
     /// The channel this webhook belongs to
     pub channel_id: Id<ChannelMarker>,
 
     /// The private token for the webhook
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub token: Option<String>,
 }
 
 /// Partial webhook data
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PartialWebhook {
     /// The name of the webhook
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
 
     /// The avatar of the webhook
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub avatar: Option<Attachment>,
 
     /// The channel this webhook belongs to
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub channel_id: Option<Id<ChannelMarker>>,
 
     /// The private token for the webhook
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub token: Option<String>,
 }
 
@@ -3,8 +3,16 @@
 mod event;
 pub use event::StandbyEvent;
 
-use rive_models::event::ServerEvent;
-use tokio::sync::broadcast::{channel, error::RecvError, Sender};
+use std::future::Future;
+
+use rive_models::{event::ServerEvent, message::Message};
+use tokio::sync::broadcast::{channel, error::RecvError, Receiver, Sender};
+
+/// Amount of events kept for each waiter until it is polled.
+///
+/// A waiter registered before sending a request may not be polled until the
+/// response arrives, while the gateway keeps delivering events.
+const CAPACITY: usize = 64;
 
 /// A struct used by the main event loop to process incoming events and by tasks
 /// to wait for specific events.
@@ -22,7 +30,7 @@ pub struct Standby {
 impl Standby {
     /// Create a new [`Standby`].
     pub fn new() -> Self {
-        let (tx, _) = channel(1);
+        let (tx, _) = channel(CAPACITY);
         Self { tx }
     }
 
@@ -45,18 +53,85 @@ impl Standby {
         let mut rx = self.tx.subscribe();
 
         loop {
-            match rx.recv().await {
-                Ok(event) => match T::from_server_event(event) {
-                    Some(event) if predictate(&event) => return event,
-                    _ => continue,
-                },
-                Err(RecvError::Lagged(_)) => continue,
-                Err(RecvError::Closed) => unreachable!("channel sender must not close"),
-            };
+            // events dropped while lagging behind are skipped
+            if let Ok(event) = next_match(&mut rx, &predictate).await {
+                return event;
+            }
+        }
+    }
+
+    /// Wait for the [`Message`] carrying the given nonce.
+    ///
+    /// Messages sent through the HTTP client carry the idempotency key of the
+    /// send as their nonce, so this confirms the delivery of a send. The
+    /// gateway usually echoes the message before the HTTP response arrives,
+    /// so the waiter is registered when this method is called, not when the
+    /// returned future is first polled: call it before sending.
+    ///
+    /// Resolves to [`None`] if the waiter was not polled fast enough and
+    /// events were dropped, in which case the message may have been missed.
+    ///
+    /// ```no_run
+    /// # async fn example(
+    /// #     http: rive_http::Client,
+    /// #     standby: rive_standby::Standby,
+    /// #     channel_id: rive_models::id::Id<rive_models::id::marker::ChannelMarker>,
+    /// # ) -> Result<(), rive_http::Error> {
+    /// use rive_models::data::SendMessageData;
+    ///
+    /// let nonce = rive_http::generate_nonce();
+    /// let delivered = standby.wait_for_nonce(&nonce);
+    /// let data = SendMessageData {
+    ///     content: Some("hello"),
+    ///     ..Default::default()
+    /// };
+    /// http.send_message_with_nonce(&channel_id, &data, &nonce)
+    ///     .await?;
+    /// if let Some(message) = delivered.await {
+    ///     println!("delivered as {}", message.id.value_ref());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn wait_for_nonce(
+        &self,
+        nonce: &str,
+    ) -> impl Future<Output = Option<Message>> + Send + 'static {
+        let nonce = nonce.to_string();
+        let mut rx = self.tx.subscribe();
+
+        async move {
+            next_match(&mut rx, &|message: &Message| {
+                message.nonce.as_deref() == Some(nonce.as_str())
+            })
+            .await
+            .ok()
         }
     }
 }
 
+/// Wait for an event matching the predicate on a subscribed receiver.
+///
+/// Returns the amount of dropped events if the receiver lagged behind.
+async fn next_match<T>(
+    rx: &mut Receiver<ServerEvent>,
+    predictate: &(impl Fn(&T) -> bool + Send),
+) -> Result<T, u64>
+where
+    T: StandbyEvent,
+{
+    loop {
+        match rx.recv().await {
+            Ok(event) => match T::from_server_event(event) {
+                Some(event) if predictate(&event) => return Ok(event),
+                _ => continue,
+            },
+            Err(RecvError::Lagged(skipped)) => return Err(skipped),
+            Err(RecvError::Closed) => unreachable!("channel sender must not close"),
+        };
+    }
+}
+
 impl Default for Standby {
     fn default() -> Self {
         Self::new()
@@ -67,10 +142,16 @@ impl Default for Standby {
 mod tests {
     use std::{sync::Arc, time::Duration};
 
-    use rive_models::event::{Ping, PongEvent, ServerEvent};
-    use tokio::{task::JoinSet, time::sleep};
+    use rive_models::{
+        event::{Ping, PongEvent, ServerEvent},
+        test_util::{next_id, MessageBuilder},
+    };
+    use tokio::{
+        task::JoinSet,
+        time::{sleep, timeout},
+    };
 
-    use crate::Standby;
+    use crate::{Standby, CAPACITY};
 
     /// Create 10 tasks waiting for an event produced on the main thread.
     #[tokio::test]
@@ -106,4 +187,47 @@ mod tests {
             res.unwrap();
         }
     }
+
+    /// Test that a nonce waiter receives a message processed before it is
+    /// first polled.
+    #[tokio::test]
+    async fn wait_for_nonce() {
+        let standby = Standby::new();
+        let delivered = standby.wait_for_nonce("nonce");
+
+        let (channel, author) = (next_id(), next_id());
+        for nonce in ["other", "nonce"] {
+            let message = MessageBuilder::new(channel.clone(), author.clone())
+                .nonce(nonce)
+                .build();
+            standby.process(ServerEvent::Message(message));
+        }
+
+        let message = timeout(Duration::from_secs(1), delivered)
+            .await
+            .expect("message was missed")
+            .unwrap();
+        assert_eq!(message.nonce.as_deref(), Some("nonce"));
+    }
+
+    /// Test that a nonce waiter lagging behind gives up instead of waiting
+    /// for a message it may have missed.
+    #[tokio::test]
+    async fn wait_for_nonce_lagged() {
+        let standby = Standby::new();
+        let delivered = standby.wait_for_nonce("nonce");
+
+        let (channel, author) = (next_id(), next_id());
+        for _ in 0..=CAPACITY {
+            let message = MessageBuilder::new(channel.clone(), author.clone())
+                .nonce("other")
+                .build();
+            standby.process(ServerEvent::Message(message));
+        }
+
+        let message = timeout(Duration::from_secs(1), delivered)
+            .await
+            .expect("waiter did not give up");
+        assert!(message.is_none());
+    }
 }
@@ -0,0 +1,70 @@
+//! Errors returned by [`Rive`] constructors and accessors.
+//!
+//! [`Rive`]: crate::Rive
+
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// Type of [`InstanceError`] that occurred.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum InstanceErrorKind {
+    /// Querying the instance configuration from the root node failed.
+    QueryNode,
+    /// File server (Autumn) is disabled on the instance.
+    AutumnDisabled,
+}
+
+/// Setting up or accessing clients of an instance failed.
+#[derive(Debug)]
+pub struct InstanceError {
+    /// Type of error.
+    pub(crate) kind: InstanceErrorKind,
+    /// Source error, if any.
+    pub(crate) source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl InstanceError {
+    /// Create new [`InstanceError`] with given error type and source.
+    pub(crate) fn new(
+        kind: InstanceErrorKind,
+        source: Option<Box<dyn Error + Send + Sync>>,
+    ) -> Self {
+        Self { kind, source }
+    }
+
+    /// An immutable reference to the type of error that occurred.
+    #[must_use]
+    pub const fn kind(&self) -> &InstanceErrorKind {
+        &self.kind
+    }
+
+    /// Consume the error, returning the owned error type and the source error.
+    #[must_use]
+    pub fn into_parts(self) -> (InstanceErrorKind, Option<Box<dyn Error + Send + Sync>>) {
+        (self.kind, self.source)
+    }
+}
+
+impl Display for InstanceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            InstanceErrorKind::QueryNode => {
+                f.write_str("failed to query the instance configuration")
+            }
+            InstanceErrorKind::AutumnDisabled => {
+                f.write_str("file server (autumn) is disabled on the instance")
+            }
+        }
+    }
+}
+
+impl Error for InstanceError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| &**source as &(dyn Error + 'static))
+    }
+}
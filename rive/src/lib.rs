@@ -1,10 +1,17 @@
 #![doc = include_str!("../README.md")]
 
+pub mod error;
+pub mod resolve;
+
 use std::sync::Arc;
 
+use error::{InstanceError, InstanceErrorKind};
+use resolve::BatchResolver;
+
 use rive_cache_inmemory::InMemoryCache;
 use rive_gateway::Gateway;
 use rive_models::{authentication::Authentication, event::ServerEvent};
+use tracing::warn;
 
 /// Revolt entities
 ///
@@ -53,6 +60,7 @@ pub mod cache_inmemory {
 /// | [`rive_cache_inmemory::Config`] | [`InMemoryCacheConfig`] |
 /// | [`rive_gateway::BASE_URL`]      | [`GATEWAY_BASE_URL`]    |
 /// | [`rive_http::BASE_URL`]         | [`HTTP_BASE_URL`]       |
+/// | [`rive_http::Config`]           | [`HttpConfig`]          |
 /// | [`rive_http::Error`]            | [`HttpError`]           |
 ///
 /// [`AUTUMN_BASE_URL`]: crate::prelude::AUTUMN_BASE_URL
@@ -61,13 +69,14 @@ pub mod cache_inmemory {
 /// [`InMemoryCacheConfig`]: crate::prelude::InMemoryCacheConfig
 /// [`GATEWAY_BASE_URL`]: crate::prelude::GATEWAY_BASE_URL
 /// [`HTTP_BASE_URL`]: crate::prelude::HTTP_BASE_URL
+/// [`HttpConfig`]: crate::prelude::HttpConfig
 /// [`HttpError`]: crate::prelude::HttpError
 pub mod prelude {
     pub use rive_models::{
         account::*, attachment::*, authentication::*, bot::*, channel::*, core::*, data::*,
         embed::*, emoji::*, error::*, event::*, invite::*, member::*, message::*, mfa::*,
-        onboarding::*, permission::*, report::*, server::*, session::*, snapshot::*, stats::*,
-        strike::*, user::*, voice::*, webhook::*,
+        moderation::*, onboarding::*, permission::*, report::*, server::*, session::*, snapshot::*,
+        stats::*, strike::*, user::*, voice::*, webhook::*,
     };
 
     pub use rive_autumn::{
@@ -78,9 +87,17 @@ pub mod prelude {
         InMemoryCacheStats, IterReference, Reference, ResourceIter,
     };
     pub use rive_gateway::{error::*, Config, Gateway, BASE_URL as GATEWAY_BASE_URL};
-    pub use rive_http::{Client, Error as HttpError, BASE_URL as HTTP_BASE_URL};
+    pub use rive_http::{
+        Client, Config as HttpConfig, DeliveryStatus, Error as HttpError, FileSessionStore,
+        InMemorySessionStore, LoginError, Outbox, OutboxConfig, OutgoingMessage, PermissionSource,
+        Preflight, SessionStore, StoredSession, BASE_URL as HTTP_BASE_URL,
+    };
 
-    pub use crate::Rive;
+    pub use crate::{
+        error::{InstanceError, InstanceErrorKind},
+        resolve::BatchResolver,
+        Rive,
+    };
 
     pub use futures::StreamExt;
 }
@@ -89,7 +106,10 @@ pub mod prelude {
 pub struct Rive {
     pub http: rive_http::Client,
     pub gateway: Arc<Gateway>,
-    pub autumn: rive_autumn::Client,
+    /// Client of the file server, if the instance has one.
+    ///
+    /// See [`Rive::autumn`].
+    pub autumn: Option<rive_autumn::Client>,
     pub cache: Arc<InMemoryCache>,
 }
 
@@ -100,7 +120,7 @@ impl Rive {
     pub fn new(auth: Authentication) -> Self {
         let http = rive_http::Client::new(auth.clone());
         let gateway = Arc::new(Gateway::new(auth));
-        let autumn = rive_autumn::Client::new();
+        let autumn = Some(rive_autumn::Client::new());
         let cache = Arc::new(InMemoryCache::new());
 
         Self {
@@ -111,8 +131,144 @@ impl Rive {
         }
     }
 
+    /// Creates a new [`Rive`] for a self-hosted instance.
+    ///
+    /// The instance configuration is queried from the root node of the API at
+    /// `api_url`, and the gateway and Autumn clients are set up with the URLs
+    /// it advertises. Instances without a file server are supported, in which
+    /// case [`Rive::autumn`] fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of type [`InstanceErrorKind::QueryNode`] if the
+    /// instance configuration could not be queried.
+    pub async fn from_instance(
+        api_url: impl ToString,
+        auth: Authentication,
+    ) -> Result<Self, InstanceError> {
+        let http = rive_http::Client::new_base_url(auth.clone(), api_url);
+        let configuration = http.query_node().await.map_err(|source| {
+            InstanceError::new(InstanceErrorKind::QueryNode, Some(Box::new(source)))
+        })?;
+
+        let autumn = &configuration.features.autumn;
+        let autumn = autumn
+            .enabled
+            .then(|| rive_autumn::Client::new_base_url(&autumn.url));
+
+        Ok(Self {
+            http,
+            gateway: Arc::new(Gateway::with_url(configuration.ws, auth)),
+            autumn,
+            cache: Arc::new(InMemoryCache::new()),
+        })
+    }
+
+    /// Client of the file server.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of type [`InstanceErrorKind::AutumnDisabled`] if the
+    /// file server is disabled on the instance.
+    pub fn autumn(&self) -> Result<&rive_autumn::Client, InstanceError> {
+        self.autumn
+            .as_ref()
+            .ok_or_else(|| InstanceError::new(InstanceErrorKind::AutumnDisabled, None))
+    }
+
+    /// Create a resolver of many users or members at once, backed by the
+    /// cache.
+    pub fn resolver(&self) -> BatchResolver {
+        BatchResolver::new(self.http.clone(), Arc::clone(&self.cache))
+    }
+
     /// Handle an incoming event.
+    ///
+    /// The event updates the cache, and session events are passed to
+    /// [`rive_http::Client::handle_authifier_event`] so that a revoked
+    /// session is removed from the session store.
     pub fn update(&self, event: &ServerEvent) {
         self.cache.update(event);
+        self.handle_session_events(event);
+    }
+
+    fn handle_session_events(&self, event: &ServerEvent) {
+        match event {
+            ServerEvent::Auth(event) => {
+                if let Err(error) = self.http.handle_authifier_event(event) {
+                    warn!(%error, "failed to handle session event");
+                }
+            }
+            ServerEvent::Bulk(bulk) => {
+                for event in &bulk.v {
+                    self.handle_session_events(event);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use rive_http::{
+        test_util::FakeServer, Client, Config, InMemorySessionStore, SessionStore, StoredSession,
+    };
+    use rive_models::{
+        authentication::Authentication,
+        event::{AuthifierEvent, BulkEvent, ServerEvent},
+        id::Id,
+    };
+
+    use crate::{error::InstanceErrorKind, Rive};
+
+    #[test]
+    fn update_clears_revoked_session() {
+        let store = Arc::new(InMemorySessionStore::new());
+        let session = StoredSession {
+            id: Id::new("01H9F3XJ2C2K6G8B4Y7Q9M0S1T".to_string()),
+            user_id: Id::new("01H9F3XJ2C2K6G8B4Y7Q9M0S1U".to_string()),
+            token: "token".to_string(),
+        };
+        store.save(&session).unwrap();
+
+        let mut rive = Rive::new(Authentication::None);
+        rive.http = Client::with_config(Config {
+            session_store: Some(store.clone()),
+            ..Config::new(Authentication::None)
+        });
+
+        rive.update(&ServerEvent::Bulk(BulkEvent {
+            v: vec![ServerEvent::Auth(AuthifierEvent::DeleteSession {
+                user_id: session.user_id.clone(),
+                session_id: session.id.clone(),
+            })],
+        }));
+        assert_eq!(store.load().unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn from_instance() {
+        let server = FakeServer::start().await.unwrap();
+
+        let rive = Rive::from_instance(server.url(), Authentication::None)
+            .await
+            .unwrap();
+        assert!(rive.autumn().is_ok());
+
+        server.set_autumn_enabled(false);
+        let rive = Rive::from_instance(server.url(), Authentication::None)
+            .await
+            .unwrap();
+        let error = rive.autumn().unwrap_err();
+        assert!(matches!(error.kind(), InstanceErrorKind::AutumnDisabled));
+
+        drop(server);
+        let error = Rive::from_instance("http://127.0.0.1:1", Authentication::None)
+            .await
+            .unwrap_err();
+        assert!(matches!(error.kind(), InstanceErrorKind::QueryNode));
     }
 }
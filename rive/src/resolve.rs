@@ -0,0 +1,199 @@
+//! Cache-aware batch resolution of users and members.
+
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    hash::Hash,
+    sync::Arc,
+};
+
+use futures::{stream, StreamExt};
+use rive_cache_inmemory::InMemoryCache;
+use rive_http::{Client, Error};
+use rive_models::{
+    error::ApiError,
+    id::{marker::UserMarker, Id},
+    member::{Member, MemberCompositeKey},
+    user::User,
+};
+
+/// Default maximum amount of concurrent requests of a [`BatchResolver`].
+pub const DEFAULT_CONCURRENCY: usize = 8;
+
+/// Resolver of many users or members at once.
+///
+/// Resources found in the cache are returned as is. The others are fetched
+/// from the API, at most [`concurrency`] at a time, and inserted into the
+/// cache.
+///
+/// Resources that do not exist are left out of the returned map.
+///
+/// [`concurrency`]: BatchResolver::concurrency
+#[derive(Debug, Clone)]
+pub struct BatchResolver {
+    http: Client,
+    cache: Arc<InMemoryCache>,
+    concurrency: usize,
+}
+
+impl BatchResolver {
+    /// Create a resolver with [`DEFAULT_CONCURRENCY`].
+    pub fn new(http: Client, cache: Arc<InMemoryCache>) -> Self {
+        Self {
+            http,
+            cache,
+            concurrency: DEFAULT_CONCURRENCY,
+        }
+    }
+
+    /// Set the maximum amount of concurrent requests.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the concurrency is zero.
+    pub const fn concurrency(mut self, concurrency: usize) -> Self {
+        assert!(concurrency > 0, "concurrency must not be zero");
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Resolve users by ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of a request, other than a resource not being
+    /// found.
+    pub async fn users(
+        &self,
+        ids: impl IntoIterator<Item = Id<UserMarker>>,
+    ) -> Result<HashMap<Id<UserMarker>, User>, Error> {
+        let http = &self.http;
+
+        self.resolve(
+            ids,
+            |id| self.cache.user(id).map(|user| user.clone()),
+            |id| async move { http.fetch_user(&id).await },
+            |user| self.cache.insert_user(user),
+        )
+        .await
+    }
+
+    /// Resolve members by [`MemberCompositeKey`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of a request, other than a resource not being
+    /// found.
+    pub async fn members(
+        &self,
+        keys: impl IntoIterator<Item = MemberCompositeKey>,
+    ) -> Result<HashMap<MemberCompositeKey, Member>, Error> {
+        let http = &self.http;
+
+        self.resolve(
+            keys,
+            |key| self.cache.member(key).map(|member| member.clone()),
+            |key| async move { http.fetch_member(&key.server, &key.user).await },
+            |member| self.cache.insert_member(member),
+        )
+        .await
+    }
+
+    async fn resolve<K, V, Fut>(
+        &self,
+        keys: impl IntoIterator<Item = K>,
+        cached: impl Fn(&K) -> Option<V>,
+        fetch: impl Fn(K) -> Fut,
+        insert: impl Fn(V),
+    ) -> Result<HashMap<K, V>, Error>
+    where
+        K: Eq + Hash + Clone,
+        V: Clone,
+        Fut: Future<Output = Result<V, Error>>,
+    {
+        let mut resolved = HashMap::new();
+        let mut missing = Vec::new();
+
+        for key in keys.into_iter().collect::<HashSet<_>>() {
+            match cached(&key) {
+                Some(value) => {
+                    resolved.insert(key, value);
+                }
+                None => missing.push(key),
+            }
+        }
+
+        let mut fetched = stream::iter(missing)
+            .map(|key| {
+                let fetch = fetch(key.clone());
+                async move { (key, fetch.await) }
+            })
+            .buffer_unordered(self.concurrency);
+
+        while let Some((key, result)) = fetched.next().await {
+            match result {
+                Ok(value) => {
+                    insert(value.clone());
+                    resolved.insert(key, value);
+                }
+                Err(Error::Api(ApiError::NotFound)) => {}
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(resolved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use rive_cache_inmemory::InMemoryCache;
+    use rive_http::test_util::FakeServer;
+    use rive_models::{data::CreateServerData, id::Id, member::MemberCompositeKey};
+
+    use super::BatchResolver;
+
+    #[tokio::test]
+    async fn resolves_misses_once() {
+        let server = FakeServer::start().await.unwrap();
+        let owner = server.add_bot("owner");
+        let first = server.add_user("first");
+        let second = server.add_user("second");
+        let http = server.client(owner.authentication.clone());
+        let created = http
+            .create_server(&CreateServerData {
+                name: "Resolve".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        let cache = Arc::new(InMemoryCache::new());
+        cache.insert_user(http.fetch_self().await.unwrap());
+        let resolver = BatchResolver::new(http, Arc::clone(&cache)).concurrency(2);
+
+        let unknown = Id::new("01H9F3XJ2C2K6G8B4Y7Q9M0S1T".to_string());
+        let ids = [&owner.id, &first.id, &second.id, &first.id, &unknown];
+
+        let requests = server.request_count();
+        let users = resolver.users(ids.map(Clone::clone)).await.unwrap();
+        assert_eq!(users.len(), 3);
+        assert_eq!(users[&second.id].username, "second");
+        assert_eq!(server.request_count(), requests + 3);
+        assert!(cache.user(&first.id).is_some());
+
+        let requests = server.request_count();
+        resolver.users([first.id, second.id]).await.unwrap();
+        assert_eq!(server.request_count(), requests);
+
+        let key = MemberCompositeKey {
+            server: created.id,
+            user: owner.id,
+        };
+        let members = resolver.members([key.clone()]).await.unwrap();
+        assert_eq!(members[&key].id, key);
+        assert!(cache.member(&key).is_some());
+    }
+}